@@ -0,0 +1,164 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
+
+use crate::error::ContractError;
+use crate::execute::create_deal::execute_create_deal;
+use crate::execute::execute_deal::execute_execute_deal;
+use crate::execute::sync_deal::execute_sync_deal;
+use crate::execute::update_code_id::execute_update_code_id;
+use crate::instantiate::instantiate_contract::instantiate_contract;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::query::deal::{query_deal, query_deal_address};
+use crate::query::deals::{query_deals, DealFilter};
+use crate::storage::state_store::retrieve_contract_config;
+use crate::version_info::{set_version_info, VersionInfoV1, CRATE_NAME, PACKAGE_VERSION};
+
+/// The entry point used when an account instantiates a stored code wasm payload of the factory on
+/// the Provenance Blockchain.
+///
+/// # Parameters
+///
+/// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
+/// * `_env` An environment object provided by the cosmwasm framework.  Unused by this function.
+/// * `info` A message information object provided by the cosmwasm framework.  Describes the sender
+///   of the instantiation message, as well as the funds provided as an amount during the transaction.
+/// * `msg` A custom instantiation message defined by the factory for creating the initial
+///   configuration used by the factory.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    instantiate_contract(deps, info, msg)
+}
+
+/// The entry point used when an account initiates an execution process defined in the factory.
+///
+/// # Parameters
+///
+/// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
+/// * `env` An environment object provided by the cosmwasm framework.  Describes the contract's
+///   details, as well as blockchain information at the time of the transaction.
+/// * `info` A message information object provided by the cosmwasm framework.  Describes the sender
+///   of the execution message, as well as the funds provided as an amount during the transaction.
+/// * `msg` A custom execution message enum defined by the factory that will map the desired
+///   operation to the proper contract logic.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateDeal { label, salt, deal } => {
+            execute_create_deal(deps, env, info, label, salt, *deal)
+        }
+        ExecuteMsg::SyncDeal { deal_address } => execute_sync_deal(deps, deal_address),
+        ExecuteMsg::ExecuteDeal { deal_address, msg } => {
+            execute_execute_deal(deps, info, deal_address, *msg)
+        }
+        ExecuteMsg::UpdateCodeId {
+            forward_market_code_id,
+        } => execute_update_code_id(deps, info, forward_market_code_id),
+    }
+}
+
+/// The entry point used when an account invokes the factory to retrieve information.  Allows
+/// read-only access to the registry of deals.
+///
+/// # Parameters
+///
+/// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
+/// * `env` An environment object provided by the cosmwasm framework.  Describes the contract's
+///   details, as well as blockchain information at the time of the query.
+/// * `msg` A custom query message enum defined by the factory that will map the desired query
+///   to the proper contract logic
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::GetConfig {} => Ok(to_json_binary(&retrieve_contract_config(deps.storage)?)?),
+        QueryMsg::GetDeal { deal_address } => Ok(to_json_binary(&query_deal(deps, deal_address)?)?),
+        QueryMsg::GetDealAddress { salt } => {
+            Ok(to_json_binary(&query_deal_address(deps, env, salt)?)?)
+        }
+        QueryMsg::GetDeals { start_after, limit } => Ok(to_json_binary(&query_deals(
+            deps,
+            DealFilter::All,
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::GetDealsBySeller {
+            seller,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_deals(
+            deps,
+            DealFilter::Seller(seller),
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::GetDealsByBuyer {
+            buyer,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_deals(
+            deps,
+            DealFilter::Buyer(buyer),
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::GetDealsByDealer {
+            dealer,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_deals(
+            deps,
+            DealFilter::Dealer(dealer),
+            start_after,
+            limit,
+        )?)?),
+        QueryMsg::GetDealsByStatus {
+            status,
+            start_after,
+            limit,
+        } => Ok(to_json_binary(&query_deals(
+            deps,
+            DealFilter::Status(status),
+            start_after,
+            limit,
+        )?)?),
+    }
+}
+
+/// The entry point used when the factory admin migrates the factory to a new stored code instance
+/// on chain.
+///
+/// # Parameters
+///
+/// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
+/// * `_env` An environment object provided by the cosmwasm framework.  Unused by this function.
+/// * `msg` A custom migration message defined by the factory that will map the desired operation
+///   to the proper contract logic.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::ContractUpgrade {} => {
+            set_version_info(
+                deps.storage,
+                &VersionInfoV1 {
+                    version: PACKAGE_VERSION.to_string(),
+                    definition: CRATE_NAME.to_string(),
+                },
+            )?;
+            Ok(Response::new())
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use cosmwasm_std::{Instantiate2AddressError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    /// Occurs when an error is encountered during contract store communication.
+    #[error("Contract storage error occurred: {message}")]
+    StorageError {
+        /// A message describing the nature of the error.
+        message: String,
+    },
+
+    /// Occurs when the deal address cannot be derived from the factory address and the salt
+    #[error("Unable to derive the deal address: {0}")]
+    Instantiate2Address(#[from] Instantiate2AddressError),
+
+    /// Occurs when an action must be performed by the factory admin but is attempted by a different address
+    #[error("Action must be performed by the factory admin")]
+    UnauthorizedAsFactoryAdmin,
+
+    /// Occurs when an action must be performed by the creator of the deal but is attempted by a different address
+    #[error("Action must be performed by the creator of the deal")]
+    UnauthorizedAsDealCreator,
+
+    /// Occurs when the code id of the forward market contract is set to zero
+    #[error("The forward market code id must be greater than zero")]
+    InvalidCodeId,
+
+    /// Occurs when a deal is created with a label that is empty or starts or ends with whitespace
+    #[error("The deal label cannot be empty or start or end with whitespace")]
+    InvalidDealLabel,
+
+    /// Occurs when a deal is created with a salt that has already been used by another deal
+    #[error("A deal already exists at address {deal_address}")]
+    DealAlreadyExists { deal_address: String },
+
+    /// Occurs when an address that was not created by the factory is used as a deal
+    #[error("No deal has been registered at address {deal_address}")]
+    UnknownDeal { deal_address: String },
+}
@@ -0,0 +1,59 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{DealAlreadyExists, InvalidDealLabel};
+use crate::storage::state_store::{
+    retrieve_contract_config, retrieve_optional_deal_state, save_deal_state, Deal, DealStatus,
+};
+use crate::util::helpers::derive_deal_address;
+use cosmwasm_std::{to_json_binary, Addr, Binary, DepsMut, Env, MessageInfo, Response, WasmMsg};
+use forward_market_contract::msg::InstantiateContractMsg;
+
+pub fn execute_create_deal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    label: String,
+    salt: Binary,
+    deal_msg: InstantiateContractMsg,
+) -> Result<Response, ContractError> {
+    // The chain rejects labels that are empty or padded with whitespace
+    if label.is_empty() || label.trim() != label {
+        return Err(InvalidDealLabel);
+    }
+
+    let config = retrieve_contract_config(deps.storage)?;
+    let deal_address =
+        derive_deal_address(deps.as_ref(), &env, config.forward_market_code_id, &salt)?;
+    if retrieve_optional_deal_state(deps.storage, &deal_address)?.is_some() {
+        return Err(DealAlreadyExists {
+            deal_address: deal_address.to_string(),
+        });
+    }
+
+    let dealers = deal_msg
+        .dealers
+        .iter()
+        .map(|dealer| deps.api.addr_validate(dealer))
+        .collect::<Result<Vec<Addr>, _>>()?;
+    let deal = Deal {
+        deal_address,
+        creator: info.sender,
+        label: label.clone(),
+        code_id: config.forward_market_code_id,
+        sellers: vec![],
+        buyers: vec![],
+        dealers,
+        status: DealStatus::Open,
+    };
+    save_deal_state(deps.storage, &deal)?;
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Instantiate2 {
+            admin: Some(config.admin.to_string()),
+            code_id: config.forward_market_code_id,
+            label,
+            msg: to_json_binary(&deal_msg)?,
+            funds: vec![],
+            salt,
+        })
+        .add_attribute("deal", format!("{:?}", deal)))
+}
@@ -0,0 +1,26 @@
+use crate::error::ContractError;
+use crate::error::ContractError::UnauthorizedAsDealCreator;
+use crate::storage::state_store::retrieve_deal_state;
+use cosmwasm_std::{to_json_binary, DepsMut, MessageInfo, Response, WasmMsg};
+use forward_market_contract::msg::ExecuteMsg as DealExecuteMsg;
+
+pub fn execute_execute_deal(
+    deps: DepsMut,
+    info: MessageInfo,
+    deal_address: String,
+    msg: DealExecuteMsg,
+) -> Result<Response, ContractError> {
+    let deal_address = deps.api.addr_validate(&deal_address)?;
+    let deal = retrieve_deal_state(deps.storage, &deal_address)?;
+    if info.sender != deal.creator {
+        return Err(UnauthorizedAsDealCreator);
+    }
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: deal_address.to_string(),
+            msg: to_json_binary(&msg)?,
+            funds: info.funds,
+        })
+        .add_attribute("deal_address", deal_address.to_string()))
+}
@@ -0,0 +1,4 @@
+pub mod create_deal;
+pub mod execute_deal;
+pub mod sync_deal;
+pub mod update_code_id;
@@ -0,0 +1,37 @@
+use crate::error::ContractError;
+use crate::storage::state_store::{retrieve_deal_state, save_deal_state, DealStatus};
+use cosmwasm_std::{DepsMut, Response};
+use forward_market_contract::msg::{GetContractStateResponse, QueryMsg};
+
+pub fn execute_sync_deal(deps: DepsMut, deal_address: String) -> Result<Response, ContractError> {
+    let deal_address = deps.api.addr_validate(&deal_address)?;
+    let mut deal = retrieve_deal_state(deps.storage, &deal_address)?;
+
+    let deal_state: GetContractStateResponse = deps.querier.query_wasm_smart(
+        &deal_address,
+        &QueryMsg::GetContractState { trade_id: None },
+    )?;
+    deal.sellers = deal_state
+        .sellers
+        .into_iter()
+        .map(|seller| seller.seller_address)
+        .collect();
+    deal.buyers = deal_state
+        .buyers
+        .into_iter()
+        .map(|buyer| buyer.buyer_address)
+        .collect();
+    deal.dealers = deal_state.config.dealers;
+    deal.status = if deal_state.settlement_data.is_some() {
+        DealStatus::Settled
+    } else if deal_state.config.is_disabled {
+        DealStatus::Disabled
+    } else if !deal.buyers.is_empty() {
+        DealStatus::BidAccepted
+    } else {
+        DealStatus::Open
+    };
+    save_deal_state(deps.storage, &deal)?;
+
+    Ok(Response::new().add_attribute("deal", format!("{:?}", deal)))
+}
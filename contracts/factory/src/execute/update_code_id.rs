@@ -0,0 +1,23 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{InvalidCodeId, UnauthorizedAsFactoryAdmin};
+use crate::storage::state_store::{retrieve_contract_config, save_contract_config};
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+pub fn execute_update_code_id(
+    deps: DepsMut,
+    info: MessageInfo,
+    forward_market_code_id: u64,
+) -> Result<Response, ContractError> {
+    let mut config = retrieve_contract_config(deps.storage)?;
+    if info.sender != config.admin {
+        return Err(UnauthorizedAsFactoryAdmin);
+    }
+    if forward_market_code_id == 0 {
+        return Err(InvalidCodeId);
+    }
+
+    config.forward_market_code_id = forward_market_code_id;
+    save_contract_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("contract_config", format!("{:?}", config)))
+}
@@ -0,0 +1,33 @@
+use crate::error::ContractError;
+use crate::error::ContractError::InvalidCodeId;
+use crate::msg::InstantiateMsg;
+use crate::storage::state_store::{save_contract_config, Config};
+use crate::version_info::{set_version_info, VersionInfoV1, CRATE_NAME, PACKAGE_VERSION};
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+pub fn instantiate_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    // Stored code ids start at one
+    if msg.forward_market_code_id == 0 {
+        return Err(InvalidCodeId);
+    }
+
+    let config = Config {
+        admin: info.sender,
+        forward_market_code_id: msg.forward_market_code_id,
+    };
+    save_contract_config(deps.storage, &config)?;
+
+    set_version_info(
+        deps.storage,
+        &VersionInfoV1 {
+            version: PACKAGE_VERSION.to_string(),
+            definition: CRATE_NAME.to_string(),
+        },
+    )?;
+
+    Ok(Response::new().add_attribute("contract_config", format!("{:?}", config)))
+}
@@ -0,0 +1 @@
+pub mod instantiate_contract;
@@ -0,0 +1,27 @@
+//! Forward Market Factory Smart Contract
+//!
+//! This contract stores the code id of the forward market contract and instantiates a new forward
+//! market deal for each trade. Deals are created with `Instantiate2`, so the address of a deal can
+//! be derived from the factory address and the salt before it is created. The factory keeps a
+//! registry of every deal it has created, indexed by the sellers, buyers and dealers of the deal as
+//! well as its status, so that deals can be discovered without tracking every address off chain.
+
+/// The entry point of all commands sent to the contract
+pub mod contract;
+// The custom errors that the contract can return
+pub mod error;
+// All commands that can be executed by the contract
+mod execute;
+// Defines the contract instantiation
+mod instantiate;
+// Defines the messages that map to execution logic
+pub mod msg;
+// Defines the contract query process
+mod query;
+// Defines the state storage for the contract
+pub mod storage;
+// Defines the tests for the contract
+mod tests;
+// Utility methods that give access to shared logic
+mod util;
+pub mod version_info;
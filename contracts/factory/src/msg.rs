@@ -0,0 +1,106 @@
+use crate::storage::state_store::{Config, Deal, DealStatus};
+use cosmwasm_schema::QueryResponses;
+use cosmwasm_std::{Addr, Binary};
+use forward_market_contract::msg::{
+    ExecuteMsg as DealExecuteMsg, InstantiateContractMsg as DealInstantiateMsg,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The msg that is sent to the chain in order to instantiate a new instance of the factory.  Used in
+/// the functionality defined in [instantiate_contract](crate::instantiate::instantiate_contract::instantiate_contract).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// The stored code id of the forward market contract used to instantiate new deals
+    pub forward_market_code_id: u64,
+}
+
+/// All defined payloads to be used when executing routes on the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ExecuteMsg {
+    /// A route that instantiates a new forward market deal at the address derived from the factory
+    /// address and the salt, and adds it to the registry. The sender is recorded as the creator of
+    /// the deal and the factory admin can migrate it
+    CreateDeal {
+        label: String,
+        salt: Binary,
+        deal: Box<DealInstantiateMsg>,
+    },
+    /// A route that refreshes the sellers, buyers, dealers and status of a deal in the registry
+    /// from the state of the deal contract
+    SyncDeal { deal_address: String },
+    /// A route that allows the creator of a deal to execute admin routes on the deal, since the
+    /// factory is the admin of every deal it creates. Any funds sent are forwarded to the deal
+    ExecuteDeal {
+        deal_address: String,
+        msg: Box<DealExecuteMsg>,
+    },
+    /// A route that allows the factory admin to change the code id used for new deals
+    UpdateCodeId { forward_market_code_id: u64 },
+}
+
+/// All defined payloads to be used when querying the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, QueryResponses)]
+pub enum QueryMsg {
+    /// A route used to read the factory configuration
+    #[returns(Config)]
+    GetConfig {},
+    /// A route used to read a single deal from the registry
+    #[returns(Deal)]
+    GetDeal { deal_address: String },
+    /// A route used to derive the address a deal created with the salt will have
+    #[returns(GetDealAddressResponse)]
+    GetDealAddress { salt: Binary },
+    /// A route used to page through every deal in the registry, ordered by deal address
+    #[returns(GetDealsResponse)]
+    GetDeals {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// A route used to page through the deals the address is a seller on
+    #[returns(GetDealsResponse)]
+    GetDealsBySeller {
+        seller: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// A route used to page through the deals the address is a buyer on
+    #[returns(GetDealsResponse)]
+    GetDealsByBuyer {
+        buyer: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// A route used to page through the deals the address is a dealer on
+    #[returns(GetDealsResponse)]
+    GetDealsByDealer {
+        dealer: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// A route used to page through the deals with the status
+    #[returns(GetDealsResponse)]
+    GetDealsByStatus {
+        status: DealStatus,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+/// All defined payloads to be used when migrating to a new instance of the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMsg {
+    /// The standard migration route that updates the version info of the factory
+    ContractUpgrade {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GetDealAddressResponse {
+    pub deal_address: Addr,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GetDealsResponse {
+    pub deals: Vec<Deal>,
+}
@@ -0,0 +1,20 @@
+use crate::error::ContractError;
+use crate::msg::GetDealAddressResponse;
+use crate::storage::state_store::{retrieve_contract_config, retrieve_deal_state, Deal};
+use crate::util::helpers::derive_deal_address;
+use cosmwasm_std::{Binary, Deps, Env};
+
+pub fn query_deal(deps: Deps, deal_address: String) -> Result<Deal, ContractError> {
+    let deal_address = deps.api.addr_validate(&deal_address)?;
+    retrieve_deal_state(deps.storage, &deal_address)
+}
+
+pub fn query_deal_address(
+    deps: Deps,
+    env: Env,
+    salt: Binary,
+) -> Result<GetDealAddressResponse, ContractError> {
+    let config = retrieve_contract_config(deps.storage)?;
+    let deal_address = derive_deal_address(deps, &env, config.forward_market_code_id, &salt)?;
+    Ok(GetDealAddressResponse { deal_address })
+}
@@ -0,0 +1,46 @@
+use crate::error::ContractError;
+use crate::msg::GetDealsResponse;
+use crate::storage::state_store::{
+    retrieve_deal_page, retrieve_deal_page_by_buyer, retrieve_deal_page_by_dealer,
+    retrieve_deal_page_by_seller, retrieve_deal_page_by_status, DealStatus,
+};
+use cosmwasm_std::{Addr, Deps};
+
+/// The party a page of deals is filtered by
+pub enum DealFilter {
+    All,
+    Seller(String),
+    Buyer(String),
+    Dealer(String),
+    Status(DealStatus),
+}
+
+pub fn query_deals(
+    deps: Deps,
+    filter: DealFilter,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> Result<GetDealsResponse, ContractError> {
+    let start_after = start_after
+        .map(|deal_address| deps.api.addr_validate(&deal_address))
+        .transpose()?;
+    let start_after = start_after.as_ref();
+    let validate =
+        |address: String| -> Result<Addr, ContractError> { Ok(deps.api.addr_validate(&address)?) };
+    let deals = match filter {
+        DealFilter::All => retrieve_deal_page(deps.storage, start_after, limit)?,
+        DealFilter::Seller(seller) => {
+            retrieve_deal_page_by_seller(deps.storage, &validate(seller)?, start_after, limit)?
+        }
+        DealFilter::Buyer(buyer) => {
+            retrieve_deal_page_by_buyer(deps.storage, &validate(buyer)?, start_after, limit)?
+        }
+        DealFilter::Dealer(dealer) => {
+            retrieve_deal_page_by_dealer(deps.storage, &validate(dealer)?, start_after, limit)?
+        }
+        DealFilter::Status(status) => {
+            retrieve_deal_page_by_status(deps.storage, status, start_after, limit)?
+        }
+    };
+    Ok(GetDealsResponse { deals })
+}
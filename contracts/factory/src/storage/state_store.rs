@@ -0,0 +1,257 @@
+use crate::error::ContractError;
+use crate::error::ContractError::StorageError;
+use cosmwasm_std::{Addr, Order, Storage};
+use cw_storage_plus::{Bound, Item, Map};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The number of deals returned by a paginated query when no limit is given
+pub const DEFAULT_PAGE_LIMIT: u32 = 10;
+/// The largest number of deals a paginated query can return
+pub const MAX_PAGE_LIMIT: u32 = 30;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Config {
+    pub admin: Addr,
+    pub forward_market_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+pub enum DealStatus {
+    /// No bid has been accepted on the deal
+    Open,
+    /// A bid has been accepted and the deal is waiting for the dealer to settle it
+    BidAccepted,
+    /// The dealer has settled the deal
+    Settled,
+    /// The deal has been disabled
+    Disabled,
+}
+
+impl DealStatus {
+    pub fn to_str(&self) -> &str {
+        match self {
+            DealStatus::Open => "open",
+            DealStatus::BidAccepted => "bid_accepted",
+            DealStatus::Settled => "settled",
+            DealStatus::Disabled => "disabled",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct Deal {
+    pub deal_address: Addr,
+    pub creator: Addr,
+    pub label: String,
+    pub code_id: u64,
+    pub sellers: Vec<Addr>,
+    pub buyers: Vec<Addr>,
+    pub dealers: Vec<Addr>,
+    pub status: DealStatus,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const DEALS: Map<&Addr, Deal> = Map::new("deals");
+pub const DEALS_BY_SELLER: Map<(&Addr, &Addr), ()> = Map::new("deals_by_seller");
+pub const DEALS_BY_BUYER: Map<(&Addr, &Addr), ()> = Map::new("deals_by_buyer");
+pub const DEALS_BY_DEALER: Map<(&Addr, &Addr), ()> = Map::new("deals_by_dealer");
+pub const DEALS_BY_STATUS: Map<(&str, &Addr), ()> = Map::new("deals_by_status");
+
+pub fn save_contract_config(
+    storage: &mut dyn Storage,
+    config: &Config,
+) -> Result<(), ContractError> {
+    CONFIG.save(storage, config).map_err(|e| StorageError {
+        message: format!("{e:?}"),
+    })
+}
+
+pub fn retrieve_contract_config(storage: &dyn Storage) -> Result<Config, ContractError> {
+    CONFIG.load(storage).map_err(|e| StorageError {
+        message: format!("{e:?}"),
+    })
+}
+
+pub fn retrieve_optional_deal_state(
+    storage: &dyn Storage,
+    deal_address: &Addr,
+) -> Result<Option<Deal>, ContractError> {
+    DEALS
+        .may_load(storage, deal_address)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_deal_state(
+    storage: &dyn Storage,
+    deal_address: &Addr,
+) -> Result<Deal, ContractError> {
+    retrieve_optional_deal_state(storage, deal_address)?.ok_or(ContractError::UnknownDeal {
+        deal_address: deal_address.to_string(),
+    })
+}
+
+/// Saves the deal and replaces the index entries of any previously saved version of it
+pub fn save_deal_state(storage: &mut dyn Storage, deal: &Deal) -> Result<(), ContractError> {
+    if let Some(previous_deal) = retrieve_optional_deal_state(storage, &deal.deal_address)? {
+        update_deal_indexes(storage, &previous_deal, false)?;
+    }
+    DEALS
+        .save(storage, &deal.deal_address, deal)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })?;
+    update_deal_indexes(storage, deal, true)
+}
+
+pub fn retrieve_deal_page(
+    storage: &dyn Storage,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> Result<Vec<Deal>, ContractError> {
+    DEALS
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(page_limit(limit))
+        .map(|entry| entry.map(|(_, deal)| deal))
+        .collect::<Result<Vec<Deal>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_deal_page_by_seller(
+    storage: &dyn Storage,
+    seller_address: &Addr,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> Result<Vec<Deal>, ContractError> {
+    retrieve_deal_page_by_address(
+        storage,
+        &DEALS_BY_SELLER,
+        seller_address,
+        start_after,
+        limit,
+    )
+}
+
+pub fn retrieve_deal_page_by_buyer(
+    storage: &dyn Storage,
+    buyer_address: &Addr,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> Result<Vec<Deal>, ContractError> {
+    retrieve_deal_page_by_address(storage, &DEALS_BY_BUYER, buyer_address, start_after, limit)
+}
+
+pub fn retrieve_deal_page_by_dealer(
+    storage: &dyn Storage,
+    dealer_address: &Addr,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> Result<Vec<Deal>, ContractError> {
+    retrieve_deal_page_by_address(
+        storage,
+        &DEALS_BY_DEALER,
+        dealer_address,
+        start_after,
+        limit,
+    )
+}
+
+pub fn retrieve_deal_page_by_status(
+    storage: &dyn Storage,
+    status: DealStatus,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> Result<Vec<Deal>, ContractError> {
+    let deal_addresses = DEALS_BY_STATUS
+        .prefix(status.to_str())
+        .keys(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(page_limit(limit))
+        .collect::<Result<Vec<Addr>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })?;
+    deal_addresses
+        .iter()
+        .map(|deal_address| retrieve_deal_state(storage, deal_address))
+        .collect()
+}
+
+fn retrieve_deal_page_by_address(
+    storage: &dyn Storage,
+    index: &Map<(&Addr, &Addr), ()>,
+    address: &Addr,
+    start_after: Option<&Addr>,
+    limit: Option<u32>,
+) -> Result<Vec<Deal>, ContractError> {
+    let deal_addresses = index
+        .prefix(address)
+        .keys(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(page_limit(limit))
+        .collect::<Result<Vec<Addr>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })?;
+    deal_addresses
+        .iter()
+        .map(|deal_address| retrieve_deal_state(storage, deal_address))
+        .collect()
+}
+
+/// Adds or removes the index entries that point at the deal
+fn update_deal_indexes(
+    storage: &mut dyn Storage,
+    deal: &Deal,
+    is_added: bool,
+) -> Result<(), ContractError> {
+    let deal_address = &deal.deal_address;
+    for (index, addresses) in [
+        (DEALS_BY_SELLER, &deal.sellers),
+        (DEALS_BY_BUYER, &deal.buyers),
+        (DEALS_BY_DEALER, &deal.dealers),
+    ] {
+        for address in addresses {
+            if is_added {
+                index
+                    .save(storage, (address, deal_address), &())
+                    .map_err(|e| StorageError {
+                        message: format!("{e:?}"),
+                    })?;
+            } else {
+                index.remove(storage, (address, deal_address));
+            }
+        }
+    }
+    if is_added {
+        DEALS_BY_STATUS
+            .save(storage, (deal.status.to_str(), deal_address), &())
+            .map_err(|e| StorageError {
+                message: format!("{e:?}"),
+            })?;
+    } else {
+        DEALS_BY_STATUS.remove(storage, (deal.status.to_str(), deal_address));
+    }
+    Ok(())
+}
+
+fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize
+}
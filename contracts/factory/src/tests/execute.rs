@@ -0,0 +1,2 @@
+mod execute_create_deal;
+mod execute_sync_deal;
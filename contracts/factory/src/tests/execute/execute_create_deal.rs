@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod execute_create_deal_tests {
+    use crate::contract::{execute, instantiate, query};
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::CreateDeal;
+    use crate::msg::QueryMsg::{GetDeal, GetDealAddress, GetDealsByDealer};
+    use crate::msg::{GetDealAddressResponse, GetDealsResponse, InstantiateMsg};
+    use crate::storage::state_store::{Deal, DealStatus};
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        from_json, to_json_binary, Addr, Binary, Checksum, CodeInfoResponse, ContractResult,
+        CosmosMsg, SystemError, SystemResult, WasmMsg, WasmQuery,
+    };
+    use forward_market_contract::msg::InstantiateContractMsg;
+
+    #[test]
+    fn create_deal_at_derived_address() {
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::CodeInfo { code_id } => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&CodeInfoResponse::new(
+                    *code_id,
+                    Addr::unchecked("code-creator"),
+                    Checksum::from([7u8; 32]),
+                ))
+                .unwrap(),
+            )),
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_string(),
+            }),
+        });
+        let factory_admin = deps.api.addr_make("factory-admin");
+        let deal_creator = deps.api.addr_make("deal-creator");
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let mut env = mock_env();
+        env.contract.address = deps.api.addr_make("forward-market-factory");
+        let salt = Binary::from(b"deal-1".to_vec());
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&factory_admin, &[]),
+            InstantiateMsg {
+                forward_market_code_id: 7,
+            },
+        )
+        .unwrap();
+
+        let deal_msg = InstantiateContractMsg {
+            use_private_sellers: false,
+            use_private_buyers: false,
+            allowed_sellers: vec![],
+            allowed_buyers: vec![],
+            max_buyer_count: None,
+            dealers: vec![dealer_address.to_string()],
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
+        };
+        let create_deal_msg = CreateDeal {
+            label: "Forward Trade 1".to_string(),
+            salt: salt.clone(),
+            deal: Box::new(deal_msg.clone()),
+        };
+
+        let response = execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&deal_creator, &[]),
+            create_deal_msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            response.messages[0].msg,
+            CosmosMsg::Wasm(WasmMsg::Instantiate2 {
+                admin: Some(factory_admin.to_string()),
+                code_id: 7,
+                label: "Forward Trade 1".to_string(),
+                msg: to_json_binary(&deal_msg).unwrap(),
+                funds: vec![],
+                salt: salt.clone(),
+            })
+        );
+
+        let deal_address: GetDealAddressResponse =
+            from_json(query(deps.as_ref(), env.clone(), GetDealAddress { salt }).unwrap()).unwrap();
+        let expected_deal = Deal {
+            deal_address: deal_address.deal_address.clone(),
+            creator: deal_creator.clone(),
+            label: "Forward Trade 1".to_string(),
+            code_id: 7,
+            sellers: vec![],
+            buyers: vec![],
+            dealers: vec![dealer_address.clone()],
+            status: DealStatus::Open,
+        };
+        let deal: Deal = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetDeal {
+                    deal_address: deal_address.deal_address.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(expected_deal, deal);
+
+        let dealer_deals: GetDealsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetDealsByDealer {
+                    dealer: dealer_address.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(vec![expected_deal], dealer_deals.deals);
+
+        // The same salt cannot be used for a second deal
+        match execute(
+            deps.as_mut(),
+            env,
+            message_info(&deal_creator, &[]),
+            create_deal_msg,
+        ) {
+            Ok(_) => panic!("expected error, but ok"),
+            Err(error) => match error {
+                ContractError::DealAlreadyExists {
+                    deal_address: address,
+                } => {
+                    assert_eq!(deal_address.deal_address.to_string(), address)
+                }
+                error => panic!("unexpected error: {:?}", error),
+            },
+        }
+    }
+
+    #[test]
+    fn create_deal_with_invalid_label() {
+        let mut deps = mock_dependencies();
+        let factory_admin = deps.api.addr_make("factory-admin");
+        let deal_creator = deps.api.addr_make("deal-creator");
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let env = mock_env();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&factory_admin, &[]),
+            InstantiateMsg {
+                forward_market_code_id: 7,
+            },
+        )
+        .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            env,
+            message_info(&deal_creator, &[]),
+            CreateDeal {
+                label: " Forward Trade 1".to_string(),
+                salt: Binary::from(b"deal-1".to_vec()),
+                deal: Box::new(InstantiateContractMsg {
+                    use_private_sellers: false,
+                    use_private_buyers: false,
+                    allowed_sellers: vec![],
+                    allowed_buyers: vec![],
+                    max_buyer_count: None,
+                    dealers: vec![dealer_address.to_string()],
+                    auction: None,
+                    bid_collateral: None,
+                    margin: None,
+                    fees: None,
+                    pool_eligibility: None,
+                    required_attributes: None,
+                    accepted_token_contracts: vec![],
+                }),
+            },
+        ) {
+            Ok(_) => panic!("expected error, but ok"),
+            Err(error) => match error {
+                ContractError::InvalidDealLabel => {}
+                error => panic!("unexpected error: {:?}", error),
+            },
+        }
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod execute_sync_deal_tests {
+    use crate::contract::{execute, query};
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::SyncDeal;
+    use crate::msg::GetDealsResponse;
+    use crate::msg::QueryMsg::{GetDealsByBuyer, GetDealsBySeller, GetDealsByStatus};
+    use crate::storage::state_store::{
+        save_contract_config, save_deal_state, Config, Deal, DealStatus,
+    };
+    use cosmwasm_std::testing::{message_info, mock_dependencies, mock_env};
+    use cosmwasm_std::{
+        from_json, to_json_binary, ContractResult, SystemError, SystemResult, Uint128, WasmQuery,
+    };
+    use forward_market_contract::msg::GetContractStateResponse;
+    use forward_market_contract::storage::state_store::{Buyer, Config as DealConfig, Seller};
+    use forward_market_contract::version_info::VersionInfoV1;
+
+    #[test]
+    fn sync_deal_with_accepted_bid() {
+        let mut deps = mock_dependencies();
+        let factory_address = deps.api.addr_make("forward-market-factory");
+        let deal_address = deps.api.addr_make("forward-market-deal");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let sender = deps.api.addr_make("anyone");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                admin: deps.api.addr_make("factory-admin"),
+                forward_market_code_id: 7,
+            },
+        )
+        .unwrap();
+        save_deal_state(
+            &mut deps.storage,
+            &Deal {
+                deal_address: deal_address.clone(),
+                creator: deps.api.addr_make("deal-creator"),
+                label: "Forward Trade 1".to_string(),
+                code_id: 7,
+                sellers: vec![],
+                buyers: vec![],
+                dealers: vec![dealer_address.clone()],
+                status: DealStatus::Open,
+            },
+        )
+        .unwrap();
+
+        let deal_state = to_json_binary(&GetContractStateResponse {
+            bids: vec![],
+            allowed_sellers: vec![],
+            allowed_buyers: vec![],
+            bid_commitments: vec![],
+            bid_collateral: vec![],
+            acceptance_deadline: None,
+            margin_accounts: vec![],
+            mark_price: None,
+            sellers: vec![Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            }],
+            config: DealConfig {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                max_bid_count: None,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                contract_admin: factory_address,
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+            settlement_data: None,
+            version_info: VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+            buyers: vec![Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::zero(),
+            }],
+            token_data: None,
+            bid_revisions: vec![],
+            offer_revisions: vec![],
+        })
+        .unwrap();
+        let queried_address = deal_address.to_string();
+        deps.querier.update_wasm(move |query| match query {
+            WasmQuery::Smart { contract_addr, .. } if *contract_addr == queried_address => {
+                SystemResult::Ok(ContractResult::Ok(deal_state.clone()))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "wasm".to_string(),
+            }),
+        });
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            message_info(&sender, &[]),
+            SyncDeal {
+                deal_address: deal_address.to_string(),
+            },
+        )
+        .unwrap();
+
+        let seller_deals: GetDealsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetDealsBySeller {
+                    seller: seller_address.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(1, seller_deals.deals.len());
+        assert_eq!(vec![seller_address], seller_deals.deals[0].sellers);
+        assert_eq!(vec![buyer_address.clone()], seller_deals.deals[0].buyers);
+        assert_eq!(DealStatus::BidAccepted, seller_deals.deals[0].status);
+
+        let buyer_deals: GetDealsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetDealsByBuyer {
+                    buyer: buyer_address.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(seller_deals.deals, buyer_deals.deals);
+
+        // The deal is no longer listed under its previous status
+        let open_deals: GetDealsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                GetDealsByStatus {
+                    status: DealStatus::Open,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(open_deals.deals.is_empty());
+    }
+
+    #[test]
+    fn sync_unknown_deal() {
+        let mut deps = mock_dependencies();
+        let deal_address = deps.api.addr_make("forward-market-deal");
+        let sender = deps.api.addr_make("anyone");
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&sender, &[]),
+            SyncDeal {
+                deal_address: deal_address.to_string(),
+            },
+        ) {
+            Ok(_) => panic!("expected error, but ok"),
+            Err(error) => match error {
+                ContractError::UnknownDeal {
+                    deal_address: address,
+                } => assert_eq!(deal_address.to_string(), address),
+                error => panic!("unexpected error: {:?}", error),
+            },
+        }
+    }
+}
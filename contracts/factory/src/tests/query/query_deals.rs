@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod query_deals_tests {
+    use crate::contract::query;
+    use crate::msg::GetDealsResponse;
+    use crate::msg::QueryMsg::{GetDeals, GetDealsByDealer};
+    use crate::storage::state_store::{save_deal_state, Deal, DealStatus};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{from_json, Addr};
+
+    #[test]
+    fn query_deals_in_pages() {
+        let mut deps = mock_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let other_dealer_address = deps.api.addr_make("other-dealer-address");
+        let env = mock_env();
+
+        let mut deal_addresses = vec![];
+        for deal_number in 0..3 {
+            let deal_address = deps
+                .api
+                .addr_make(&format!("forward-market-deal-{deal_number}"));
+            let dealers = if deal_number == 0 {
+                vec![other_dealer_address.clone()]
+            } else {
+                vec![dealer_address.clone()]
+            };
+            save_deal_state(
+                &mut deps.storage,
+                &Deal {
+                    deal_address: deal_address.clone(),
+                    creator: deps.api.addr_make("deal-creator"),
+                    label: format!("Forward Trade {deal_number}"),
+                    code_id: 7,
+                    sellers: vec![],
+                    buyers: vec![],
+                    dealers,
+                    status: DealStatus::Open,
+                },
+            )
+            .unwrap();
+            deal_addresses.push(deal_address);
+        }
+        deal_addresses.sort();
+
+        let first_page: GetDealsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetDeals {
+                    start_after: None,
+                    limit: Some(2),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let last_address = first_page.deals.last().unwrap().deal_address.to_string();
+        let second_page: GetDealsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetDeals {
+                    start_after: Some(last_address),
+                    limit: Some(2),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let paged_addresses = first_page
+            .deals
+            .iter()
+            .chain(second_page.deals.iter())
+            .map(|deal| deal.deal_address.clone())
+            .collect::<Vec<Addr>>();
+        assert_eq!(2, first_page.deals.len());
+        assert_eq!(deal_addresses, paged_addresses);
+
+        let dealer_deals: GetDealsResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                GetDealsByDealer {
+                    dealer: dealer_address.to_string(),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(2, dealer_deals.deals.len());
+        assert!(dealer_deals
+            .deals
+            .iter()
+            .all(|deal| deal.dealers == vec![dealer_address.clone()]));
+    }
+}
@@ -0,0 +1,16 @@
+use crate::error::ContractError;
+use cosmwasm_std::{instantiate2_address, Addr, Deps, Env};
+
+/// Derives the address that `Instantiate2` assigns to a deal created by the factory with the code
+/// id and salt
+pub fn derive_deal_address(
+    deps: Deps,
+    env: &Env,
+    code_id: u64,
+    salt: &[u8],
+) -> Result<Addr, ContractError> {
+    let code_info = deps.querier.query_wasm_code_info(code_id)?;
+    let factory_address = deps.api.addr_canonicalize(env.contract.address.as_str())?;
+    let deal_address = instantiate2_address(code_info.checksum.as_slice(), &factory_address, salt)?;
+    Ok(deps.api.addr_humanize(&deal_address)?)
+}
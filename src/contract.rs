@@ -1,28 +1,53 @@
-use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response,
-};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_json_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response};
 
 use crate::error::ContractError;
-use crate::error::ContractError::{IllegalContractExecution, InvalidContractExecution};
+use crate::execute::accept_best_bid::execute_accept_best_bid;
 use crate::execute::accept_bid::execute_accept_bid;
 use crate::execute::accept_finalized_pools::execute_accept_finalized_pools;
+use crate::execute::add_allowed_buyers::execute_add_allowed_buyers;
+use crate::execute::add_allowed_sellers::execute_add_allowed_sellers;
 use crate::execute::add_bidder::execute_add_bidder;
 use crate::execute::add_seller::execute_add_seller;
+use crate::execute::commit_bid::execute_commit_bid;
+use crate::execute::create_trade::{execute_create_trade, TradeConfigOverrides};
 use crate::execute::dealer_confirm::execute_dealer_confirm;
+use crate::execute::declare_margin_default::execute_declare_margin_default;
+use crate::execute::deposit_fees::execute_deposit_fees;
+use crate::execute::deposit_margin::execute_deposit_margin;
 use crate::execute::disable_contract::execute_disable_contract;
 use crate::execute::finalize_pools::execute_finalize_pools;
+use crate::execute::forfeit_bid_collateral::execute_forfeit_bid_collateral;
+use crate::execute::mark_to_market::execute_mark_to_market;
 use crate::execute::mint_tokens::execute_mint_tokens;
+use crate::execute::receive_pool::execute_receive_pool;
+use crate::execute::remove_allowed_buyers::execute_remove_allowed_buyers;
+use crate::execute::remove_allowed_sellers::execute_remove_allowed_sellers;
 use crate::execute::rescind_finalized_pools::execute_rescind_finalized_pools;
+use crate::execute::reveal_bid::execute_reveal_bid;
 use crate::execute::update_allowed_buyers::execute_update_allowed_buyers;
 use crate::execute::update_allowed_sellers::execute_update_allowed_sellers;
+use crate::execute::update_max_bid_count::execute_update_max_bid_count;
 use crate::execute::update_seller_offer_hash::execute_update_seller_offer_hash;
+use crate::execute::update_visibility::execute_update_visibility;
+use crate::execute::withdraw_bid::execute_withdraw_bid;
 use crate::instantiate::instantiate_contract::instantiate_contract;
 use crate::migrate::migrate::migrate_contract;
-use crate::msg::{ExecuteMsg, InstantiateContractMsg, MigrateMsg, QueryMsg};
-use crate::query::contract_state::query_contract_state;
-use crate::storage::state_store::{
-    retrieve_contract_config, retrieve_optional_settlement_data_state,
+use crate::msg::{
+    Cw20ReceiveMsg, Cw721ReceiveMsg, ExecuteMsg, InstantiateContractMsg, MigrateMsg, QueryMsg,
 };
+use crate::query::contract_state::query_contract_state;
+use crate::query::escrow_reconciliation::query_escrow_reconciliation;
+use crate::query::history::query_history;
+use crate::query::permissions::query_permissions;
+use crate::query::settlement_fees::query_settlement_fees;
+use crate::query::simulate::{query_simulate_dealer_confirm, query_simulate_finalize_pools};
+use crate::storage::history_storage::HistoryStorage;
+use crate::storage::state_store::PoolAsset;
+use crate::storage::trade_storage::{ReadonlyTradeStorage, TradeStorage};
+use crate::util::helpers::{validate_trade_id, validate_trade_is_open};
+use crate::util::history::record_history;
 
 /// The entry point used when an account instantiates a stored code wasm payload of this contract on
 /// the Provenance Blockchain.
@@ -30,14 +55,14 @@ use crate::storage::state_store::{
 /// # Parameters
 ///
 /// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
-/// resources like contract internal storage and a querier to retrieve blockchain objects.
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
 /// * `env` An environment object provided by the cosmwasm framework.  Describes the contract's
-/// details, as well as blockchain information at the time of the transaction.
+///   details, as well as blockchain information at the time of the transaction.
 /// * `info` A message information object provided by the cosmwasm framework.  Describes the sender
-/// of the instantiation message, as well as the funds provided as an amount during the transaction.
+///   of the instantiation message, as well as the funds provided as an amount during the transaction.
 /// * `msg` A custom instantiation message defined by this contract for creating the initial
-/// configuration used by the contract.
-#[entry_point]
+///   configuration used by the contract.
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     env: Env,
@@ -53,62 +78,200 @@ pub fn instantiate(
 /// # Parameters
 ///
 /// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
-/// resources like contract internal storage and a querier to retrieve blockchain objects.
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
 /// * `env` An environment object provided by the cosmwasm framework.  Describes the contract's
-/// details, as well as blockchain information at the time of the transaction.
+///   details, as well as blockchain information at the time of the transaction.
 /// * `info` A message information object provided by the cosmwasm framework.  Describes the sender
-/// of the instantiation message, as well as the funds provided as an amount during the transaction.
+///   of the instantiation message, as well as the funds provided as an amount during the transaction.
 /// * `msg` A custom execution message enum defined by this contract that will map the desired operation
-/// to the proper contract logic.
-#[entry_point]
+///   to the proper contract logic.
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-    // If the contract has already been settled, no further execution
-    let has_settled = retrieve_optional_settlement_data_state(deps.storage)?.is_some();
-    if has_settled {
-        return Err(IllegalContractExecution);
-    }
+    // Every route other than CreateTrade is executed against the storage of a single trade
+    let trade_id = msg.trade_id();
+    validate_trade_id(deps.storage, trade_id)?;
+    let mut trade_storage = TradeStorage::new(deps.storage, trade_id);
 
-    // If the contract has been disabled, no further execution is allowed
-    let is_disabled = retrieve_contract_config(deps.storage)?.is_disabled;
-    if is_disabled {
-        return Err(InvalidContractExecution);
+    // New trades are independent of the trade created at instantiation, so they can be created
+    // after it has been settled or disabled
+    if !matches!(msg, ExecuteMsg::CreateTrade { .. }) {
+        validate_trade_is_open(&trade_storage)?;
     }
+
+    // Every successful route is recorded in the history of the trade along with the entries it changed
+    let actor = info.sender.clone();
+    let action = msg.action();
+    let mut history_storage = HistoryStorage::new(&mut trade_storage);
+    let route_deps = DepsMut {
+        storage: &mut history_storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+    let response = execute_route(route_deps, env.clone(), info, msg)?;
+    let writes = history_storage.into_writes();
+    record_history(&mut trade_storage, &env, actor, action.name(), writes)?;
+    Ok(response)
+}
+
+/// Maps the execution message to the logic of its route
+fn execute_route(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::AddSeller { offer_hash } => execute_add_seller(deps, info, offer_hash),
-        ExecuteMsg::UpdateSellerOfferHash { offer_hash } => {
-            execute_update_seller_offer_hash(deps, info, offer_hash)
+        ExecuteMsg::CreateTrade {
+            use_private_sellers,
+            use_private_buyers,
+            allowed_sellers,
+            allowed_buyers,
+            max_bid_count,
+            dealers,
+            accepted_token_contracts,
+            auction,
+            bid_collateral,
+            margin,
+            fees,
+            pool_eligibility,
+            required_attributes,
+        } => execute_create_trade(
+            deps,
+            env,
+            info,
+            use_private_sellers,
+            use_private_buyers,
+            allowed_sellers,
+            allowed_buyers,
+            max_bid_count,
+            dealers,
+            accepted_token_contracts,
+            TradeConfigOverrides {
+                auction,
+                bid_collateral,
+                margin,
+                fees: fees.map(|fees| *fees),
+                pool_eligibility,
+                required_attributes: required_attributes.map(|attributes| *attributes),
+            },
+        ),
+        ExecuteMsg::AddSeller { offer_hash, .. } => execute_add_seller(deps, env, info, offer_hash),
+        ExecuteMsg::UpdateSellerOfferHash { offer_hash, .. } => {
+            execute_update_seller_offer_hash(deps, env, info, offer_hash)
         }
-        ExecuteMsg::FinalizePools { pool_denoms } => {
+        ExecuteMsg::FinalizePools { pool_denoms, .. } => {
             execute_finalize_pools(deps, env, info, &pool_denoms)
         }
-        ExecuteMsg::DealerConfirm {} => execute_dealer_confirm(deps, env, info),
-        ExecuteMsg::UpdateAllowedSellers { allowed_sellers } => {
-            execute_update_allowed_sellers(deps, info, allowed_sellers)
-        }
-        ExecuteMsg::UpdateAllowedBuyers { allowed_buyers } => {
+        ExecuteMsg::DealerConfirm { .. } => execute_dealer_confirm(deps, env, info),
+        ExecuteMsg::UpdateAllowedSellers {
+            allowed_sellers, ..
+        } => execute_update_allowed_sellers(deps, info, allowed_sellers),
+        ExecuteMsg::UpdateAllowedBuyers { allowed_buyers, .. } => {
             execute_update_allowed_buyers(deps, info, allowed_buyers)
         }
-        ExecuteMsg::AcceptFinalizedPools { offer_hash } => {
-            execute_accept_finalized_pools(deps, info, offer_hash)
+        ExecuteMsg::UpdateVisibility {
+            private_sellers,
+            private_buyers,
+            allowed_sellers,
+            allowed_buyers,
+            max_bid_count,
+            ..
+        } => execute_update_visibility(
+            deps,
+            env,
+            info,
+            private_sellers,
+            private_buyers,
+            allowed_sellers,
+            allowed_buyers,
+            max_bid_count,
+        ),
+        ExecuteMsg::UpdateMaxBidCount { max_bid_count, .. } => {
+            execute_update_max_bid_count(deps, info, max_bid_count)
+        }
+        ExecuteMsg::AddAllowedSellers {
+            allowed_sellers, ..
+        } => execute_add_allowed_sellers(deps, info, allowed_sellers),
+        ExecuteMsg::RemoveAllowedSellers {
+            allowed_sellers, ..
+        } => execute_remove_allowed_sellers(deps, info, allowed_sellers),
+        ExecuteMsg::AddAllowedBuyers { allowed_buyers, .. } => {
+            execute_add_allowed_buyers(deps, info, allowed_buyers)
+        }
+        ExecuteMsg::RemoveAllowedBuyers {
+            allowed_buyers,
+            remove_bids,
+            ..
+        } => execute_remove_allowed_buyers(deps, env, info, allowed_buyers, remove_bids),
+        ExecuteMsg::AcceptFinalizedPools { offers, .. } => {
+            execute_accept_finalized_pools(deps, info, offers)
+        }
+        ExecuteMsg::RescindFinalizedPools { .. } => {
+            execute_rescind_finalized_pools(deps, env, info)
+        }
+        ExecuteMsg::ContractDisable { .. } => execute_disable_contract(deps, env, info),
+        ExecuteMsg::AcceptBid { accepted_bids, .. } => {
+            execute_accept_bid(deps, env, info, accepted_bids)
         }
-        ExecuteMsg::RescindFinalizedPools {} => execute_rescind_finalized_pools(deps, env, info),
-        ExecuteMsg::ContractDisable {} => execute_disable_contract(deps, env, info),
-        ExecuteMsg::AcceptBid {
-            bidder_address,
-            agreement_terms_hash,
-        } => execute_accept_bid(deps, env, info, bidder_address, agreement_terms_hash),
         ExecuteMsg::AddBid {
             agreement_terms_hash,
-        } => execute_add_bidder(deps, info, agreement_terms_hash),
+            price,
+            ..
+        } => execute_add_bidder(deps, env, info, agreement_terms_hash, price),
+        ExecuteMsg::WithdrawBid { .. } => execute_withdraw_bid(deps, env, info),
+        ExecuteMsg::ForfeitBidCollateral { .. } => execute_forfeit_bid_collateral(deps, env, info),
+        ExecuteMsg::DepositFees { amount, .. } => execute_deposit_fees(deps, env, info, amount),
+        ExecuteMsg::DepositMargin { .. } => execute_deposit_margin(deps, info),
+        ExecuteMsg::MarkToMarket { price, .. } => execute_mark_to_market(deps, env, info, price),
+        ExecuteMsg::DeclareMarginDefault { address, .. } => {
+            execute_declare_margin_default(deps, env, info, address)
+        }
+        ExecuteMsg::AcceptBestBid { .. } => execute_accept_best_bid(deps, env, info),
+        ExecuteMsg::CommitBid { commitment, .. } => execute_commit_bid(deps, env, info, commitment),
+        ExecuteMsg::RevealBid {
+            agreement_terms_hash,
+            price,
+            salt,
+            ..
+        } => execute_reveal_bid(deps, env, info, agreement_terms_hash, price, salt),
         ExecuteMsg::MintTokens {
             token_count,
             token_denom,
+            ..
         } => execute_mint_tokens(deps, env, info, token_count, token_denom),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender,
+            amount,
+            msg,
+        }) => execute_receive_pool(
+            deps,
+            info.clone(),
+            sender,
+            &msg,
+            PoolAsset::Cw20 {
+                contract: info.sender,
+                amount,
+            },
+        ),
+        ExecuteMsg::ReceiveNft(Cw721ReceiveMsg {
+            sender,
+            token_id,
+            msg,
+        }) => execute_receive_pool(
+            deps,
+            info.clone(),
+            sender,
+            &msg,
+            PoolAsset::Cw721 {
+                contract: info.sender,
+                token_id,
+            },
+        ),
     }
 }
 
@@ -118,16 +281,56 @@ pub fn execute(
 /// # Parameters
 ///
 /// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
-/// resources like contract internal storage and a querier to retrieve blockchain objects.
-/// * `_env` An environment object provided by the cosmwasm framework.  Describes the contract's
-/// details, as well as blockchain information at the time of the transaction.  Unused by this
-/// function, but required by cosmwasm for successfully defined query entrypoint.
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
+/// * `env` An environment object provided by the cosmwasm framework.  Describes the contract's
+///   details, as well as blockchain information at the time of the transaction.
 /// * `msg` A custom query message enum defined by this contract that will map the desired query
-/// to the proper contract logic
-#[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+///   to the proper contract logic
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let trade_id = match msg {
+        QueryMsg::GetContractState { trade_id }
+        | QueryMsg::GetSettlementFees { trade_id }
+        | QueryMsg::GetEscrowReconciliation { trade_id }
+        | QueryMsg::GetHistory { trade_id, .. }
+        | QueryMsg::GetPermissions { trade_id, .. }
+        | QueryMsg::SimulateFinalizePools { trade_id, .. }
+        | QueryMsg::SimulateDealerConfirm { trade_id, .. } => trade_id,
+    };
+    validate_trade_id(deps.storage, trade_id)?;
+    let trade_storage = ReadonlyTradeStorage::new(deps.storage, trade_id);
+    let deps = Deps {
+        storage: &trade_storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
     match msg {
-        QueryMsg::GetContractState {} => Ok(to_json_binary(&query_contract_state(deps)?)?),
+        QueryMsg::GetContractState { .. } => Ok(to_json_binary(&query_contract_state(deps)?)?),
+        QueryMsg::GetSettlementFees { .. } => Ok(to_json_binary(&query_settlement_fees(deps)?)?),
+        QueryMsg::GetEscrowReconciliation { .. } => {
+            Ok(to_json_binary(&query_escrow_reconciliation(deps, env)?)?)
+        }
+        QueryMsg::GetHistory {
+            start_after, limit, ..
+        } => Ok(to_json_binary(&query_history(deps, start_after, limit)?)?),
+        QueryMsg::GetPermissions { address, funds, .. } => Ok(to_json_binary(&query_permissions(
+            deps, env, address, funds,
+        )?)?),
+        QueryMsg::SimulateFinalizePools {
+            seller,
+            pool_denoms,
+            funds,
+            ..
+        } => Ok(to_json_binary(&query_simulate_finalize_pools(
+            deps,
+            env,
+            seller,
+            pool_denoms,
+            funds,
+        )?)?),
+        QueryMsg::SimulateDealerConfirm { dealer, .. } => Ok(to_json_binary(
+            &query_simulate_dealer_confirm(deps, env, dealer)?,
+        )?),
     }
 }
 
@@ -137,12 +340,12 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
 /// # Parameters
 ///
 /// * `deps` A dependencies object provided by the cosmwasm framework.  Allows access to useful
-/// resources like contract internal storage and a querier to retrieve blockchain objects.
+///   resources like contract internal storage and a querier to retrieve blockchain objects.
 /// * `env` An environment object provided by the cosmwasm framework.  Describes the contract's
-/// details, as well as blockchain information at the time of the transaction.
+///   details, as well as blockchain information at the time of the transaction.
 /// * `msg` A custom migration message defined by this contract that will map the desired operation
-/// to the proper contract logic.
-#[entry_point]
+///   to the proper contract logic.
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     match msg {
         MigrateMsg::ContractUpgrade {} => migrate_contract(deps),
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,8 +20,8 @@ pub enum ContractError {
     #[error("Action must be performed by seller")]
     UnauthorizedAsSeller,
 
-    /// Occurs when a seller is attempted to be registered when a seller is already registered
-    #[error("A seller has already been defined for this contract")]
+    /// Occurs when an address attempts to register as a seller when it is already a registered seller
+    #[error("The address has already been registered as a seller for this contract")]
     SellerAlreadyExists,
 
     /// Occurs when a seller that is not specified in the allowed sellers list tries to add themselves as seller
@@ -70,6 +70,10 @@ pub enum ContractError {
     )]
     IllegalPoolAcceptanceRequest,
 
+    /// Occurs if the buyer's accepted offers do not match the set of sellers that have finalized pools
+    #[error("Exactly one accepted offer must be provided for every seller with a finalized list of pools")]
+    InvalidAcceptedOffers,
+
     /// Occurs if someone other than the buyer attempts to finalize the list of pools
     #[error("Only the buyer can accept the finalized list of pools")]
     IllegalAcceptingParty,
@@ -167,12 +171,16 @@ pub enum ContractError {
 
     /// Occurs when a buyer attempts to submit a bid but the limit of allowed buyers has already been reached
     #[error("The limit of allowed buyers has already been reached")]
-    MaxPrivateBuyersReached,
+    MaxBuyersReached,
 
     /// Occurs when a seller attempts to accept a bid for a bidder address that doesn't exist
     #[error("Bid does not exist for address {address:?}")]
     BidDoesNotExist { address: String },
 
+    /// Occurs when the accepted bids are empty, repeat a bidder or cannot give every buyer at least one token
+    #[error("Every accepted bid must be for a distinct bidder with a non-zero allocation that yields at least one token")]
+    InvalidBidAllocation,
+
     /// Occurs when a seller attempts to accept a bid when a previous bid has already been accepted
     #[error("Cannot accept bid because a bid from address {address:?} was already accepted")]
     BidPreviouslyAccepted { address: String },
@@ -181,6 +189,208 @@ pub enum ContractError {
     #[error("The offer has cannot be updated after a buyer has accepted it")]
     IllegalOfferHashUpdate,
 
+    /// Occurs when the commit and reveal deadlines of a sealed-bid auction are not in order
+    #[error("The auction commit deadline must be in the future and before the reveal deadline")]
+    InvalidAuctionConfig,
+
+    /// Occurs when a bid is added directly to a contract that is running a sealed-bid auction
+    #[error(
+        "Bids must be committed and revealed while the contract is running a sealed-bid auction"
+    )]
+    SealedBidRequired,
+
+    /// Occurs when a bid is committed or revealed on a contract that is not running a sealed-bid auction
+    #[error("The contract is not running a sealed-bid auction")]
+    AuctionNotConfigured,
+
+    /// Occurs when a bidder attempts to commit a bid after the commit deadline
+    #[error("The commit window of the auction has closed")]
+    CommitWindowClosed,
+
+    /// Occurs when a bidder attempts to reveal a bid outside of the reveal window
+    #[error("Bids can only be revealed after the commit deadline and before the reveal deadline")]
+    RevealWindowClosed,
+
+    /// Occurs when a bidder attempts to reveal a bid without having committed to one
+    #[error("Bid commitment does not exist for address {address:?}")]
+    BidCommitmentDoesNotExist { address: String },
+
+    /// Occurs when the revealed terms and salt do not hash to the bidder's commitment
+    #[error("The revealed bid does not match the bid commitment")]
+    InvalidBidReveal,
+
+    /// Occurs when a seller attempts to accept a bid before the reveal window of the auction has closed
+    #[error("Bids cannot be accepted until the reveal window of the auction has closed")]
+    AuctionNotClosed,
+
+    /// Occurs when the best bid is requested but no bid has been submitted with a price
+    #[error("There are no priced bids to accept")]
+    NoPricedBids,
+
+    /// Occurs when the bid collateral configuration does not describe a non-zero amount of a denom
+    #[error("The bid collateral must have a denom and a non-zero amount")]
+    InvalidBidCollateralConfig,
+
+    /// Occurs when a bidder does not attach exactly the collateral required to place a bid
+    #[error("A bid requires collateral of {amount:?} {denom:?}")]
+    InvalidBidCollateral { denom: String, amount: Uint128 },
+
+    /// Occurs when a bidder that has been accepted as a buyer attempts to withdraw their bid
+    #[error("An accepted bid cannot be withdrawn")]
+    IllegalBidWithdrawal,
+
+    /// Occurs when bid collateral is forfeited before the acceptance deadline has passed
+    #[error("Bid collateral can only be forfeited after the acceptance deadline has passed")]
+    IllegalCollateralForfeit,
+
+    /// Occurs when someone other than a seller or a dealer attempts to forfeit bid collateral
+    #[error("Only a seller or a dealer can forfeit bid collateral")]
+    UnauthorizedCollateralForfeit,
+
+    /// Occurs when the margin configuration has no denom or a negative maintenance margin
+    #[error("Margin must have a denom and a maintenance margin that is not negative")]
+    InvalidMarginConfig,
+
+    /// Occurs when a margin route is used on a contract that does not require margin
+    #[error("The contract does not require margin")]
+    MarginNotConfigured,
+
+    /// Occurs when someone other than a buyer or a seller attempts to deposit margin
+    #[error("Only a buyer or a seller can deposit margin")]
+    UnauthorizedMarginDeposit,
+
+    /// Occurs when a margin deposit is not a single non-zero amount of the margin denom
+    #[error("Margin must be deposited as a single amount of {denom:?}")]
+    InvalidMarginDeposit { denom: String },
+
+    /// Occurs when someone other than a dealer attempts to mark the contract to market or declare a default
+    #[error("Only a dealer can mark the contract to market or declare a margin default")]
+    UnauthorizedMarginRequest,
+
+    /// Occurs when the contract is marked to market before any bid has been accepted
+    #[error("The contract cannot be marked to market until a bid has been accepted")]
+    IllegalMarkToMarket,
+
+    /// Occurs when a dealer marks the contract to a price that is zero or negative
+    #[error("The mark price must be positive")]
+    InvalidMarkPrice,
+
+    /// Occurs when a dealer confirms while a party has an open margin call or owes variation margin
+    #[error("The margin account of {address:?} has an open margin call or owes variation margin")]
+    OutstandingMargin { address: String },
+
+    /// Occurs when a default is declared against a party that is not past due on a margin call
+    #[error(
+        "A margin default can only be declared after an unmet margin call deadline has passed"
+    )]
+    IllegalMarginDefault,
+
+    /// Occurs when the fee configuration has no denom, a basis point value above 10,000 or a platform
+    /// share without a platform address
+    #[error("Fees must have a denom, basis points of at most 10,000 and a platform address for any platform share")]
+    InvalidFeeConfig,
+
+    /// Occurs when a fee route is used on a contract that does not charge fees
+    #[error("The contract does not charge fees")]
+    FeesNotConfigured,
+
+    /// Occurs when someone other than a buyer or a seller attempts to deposit fees
+    #[error("Only a buyer or a seller can deposit fees")]
+    UnauthorizedFeeDeposit,
+
+    /// Occurs when a fee deposit does not match the funds attached to the message
+    #[error("Fees must be deposited as a single non-zero amount of {denom:?}")]
+    InvalidFeeDeposit { denom: String },
+
+    /// Occurs when a party has not deposited enough to cover their fee at settlement
+    #[error("The fee deposit of {address:?} does not cover the required fee of {required:?}")]
+    InsufficientFeeDeposit { address: String, required: Uint128 },
+
+    /// Occurs when the pool eligibility configuration does not require at least one scope or has a
+    /// maximum scope count below the minimum
+    #[error("Pool eligibility must require at least one scope and a maximum scope count of at least the minimum")]
+    InvalidPoolEligibilityConfig,
+
+    /// Occurs when a pool marker does not own a number of scopes within the configured range
+    #[error("The pool {denom:?} owns {count:?} scopes, which is outside of the allowed range")]
+    InvalidPoolScopeCount { denom: String, count: u64 },
+
+    /// Occurs when a scope owned by a pool marker does not use one of the allowed scope specifications
+    #[error("The scope {scope_address:?} does not use an allowed scope specification")]
+    InvalidScopeSpecification { scope_address: String },
+
+    /// Occurs when a metadata address cannot be created from the provided value
+    #[error("Invalid metadata address: {message:?}")]
+    InvalidMetadataAddress { message: String },
+
+    /// Occurs when a required attribute name is empty
+    #[error("Required attribute names cannot be empty")]
+    InvalidRequiredAttributesConfig,
+
+    /// Occurs when an address does not hold an attribute that its role requires
+    #[error("The address {address:?} does not hold the required attribute {attribute:?}")]
+    MissingRequiredAttribute { address: String, attribute: String },
+
+    /// Occurs when a visibility update would leave a registered seller outside of the allowed sellers
+    #[error("The registered seller {address:?} must remain in the list of allowed sellers")]
+    IllegalVisibilityUpdate { address: String },
+
+    /// Occurs when the max bid count is set to zero
+    #[error("The max bid count must be greater than 0 when it is set")]
+    InvalidMaxBidCount,
+
+    /// Occurs when an address other than the contract admin attempts to create a trade
+    #[error("Only the contract admin can create a trade")]
+    UnauthorizedTradeCreation,
+
+    /// Occurs when a route is executed against a trade that has not been created
+    #[error("No trade has been created with trade id {trade_id}")]
+    UnknownTrade { trade_id: u64 },
+
+    /// Occurs when a seller accepts a bid whose revision has changed since they reviewed it
+    #[error("The bid from {address:?} is at revision {revision} but revision {expected_revision} was expected")]
+    BidRevisionMismatch {
+        address: String,
+        expected_revision: u64,
+        revision: u64,
+    },
+
+    /// Occurs when a buyer accepts an offer whose revision has changed since they reviewed it
+    #[error("The offer from {address:?} is at revision {revision} but revision {expected_revision} was expected")]
+    OfferRevisionMismatch {
+        address: String,
+        expected_revision: u64,
+        revision: u64,
+    },
+
+    /// Occurs when a finalized pool is held by an address other than the contract when it is moved out
+    /// of escrow
+    #[error("The pool {denom:?} is held by {holder:?} instead of the contract's escrow")]
+    EscrowMismatch { denom: String, holder: String },
+
+    /// Occurs when a dealer confirms while a finalized pool is not held in the contract's escrow
+    #[error("The pool {denom:?} is not held in escrow by the contract")]
+    UnreconciledEscrow { denom: String },
+
+    /// Occurs when the contract would transfer or withdraw coins of a marker that is not active
+    #[error("The marker {denom:?} must be active but has status {status}")]
+    InactiveMarker { denom: String, status: String },
+
+    /// Occurs when the contract would transfer or withdraw coins of a marker that has not granted the
+    /// contract the access it needs
+    #[error("The contract must be granted {access} on the marker {denom:?}")]
+    MissingMarkerAccess { denom: String, access: String },
+
+    /// Occurs when a seller finalizes a pool that is not a marker while pool eligibility is configured,
+    /// since only a pool marker can own the scopes that are checked
+    #[error("Only marker pools can be finalized when pool eligibility is configured")]
+    IneligiblePoolAsset,
+
+    /// Occurs when the receive hook is called by an address that is not one of the accepted CW20 or
+    /// CW721 token contracts
+    #[error("Pools cannot be received from {contract:?}, which is not an accepted token contract")]
+    UnacceptedTokenContract { contract: String },
+
     /// Occurs when tokens for the contract have previously been minted
     #[error("Tokens for the contract were already minted for token denom {token_denom:?}")]
     TokensAlreadyMinted { token_denom: String },
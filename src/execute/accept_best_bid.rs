@@ -0,0 +1,57 @@
+use crate::error::ContractError;
+use crate::error::ContractError::NoPricedBids;
+use crate::execute::accept_bid::{
+    accept_bids, validate_bid_acceptor, validate_bids_can_be_accepted,
+};
+use crate::msg::AcceptedBid;
+use crate::storage::state_store::{retrieve_bid_list_state, Bid};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn execute_accept_best_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let bid = validate_accept_best_bid(deps.as_ref(), &env, &info)?;
+
+    // The winning bidder receives every forward market token
+    let accepted_bid = AcceptedBid {
+        bidder_address: bid.buyer_address.to_string(),
+        agreement_terms_hash: bid.agreement_terms_hash,
+        allocation: Uint128::one(),
+        expected_revision: Some(bid.revision),
+    };
+    accept_bids(deps, &env, vec![accepted_bid])
+}
+
+/// Checks that the sender can accept bids and that there is a priced bid to accept, returning the
+/// best bid
+pub fn validate_accept_best_bid(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+) -> Result<Bid, ContractError> {
+    validate_bid_acceptor(deps, info)?;
+
+    // Rank the priced bids by price, breaking ties in favor of the bid that was submitted first.
+    // Bids submitted at the same time keep their order in the bid list.
+    let mut best_bid: Option<(Bid, Uint128)> = None;
+    for bid in retrieve_bid_list_state(deps.storage)?.bids {
+        if let Some(price) = bid.price {
+            let is_better = match &best_bid {
+                None => true,
+                Some((current, current_price)) => {
+                    price > *current_price
+                        || (price == *current_price && bid.submitted_at < current.submitted_at)
+                }
+            };
+            if is_better {
+                best_bid = Some((bid, price));
+            }
+        }
+    }
+
+    let (bid, _) = best_bid.ok_or(NoPricedBids)?;
+    validate_bids_can_be_accepted(deps, env)?;
+    Ok(bid)
+}
@@ -1,83 +1,189 @@
 use crate::error::ContractError;
 use crate::error::ContractError::{
-    BidDoesNotExist, BidPreviouslyAccepted, InvalidAgreementTermsHash, TokensNotMinted,
-    UnauthorizedAsSeller,
+    AuctionNotClosed, BidDoesNotExist, BidPreviouslyAccepted, BidRevisionMismatch,
+    InvalidAgreementTermsHash, InvalidBidAllocation, TokensNotMinted, UnauthorizedAsSeller,
 };
+use crate::msg::AcceptedBid;
 use crate::storage::state_store::{
-    retrieve_bid_list_state, retrieve_optional_buyer_state, retrieve_optional_token_data_state,
+    retrieve_bid_list_state, retrieve_buyer_list_state, retrieve_contract_config,
+    retrieve_optional_token_data_state, retrieve_token_data_state, save_acceptance_deadline_state,
     save_buyer_state, Bid, Buyer,
 };
-use crate::util::helpers::{create_transfer_tokens_message, is_seller};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use crate::util::helpers::{
+    allocate_tokens_pro_rata, create_transfer_tokens_message, is_seller, release_bid_collateral,
+    validate_marker_access, validate_required_attributes,
+};
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use provwasm_std::types::provenance::marker::v1::Access;
 
 pub fn execute_accept_bid(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    bidder_address_str: String,
-    agreement_terms_hash: String,
+    accepted_bids: Vec<AcceptedBid>,
 ) -> Result<Response, ContractError> {
-    // Only the seller can accept a buyer's bid
-    if !is_seller(&deps, &info)? {
+    validate_accept_bid(deps.as_ref(), &env, &info)?;
+
+    accept_bids(deps, &env, accepted_bids)
+}
+
+/// Checks that the sender is a seller and that the trade is ready for bids to be accepted. Shared by
+/// every route that accepts bids so that the same checks apply however the bids are chosen
+pub fn validate_accept_bid(deps: Deps, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+    validate_bid_acceptor(deps, info)?;
+    validate_bids_can_be_accepted(deps, env)
+}
+
+/// Checks that the sender is a seller holding every attribute required of sellers
+pub fn validate_bid_acceptor(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    // Only a seller can accept a buyer's bid
+    if !is_seller(deps.storage, &info.sender)? {
         return Err(UnauthorizedAsSeller);
     }
 
-    // Tokens must be minted before bid acceptance because the FM token is sent to the buyer
+    // The seller must hold every attribute required of sellers
+    if let Some(required_attributes) = retrieve_contract_config(deps.storage)?.required_attributes {
+        validate_required_attributes(deps, &info.sender, &required_attributes.sellers)?;
+    }
+    Ok(())
+}
+
+/// Checks that the trade is ready for bids to be accepted
+pub fn validate_bids_can_be_accepted(deps: Deps, env: &Env) -> Result<(), ContractError> {
+    // Bids on a sealed-bid auction can only be accepted once every bid has had the chance to be revealed
+    if let Some(auction) = retrieve_contract_config(deps.storage)?.auction {
+        if env.block.time < auction.reveal_deadline {
+            return Err(AuctionNotClosed);
+        }
+    }
+
+    // Tokens must be minted before bid acceptance because the FM token is sent to the buyers
     // upon bid acceptance
-    let token_data = match retrieve_optional_token_data_state(deps.storage)? {
-        None => return Err(TokensNotMinted),
-        Some(token_data) => token_data,
-    };
+    if retrieve_optional_token_data_state(deps.storage)?.is_none() {
+        return Err(TokensNotMinted);
+    }
 
-    let bidder_address = deps.api.addr_validate(&bidder_address_str)?;
+    // Return an error if the seller has already accepted a set of bids
+    if let Some(buyer) = retrieve_buyer_list_state(deps.storage)?.first() {
+        return Err(BidPreviouslyAccepted {
+            address: buyer.buyer_address.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Saves a buyer for each accepted bid and transfers them their share of the forward market tokens,
+/// once the route has checked the trade with [validate_accept_bid]
+pub fn accept_bids(
+    deps: DepsMut,
+    env: &Env,
+    accepted_bids: Vec<AcceptedBid>,
+) -> Result<Response, ContractError> {
+    let config = retrieve_contract_config(deps.storage)?;
+    let token_data = retrieve_token_data_state(deps.storage)?;
 
-    // Make sure the bidder address exists in the list of bids
     let bid_list = retrieve_bid_list_state(deps.storage)?;
-    let bid: Option<Bid> = bid_list.bids.into_iter().find(|existing_bid| -> bool {
-        existing_bid.buyer_address.to_string() == bidder_address_str
-    });
-
-    match bid {
-        None => {
-            return Err(BidDoesNotExist {
-                address: bidder_address.to_string(),
-            });
+    let mut bidder_addresses: Vec<Addr> = vec![];
+    for accepted_bid in &accepted_bids {
+        let bidder_address = deps.api.addr_validate(&accepted_bid.bidder_address)?;
+
+        // A bidder can only be allocated a single share of the forward market tokens
+        if bidder_addresses.contains(&bidder_address) {
+            return Err(InvalidBidAllocation);
         }
-        // Check the provided hash against the hash in the state to make sure the seller is
-        // accepting the terms the agree to
-        Some(bid_state) => {
-            if bid_state.agreement_terms_hash != agreement_terms_hash {
-                return Err(InvalidAgreementTermsHash);
+
+        // Make sure the bidder address exists in the list of bids
+        let bid: Option<&Bid> = bid_list
+            .bids
+            .iter()
+            .find(|existing_bid| -> bool { existing_bid.buyer_address == bidder_address });
+
+        match bid {
+            None => {
+                return Err(BidDoesNotExist {
+                    address: bidder_address.to_string(),
+                });
+            }
+            // Check the provided hash against the hash in the state to make sure the seller is
+            // accepting the terms the agree to
+            Some(bid_state) => {
+                if bid_state.agreement_terms_hash != accepted_bid.agreement_terms_hash {
+                    return Err(InvalidAgreementTermsHash);
+                }
+                // The bid must not have been revised since the seller reviewed it
+                if let Some(expected_revision) = accepted_bid.expected_revision {
+                    if bid_state.revision != expected_revision {
+                        return Err(BidRevisionMismatch {
+                            address: bidder_address.to_string(),
+                            expected_revision,
+                            revision: bid_state.revision,
+                        });
+                    }
+                }
             }
         }
+        // The bidder must still hold every attribute required of buyers
+        if let Some(required_attributes) = &config.required_attributes {
+            validate_required_attributes(
+                deps.as_ref(),
+                &bidder_address,
+                &required_attributes.buyers,
+            )?;
+        }
+        bidder_addresses.push(bidder_address);
     }
 
-    // Return an error if the seller has already accepted a bid
-    match retrieve_optional_buyer_state(deps.storage)? {
-        None => {}
-        Some(buyer) => {
-            return Err(BidPreviouslyAccepted {
-                address: buyer.buyer_address.to_string(),
-            })
-        }
+    // Split the forward market tokens across the accepted bids
+    let allocations: Vec<Uint128> = accepted_bids.iter().map(|bid| bid.allocation).collect();
+    let token_amounts = allocate_tokens_pro_rata(token_data.token_count, &allocations)?;
+
+    // Every bidder that was not accepted gets their collateral back, while the accepted buyers must
+    // accept the finalized pools before the acceptance deadline or forfeit theirs
+    let mut response = Response::new().add_messages(release_bid_collateral(
+        deps.storage,
+        env,
+        |bidder_address| !bidder_addresses.contains(bidder_address),
+    )?);
+    if let Some(collateral_config) = retrieve_contract_config(deps.storage)?.bid_collateral {
+        save_acceptance_deadline_state(
+            deps.storage,
+            &env.block
+                .time
+                .plus_seconds(collateral_config.acceptance_window),
+        )?;
+    }
+
+    // The buyers' tokens are withdrawn from the forward market marker, which needs withdraw access
+    validate_marker_access(
+        &deps,
+        &token_data.token_denom,
+        env.contract.address.as_str(),
+        &[Access::Withdraw],
+    )?;
+
+    for ((accepted_bid, bidder_address), token_amount) in accepted_bids
+        .into_iter()
+        .zip(bidder_addresses)
+        .zip(token_amounts)
+    {
+        let buyer = Buyer {
+            buyer_address: bidder_address.clone(),
+            buyer_has_accepted_pools: false,
+            agreement_terms_hash: accepted_bid.agreement_terms_hash,
+            token_amount,
+        };
+        save_buyer_state(deps.storage, &buyer)?;
+
+        // Now that we have a buyer, we can give them their share of the forward market tokens
+        response = response
+            .add_messages(create_transfer_tokens_message(
+                env.contract.address.to_string(),
+                token_data.token_denom.clone(),
+                token_amount,
+                bidder_address.to_string(),
+            ))
+            .add_attribute("buyer", format!("{:?}", buyer));
     }
 
-    let buyer = Buyer {
-        buyer_address: bidder_address.clone(),
-        buyer_has_accepted_pools: false,
-        agreement_terms_hash: agreement_terms_hash.clone(),
-    };
-    save_buyer_state(deps.storage, &buyer)?;
-
-    // Now that we have a buyer, we can give them the forward market tokens
-    let create_token_messages = create_transfer_tokens_message(
-        env.contract.address.to_string(),
-        token_data.token_denom,
-        token_data.token_count,
-        bidder_address.to_string(),
-    );
-
-    Ok(Response::new()
-        .add_messages(create_token_messages)
-        .add_attribute("buyer", format!("{:?}", buyer)))
+    Ok(response)
 }
@@ -1,41 +1,52 @@
 use crate::error::ContractError;
 use crate::error::ContractError::{
-    IllegalAcceptingParty, IllegalPoolAcceptanceRequest, InvalidOfferHash, PoolAlreadyAccepted,
+    IllegalAcceptingParty, IllegalPoolAcceptanceRequest, InvalidAcceptedOffers, InvalidOfferHash,
+    OfferRevisionMismatch, PoolAlreadyAccepted,
 };
+use crate::msg::AcceptedOffer;
 use crate::storage::state_store::{
-    retrieve_optional_buyer_state, retrieve_seller_state, save_buyer_state,
+    retrieve_optional_buyer_state, retrieve_seller_list_state, save_buyer_state,
 };
-use crate::util::helpers::{buyer_has_accepted, is_buyer, seller_has_finalized};
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use crate::util::helpers::any_seller_has_finalized;
+use cosmwasm_std::{Deps, DepsMut, MessageInfo, Response};
 
 pub fn execute_accept_finalized_pools(
     deps: DepsMut,
     info: MessageInfo,
-    offer_hash: String,
+    offers: Vec<AcceptedOffer>,
 ) -> Result<Response, ContractError> {
-    // Only the buyer can accept the finalized pool list
-    if !is_buyer(&deps, &info)? {
-        return Err(IllegalAcceptingParty);
-    }
+    validate_accept_finalized_pools(deps.as_ref(), &info)?;
 
-    // If the seller hasn't finalized yet, the buyer can't accept
-    if !seller_has_finalized(&deps)? {
-        return Err(IllegalPoolAcceptanceRequest);
+    // The buyer accepts the combined allocation, so there must be exactly one accepted offer for
+    // every seller that has finalized a list of pools
+    let finalized_sellers: Vec<_> = retrieve_seller_list_state(deps.storage)?
+        .into_iter()
+        .filter(|seller| !seller.pools.is_empty())
+        .collect();
+    if offers.len() != finalized_sellers.len() {
+        return Err(InvalidAcceptedOffers);
     }
 
-    // If the buyer has already accepted the pool return an error
-    if buyer_has_accepted(&deps)? {
-        return Err(PoolAlreadyAccepted);
-    }
-
-    let mut current_buyer = retrieve_optional_buyer_state(deps.storage)?.unwrap();
-
-    let seller_state = retrieve_seller_state(deps.storage)?;
-
-    if offer_hash != seller_state.offer_hash {
-        return Err(InvalidOfferHash);
+    for seller_state in finalized_sellers {
+        let offer = offers
+            .iter()
+            .find(|offer| offer.seller_address == seller_state.seller_address.as_str())
+            .ok_or(InvalidAcceptedOffers)?;
+        if offer.offer_hash != seller_state.offer_hash {
+            return Err(InvalidOfferHash);
+        }
+        // The buyer must have reviewed the revision of the offer that is currently in place
+        if offer.offer_revision != seller_state.offer_revision {
+            return Err(OfferRevisionMismatch {
+                address: seller_state.seller_address.to_string(),
+                expected_revision: offer.offer_revision,
+                revision: seller_state.offer_revision,
+            });
+        }
     }
 
+    let mut current_buyer =
+        retrieve_optional_buyer_state(deps.storage, &info.sender)?.ok_or(IllegalAcceptingParty)?;
     current_buyer.buyer_has_accepted_pools = true;
 
     // Update the transaction state to reflect that the buyer has accepted
@@ -43,3 +54,26 @@ pub fn execute_accept_finalized_pools(
 
     Ok(Response::new().add_attribute("buyer", format!("{:?}", current_buyer)))
 }
+
+/// Checks that the sender is a buyer that can still accept the finalized pools
+pub fn validate_accept_finalized_pools(
+    deps: Deps,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
+    // Only an accepted buyer can accept the finalized pool list
+    let current_buyer = match retrieve_optional_buyer_state(deps.storage, &info.sender)? {
+        None => return Err(IllegalAcceptingParty),
+        Some(current_buyer) => current_buyer,
+    };
+
+    // If no seller has finalized yet, the buyer can't accept
+    if !any_seller_has_finalized(deps.storage)? {
+        return Err(IllegalPoolAcceptanceRequest);
+    }
+
+    // If the buyer has already accepted the pool return an error
+    if current_buyer.buyer_has_accepted_pools {
+        return Err(PoolAlreadyAccepted);
+    }
+    Ok(())
+}
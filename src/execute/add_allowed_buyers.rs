@@ -0,0 +1,25 @@
+use crate::error::ContractError;
+use crate::storage::state_store::{is_allowed_buyer, save_allowed_buyer_state};
+use crate::util::helpers::validate_allowed_buyers_update;
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+pub fn execute_add_allowed_buyers(
+    deps: DepsMut,
+    info: MessageInfo,
+    allowed_buyers: Vec<String>,
+) -> Result<Response, ContractError> {
+    validate_allowed_buyers_update(deps.storage, &info.sender)?;
+
+    // Adding an address that is already allowed leaves it in place, so only the addresses that
+    // were not yet allowed are reported
+    let mut added_buyers = vec![];
+    for buyer_str in allowed_buyers {
+        let buyer_addr = deps.api.addr_validate(&buyer_str)?;
+        if !is_allowed_buyer(deps.storage, &buyer_addr)? {
+            save_allowed_buyer_state(deps.storage, &buyer_addr)?;
+            added_buyers.push(buyer_addr);
+        }
+    }
+
+    Ok(Response::new().add_attribute("added_allowed_buyers", format!("{:?}", added_buyers)))
+}
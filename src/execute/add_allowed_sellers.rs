@@ -0,0 +1,25 @@
+use crate::error::ContractError;
+use crate::storage::state_store::{is_allowed_seller, save_allowed_seller_state};
+use crate::util::helpers::validate_allowed_sellers_update;
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+pub fn execute_add_allowed_sellers(
+    deps: DepsMut,
+    info: MessageInfo,
+    allowed_sellers: Vec<String>,
+) -> Result<Response, ContractError> {
+    validate_allowed_sellers_update(deps.storage, &info.sender)?;
+
+    // Adding an address that is already allowed leaves it in place, so only the addresses that
+    // were not yet allowed are reported
+    let mut added_sellers = vec![];
+    for seller_str in allowed_sellers {
+        let seller_addr = deps.api.addr_validate(&seller_str)?;
+        if !is_allowed_seller(deps.storage, &seller_addr)? {
+            save_allowed_seller_state(deps.storage, &seller_addr)?;
+            added_sellers.push(seller_addr);
+        }
+    }
+
+    Ok(Response::new().add_attribute("added_allowed_sellers", format!("{:?}", added_sellers)))
+}
@@ -1,38 +1,44 @@
 use crate::error::ContractError;
-use crate::error::ContractError::{MaxPrivateBuyersReached, UnauthorizedPrivateBuyer};
+use crate::error::ContractError::SealedBidRequired;
 use crate::storage::state_store::{
-    retrieve_bid_list_state, retrieve_contract_config, save_bid_list_state, Bid, BidList,
+    retrieve_bid_list_state, retrieve_contract_config, save_bid_list_state,
+    save_bid_revision_state, Bid, BidList,
 };
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use crate::util::helpers::{
+    escrow_bid_collateral, next_bid_revision, validate_bid_collateral, validate_bidder,
+    validate_required_attributes,
+};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128};
 
 pub fn execute_add_bidder(
-    deps: DepsMut,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     agreement_terms_hash: String,
+    price: Option<Uint128>,
 ) -> Result<Response, ContractError> {
+    validate_add_bid(deps.as_ref(), &info)?;
     let config = retrieve_contract_config(deps.storage)?;
     let bid_list = retrieve_bid_list_state(deps.storage)?;
 
-    // If using private buyers, make sure this buyer is allowed to submit a bid
-    if config.use_private_buyers {
-        if !config.allowed_buyers.contains(&info.sender) {
-            return Err(UnauthorizedPrivateBuyer);
-        }
-
-        if bid_list.bids.len() >= usize::try_from(config.max_bid_count).unwrap() {
-            return Err(MaxPrivateBuyersReached);
-        }
-    }
+    // Take the collateral required to place a bid
+    let collateral_messages = escrow_bid_collateral(&mut deps, &env, &info, &config)?;
 
-    // Remove any existing bid for this buyer because if one exists we want to replace it
-    let mut updated_bid_list: Vec<Bid> = bid_list
+    // Replace any existing bid for this buyer, keeping the replaced bid as a prior revision
+    let revision = next_bid_revision(deps.storage, &bid_list, &info.sender)?;
+    let (replaced_bids, mut updated_bid_list): (Vec<Bid>, Vec<Bid>) = bid_list
         .bids
         .into_iter()
-        .filter(|bid| bid.buyer_address != info.sender)
-        .collect();
+        .partition(|bid| bid.buyer_address == info.sender);
+    for replaced_bid in &replaced_bids {
+        save_bid_revision_state(deps.storage, replaced_bid)?;
+    }
     updated_bid_list.push(Bid {
         buyer_address: info.sender,
         agreement_terms_hash,
+        price,
+        submitted_at: env.block.time,
+        revision,
     });
 
     // Save the updated buyer state
@@ -43,5 +49,34 @@ pub fn execute_add_bidder(
         },
     )?;
 
-    Ok(Response::new().add_attribute("bid_list", format!("{:?}", updated_bid_list.clone())))
+    Ok(Response::new()
+        .add_messages(collateral_messages)
+        .add_attribute("bid_list", format!("{:?}", updated_bid_list.clone())))
+}
+
+/// Checks that the sender can place a bid with the funds attached, including the collateral the
+/// contract requires of bidders
+pub fn validate_add_bid(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    let config = retrieve_contract_config(deps.storage)?;
+
+    // Bids on a sealed-bid auction must go through the commit and reveal routes
+    if config.auction.is_some() {
+        return Err(SealedBidRequired);
+    }
+
+    // If using private buyers, make sure this buyer is allowed to submit a bid, and make sure the
+    // limit of bids has not been reached by other bidders
+    let other_bid_count = retrieve_bid_list_state(deps.storage)?
+        .bids
+        .iter()
+        .filter(|bid| bid.buyer_address != info.sender)
+        .count();
+    validate_bidder(deps.storage, &config, &info.sender, other_bid_count)?;
+
+    // The buyer must hold every attribute required of buyers
+    if let Some(required_attributes) = &config.required_attributes {
+        validate_required_attributes(deps, &info.sender, &required_attributes.buyers)?;
+    }
+
+    validate_bid_collateral(deps.storage, info, &config)
 }
@@ -2,17 +2,39 @@ use crate::error::ContractError;
 use crate::error::ContractError::{SellerAlreadyExists, UnauthorizedPrivateSeller};
 
 use crate::storage::state_store::{
-    retrieve_contract_config, retrieve_optional_seller_state, save_seller_state, Seller,
+    is_allowed_seller, retrieve_contract_config, retrieve_optional_seller_state, save_seller_state,
+    Seller,
 };
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use crate::util::helpers::validate_required_attributes;
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
 
 pub fn execute_add_seller(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     offer_hash: String,
 ) -> Result<Response, ContractError> {
-    // Make sure we haven't already set the seller config. If we have, return an error
-    match retrieve_optional_seller_state(deps.storage)? {
+    validate_add_seller(deps.as_ref(), &info)?;
+
+    // Store the seller information
+    let seller_state = Seller {
+        seller_address: info.sender.clone(),
+        pools: vec![],
+        offer_hash,
+        scope_addresses: vec![],
+        offer_revision: 1,
+        offer_updated_at: env.block.time,
+        received_pools: vec![],
+    };
+    save_seller_state(deps.storage, &seller_state)?;
+
+    Ok(Response::new().add_attribute("seller_state", format!("{:?}", seller_state)))
+}
+
+/// Checks that the sender can register as a seller
+pub fn validate_add_seller(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    // Make sure the sender hasn't already registered as a seller. If they have, return an error
+    match retrieve_optional_seller_state(deps.storage, &info.sender)? {
         None => {}
         Some(_) => return Err(SellerAlreadyExists),
     }
@@ -20,17 +42,13 @@ pub fn execute_add_seller(
     let config = retrieve_contract_config(deps.storage)?;
 
     // If using private sellers, make sure this seller is allowed to engage with the contract
-    if config.use_private_sellers && !config.allowed_sellers.contains(&info.sender) {
+    if config.use_private_sellers && !is_allowed_seller(deps.storage, &info.sender)? {
         return Err(UnauthorizedPrivateSeller);
     }
 
-    // Store the seller information
-    let seller_state = Seller {
-        seller_address: info.sender.clone(),
-        pool_denoms: vec![],
-        offer_hash,
-    };
-    save_seller_state(deps.storage, &seller_state)?;
-
-    Ok(Response::new().add_attribute("seller_state", format!("{:?}", seller_state)))
+    // The seller must hold every attribute required of sellers
+    if let Some(required_attributes) = &config.required_attributes {
+        validate_required_attributes(deps, &info.sender, &required_attributes.sellers)?;
+    }
+    Ok(())
 }
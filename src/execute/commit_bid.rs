@@ -0,0 +1,66 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{AuctionNotConfigured, CommitWindowClosed};
+use crate::storage::state_store::{
+    retrieve_bid_commitment_list_state, retrieve_contract_config, save_bid_commitment_state,
+    BidCommitment,
+};
+use crate::util::helpers::{
+    escrow_bid_collateral, validate_bid_collateral, validate_bidder, validate_required_attributes,
+};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
+
+pub fn execute_commit_bid(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    commitment: String,
+) -> Result<Response, ContractError> {
+    validate_commit_bid(deps.as_ref(), &env, &info)?;
+    let config = retrieve_contract_config(deps.storage)?;
+
+    // Take the collateral required to place a bid
+    let collateral_messages = escrow_bid_collateral(&mut deps, &env, &info, &config)?;
+
+    // Any existing commitment for this buyer is replaced
+    let bid_commitment = BidCommitment {
+        buyer_address: info.sender,
+        commitment: commitment.to_lowercase(),
+        committed_at: env.block.time,
+    };
+    save_bid_commitment_state(deps.storage, &bid_commitment)?;
+
+    Ok(Response::new()
+        .add_messages(collateral_messages)
+        .add_attribute("bid_commitment", format!("{:?}", bid_commitment)))
+}
+
+/// Checks that the sender can commit a sealed bid with the funds attached, including the collateral
+/// the contract requires of bidders
+pub fn validate_commit_bid(deps: Deps, env: &Env, info: &MessageInfo) -> Result<(), ContractError> {
+    let config = retrieve_contract_config(deps.storage)?;
+
+    // Commitments are only accepted when the contract is running a sealed-bid auction
+    let auction = match &config.auction {
+        None => return Err(AuctionNotConfigured),
+        Some(auction) => auction,
+    };
+
+    if env.block.time >= auction.commit_deadline {
+        return Err(CommitWindowClosed);
+    }
+
+    // If using private buyers, make sure this buyer is allowed to submit a bid, and make sure the
+    // limit of bids has not been reached by other bidders
+    let other_commitment_count = retrieve_bid_commitment_list_state(deps.storage)?
+        .iter()
+        .filter(|bid_commitment| bid_commitment.buyer_address != info.sender)
+        .count();
+    validate_bidder(deps.storage, &config, &info.sender, other_commitment_count)?;
+
+    // The buyer must hold every attribute required of buyers
+    if let Some(required_attributes) = &config.required_attributes {
+        validate_required_attributes(deps, &info.sender, &required_attributes.buyers)?;
+    }
+
+    validate_bid_collateral(deps.storage, info, &config)
+}
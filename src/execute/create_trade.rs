@@ -0,0 +1,170 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    InvalidEmptyDealerConfig, InvalidMaxBidCount, InvalidVisibilityConfig,
+    UnauthorizedTradeCreation,
+};
+use crate::instantiate::instantiate_contract::validate_trade_config;
+use crate::msg::Action;
+use crate::storage::history_storage::HistoryStorage;
+use crate::storage::state_store::{
+    retrieve_contract_config, retrieve_trade_count, save_allowed_buyer_state,
+    save_allowed_seller_state, save_bid_list_state, save_trade_count, save_trade_overrides,
+    AuctionConfig, BidCollateralConfig, BidList, ConfigOverride, FeeConfig, MarginConfig,
+    PoolEligibilityConfig, RequiredAttributesConfig, TradeOverrides,
+};
+use crate::storage::trade_storage::TradeStorage;
+use crate::util::helpers::is_contract_admin;
+use crate::util::history::record_history;
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response};
+
+/// The optional sections of the configuration that a new trade sets for itself
+pub struct TradeConfigOverrides {
+    pub auction: Option<AuctionConfig>,
+    pub bid_collateral: Option<BidCollateralConfig>,
+    pub margin: Option<MarginConfig>,
+    pub fees: Option<FeeConfig>,
+    pub pool_eligibility: Option<PoolEligibilityConfig>,
+    pub required_attributes: Option<RequiredAttributesConfig>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_trade(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    use_private_sellers: Option<bool>,
+    use_private_buyers: Option<bool>,
+    allowed_sellers: Option<Vec<String>>,
+    allowed_buyers: Option<Vec<String>>,
+    max_bid_count: Option<u32>,
+    dealers: Option<Vec<String>>,
+    accepted_token_contracts: Option<Vec<String>>,
+    overrides: TradeConfigOverrides,
+) -> Result<Response, ContractError> {
+    validate_create_trade(deps.as_ref(), &info)?;
+
+    // The new trade reads the shared configuration of the contract, and only the fields that are
+    // given are recorded as its own overrides. A new trade always starts enabled.
+    let mut config_overrides = vec![ConfigOverride::IsDisabled(false)];
+    if let Some(use_private_sellers) = use_private_sellers {
+        config_overrides.push(ConfigOverride::UsePrivateSellers(use_private_sellers));
+    }
+    if let Some(use_private_buyers) = use_private_buyers {
+        config_overrides.push(ConfigOverride::UsePrivateBuyers(use_private_buyers));
+    }
+    if let Some(max_bid_count) = max_bid_count {
+        if max_bid_count == 0 {
+            return Err(InvalidMaxBidCount);
+        }
+        config_overrides.push(ConfigOverride::MaxBidCount(Some(max_bid_count)));
+    }
+    if let Some(dealers) = dealers {
+        if dealers.is_empty() {
+            return Err(InvalidEmptyDealerConfig);
+        }
+        config_overrides.push(ConfigOverride::Dealers(validate_addresses(&deps, dealers)?));
+    }
+    if let Some(accepted_token_contracts) = accepted_token_contracts {
+        config_overrides.push(ConfigOverride::AcceptedTokenContracts(validate_addresses(
+            &deps,
+            accepted_token_contracts,
+        )?));
+    }
+    if let Some(auction) = overrides.auction {
+        config_overrides.push(ConfigOverride::Auction(Some(auction)));
+    }
+    if let Some(bid_collateral) = overrides.bid_collateral {
+        config_overrides.push(ConfigOverride::BidCollateral(Some(bid_collateral)));
+    }
+    if let Some(margin) = overrides.margin {
+        config_overrides.push(ConfigOverride::Margin(Some(margin)));
+    }
+    if let Some(fees) = overrides.fees {
+        config_overrides.push(ConfigOverride::Fees(Some(fees)));
+    }
+    if let Some(pool_eligibility) = overrides.pool_eligibility {
+        config_overrides.push(ConfigOverride::PoolEligibility(Some(pool_eligibility)));
+    }
+    if let Some(required_attributes) = overrides.required_attributes {
+        config_overrides.push(ConfigOverride::RequiredAttributes(Some(
+            required_attributes,
+        )));
+    }
+
+    let mut config = retrieve_contract_config(deps.storage)?;
+    for config_override in &config_overrides {
+        config_override.apply(&mut config);
+    }
+
+    // The shared sections are checked again, since an auction of the contract may have deadlines
+    // that have already passed
+    validate_trade_config(&deps, &env, &config)?;
+
+    // A private trade reads the shared allowed list unless it is given its own, while a trade made
+    // public by its overrides keeps its own empty list
+    let allowed_sellers = match (config.use_private_sellers, allowed_sellers) {
+        (true, Some(allowed_sellers)) => Some(validate_addresses(&deps, allowed_sellers)?),
+        (false, Some(allowed_sellers)) if !allowed_sellers.is_empty() => {
+            return Err(InvalidVisibilityConfig)
+        }
+        (false, _) if use_private_sellers.is_some() => Some(vec![]),
+        _ => None,
+    };
+    let allowed_buyers = match (config.use_private_buyers, allowed_buyers) {
+        (true, Some(allowed_buyers)) => Some(validate_addresses(&deps, allowed_buyers)?),
+        (false, Some(allowed_buyers)) if !allowed_buyers.is_empty() => {
+            return Err(InvalidVisibilityConfig)
+        }
+        (false, _) if use_private_buyers.is_some() => Some(vec![]),
+        _ => None,
+    };
+
+    let trade_id = retrieve_trade_count(deps.storage)? + 1;
+    save_trade_count(deps.storage, trade_id)?;
+
+    // The creation is the first entry in the history of the new trade
+    let mut trade_storage = TradeStorage::new(deps.storage, Some(trade_id));
+    let mut history_storage = HistoryStorage::new(&mut trade_storage);
+    save_trade_overrides(
+        &mut history_storage,
+        &TradeOverrides {
+            config: config_overrides,
+            allowed_sellers: allowed_sellers.is_some(),
+            allowed_buyers: allowed_buyers.is_some(),
+        },
+    )?;
+    save_bid_list_state(&mut history_storage, &BidList { bids: vec![] })?;
+    for seller_address in allowed_sellers.iter().flatten() {
+        save_allowed_seller_state(&mut history_storage, seller_address)?;
+    }
+    for buyer_address in allowed_buyers.iter().flatten() {
+        save_allowed_buyer_state(&mut history_storage, buyer_address)?;
+    }
+    let writes = history_storage.into_writes();
+    record_history(
+        &mut trade_storage,
+        &env,
+        info.sender,
+        Action::CreateTrade.name(),
+        writes,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("trade_id", trade_id.to_string())
+        .add_attribute("contract_config", format!("{:?}", config)))
+}
+
+fn validate_addresses(deps: &DepsMut, addresses: Vec<String>) -> Result<Vec<Addr>, ContractError> {
+    addresses
+        .iter()
+        .map(|address| Ok(deps.api.addr_validate(address)?))
+        .collect()
+}
+
+/// Checks that the sender can create trades, which only the contract admin can do
+pub fn validate_create_trade(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if !is_contract_admin(deps.storage, &info.sender)? {
+        return Err(UnauthorizedTradeCreation);
+    }
+    Ok(())
+}
@@ -1,48 +1,38 @@
 use crate::error::ContractError;
 use crate::error::ContractError::{
-    IllegalConfirmationRequest, InvalidConfirmationRequest, MissingMarkerBaseAccount,
-    UnauthorizedPrivateSeller,
+    EscrowMismatch, IllegalConfirmationRequest, InsufficientFeeDeposit, InvalidConfirmationRequest,
+    MissingMarkerBaseAccount, OutstandingMargin, UnauthorizedPrivateSeller, UnreconciledEscrow,
 };
+use crate::msg::EscrowMismatchReason;
 use crate::storage::state_store::{
-    retrieve_contract_config, retrieve_seller_state, retrieve_token_data_state,
+    is_allowed_seller, retrieve_contract_config, retrieve_margin_account_list_state,
+    retrieve_seller_list_state, retrieve_token_data_state, save_seller_state,
     save_settlement_data_state, SettlementData,
 };
 use crate::util::helpers::{
-    buyer_has_accepted, get_balance, get_marker, is_dealer, seller_has_finalized,
-};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
-use provwasm_std::types::provenance::marker::v1::{
-    MarkerQuerier, MsgDeleteAccessRequest, MsgTransferRequest,
+    all_buyers_have_accepted, any_seller_has_finalized, calculate_settlement_fees,
+    create_escrow_payment_message, create_pool_transfer_message,
+    create_send_coin_back_to_seller_messages, get_marker, is_dealer, reconcile_escrow,
+    release_bid_collateral, release_fee_deposits, release_margin,
 };
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+use provwasm_std::types::provenance::marker::v1::{MarkerQuerier, MsgDeleteAccessRequest};
+use rust_decimal::Decimal;
 
 pub fn execute_dealer_confirm(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    // Only a dealer can confirm so throw an error if the sender is not in the list of dealers
-    if !is_dealer(&deps, &info)? {
-        return Err(IllegalConfirmationRequest);
-    }
-
-    // The seller must have finalized and the buyer must have accepted before the transfer can take place
-    if !seller_has_finalized(&deps)? || !buyer_has_accepted(&deps)? {
-        return Err(InvalidConfirmationRequest);
-    }
+    validate_dealer_confirm(deps.as_ref(), &env, &info)?;
 
     let config = retrieve_contract_config(deps.storage)?;
-    let seller_state = retrieve_seller_state(deps.storage)?;
+    let finalized_sellers: Vec<_> = retrieve_seller_list_state(deps.storage)?
+        .into_iter()
+        .filter(|seller| !seller.pools.is_empty())
+        .collect();
     let token_data = retrieve_token_data_state(deps.storage)?;
-
-    // A private contract should not allow a state where the accepted seller is not in the list of allowed
-    // sellers, but before we transfer anything run a sanity check
-    if config.use_private_sellers
-        && !config
-            .allowed_sellers
-            .contains(&seller_state.seller_address)
-    {
-        return Err(UnauthorizedPrivateSeller);
-    }
+    let settlement_fees = calculate_settlement_fees(deps.storage)?;
 
     // Create a response to add the messages to
     let mut response = Response::new();
@@ -62,16 +52,71 @@ pub fn execute_dealer_confirm(
         Some(base_account) => base_account.address,
     };
 
-    // Iterate over the list of denoms so that we can update the value owner of the pool markers to be the
-    // forward market marker
-    for denom in seller_state.pool_denoms {
-        let held_coin = get_balance(&deps, denom.clone())?;
-        response = response.add_message(MsgTransferRequest {
-            amount: Some(held_coin.coin),
-            administrator: env.contract.address.to_string(),
-            from_address: env.contract.address.to_string(),
-            to_address: forward_market_base_address.clone(),
-        });
+    // Iterate over every seller's list of pools so that we can move each of them from the contract's
+    // escrow to the forward market marker
+    for seller_state in finalized_sellers {
+        for pool in seller_state.pools {
+            response = response.add_message(create_pool_transfer_message(
+                &deps,
+                pool,
+                env.contract.address.as_str(),
+                forward_market_base_address.clone(),
+            )?);
+        }
+    }
+
+    // Pools received from sellers that never finalized are not part of the trade, so they are returned
+    for mut seller_state in retrieve_seller_list_state(deps.storage)? {
+        if seller_state.received_pools.is_empty() {
+            continue;
+        }
+        response = response.add_messages(create_send_coin_back_to_seller_messages(
+            &deps,
+            env.contract.address.to_string(),
+            seller_state.seller_address.to_string(),
+            seller_state.received_pools,
+        )?);
+        seller_state.received_pools = vec![];
+        save_seller_state(deps.storage, &seller_state)?;
+    }
+
+    // The buyers have seen the transaction through, so their bid collateral is returned to them
+    response = response.add_messages(release_bid_collateral(deps.storage, &env, |_| true)?);
+
+    // The trade is complete, so each party gets their remaining margin back
+    response = response.add_messages(release_margin(deps.storage)?);
+
+    // Pay the settlement fees to the platform and the settling dealer, and return any excess deposits
+    if let Some(fee_config) = config.fees {
+        let total_fees = settlement_fees
+            .iter()
+            .fold(Uint128::zero(), |total, settlement_fee| {
+                total + settlement_fee.amount
+            });
+        let platform_fees = total_fees.multiply_ratio(fee_config.platform_share_bps, 10_000u128);
+        let dealer_fees = total_fees - platform_fees;
+        if let Some(platform_address) = &fee_config.platform_address {
+            if !platform_fees.is_zero() {
+                response = response.add_message(create_escrow_payment_message(
+                    &fee_config.denom,
+                    fee_config.is_restricted_marker,
+                    env.contract.address.to_string(),
+                    platform_address.to_string(),
+                    platform_fees,
+                ));
+            }
+        }
+        if !dealer_fees.is_zero() {
+            response = response.add_message(create_escrow_payment_message(
+                &fee_config.denom,
+                fee_config.is_restricted_marker,
+                env.contract.address.to_string(),
+                info.sender.to_string(),
+                dealer_fees,
+            ));
+        }
+        response =
+            response.add_messages(release_fee_deposits(deps.storage, &env, &settlement_fees)?);
     }
 
     save_settlement_data_state(
@@ -91,3 +136,77 @@ pub fn execute_dealer_confirm(
 
     Ok(response)
 }
+
+/// Checks that the sender is a dealer and that the trade is ready to settle, with every party's fees
+/// deposited, every margin call met and every finalized pool held in escrow by the contract
+pub fn validate_dealer_confirm(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
+    // Only a dealer can confirm so throw an error if the sender is not in the list of dealers
+    if !is_dealer(deps, &info.sender)? {
+        return Err(IllegalConfirmationRequest);
+    }
+
+    // The sellers must have finalized and every buyer must have accepted before the transfer can take place
+    if !any_seller_has_finalized(deps.storage)? || !all_buyers_have_accepted(deps.storage)? {
+        return Err(InvalidConfirmationRequest);
+    }
+
+    // A private contract should not allow a state where an accepted seller is not in the list of allowed
+    // sellers, but before we transfer anything run a sanity check
+    if retrieve_contract_config(deps.storage)?.use_private_sellers {
+        for seller in retrieve_seller_list_state(deps.storage)? {
+            if !seller.pools.is_empty() && !is_allowed_seller(deps.storage, &seller.seller_address)?
+            {
+                return Err(UnauthorizedPrivateSeller);
+            }
+        }
+    }
+
+    // Every party must have deposited enough to cover their settlement fee
+    if let Some(settlement_fee) = calculate_settlement_fees(deps.storage)?
+        .iter()
+        .find(|settlement_fee| settlement_fee.deposited < settlement_fee.amount)
+    {
+        return Err(InsufficientFeeDeposit {
+            address: settlement_fee.payer.to_string(),
+            required: settlement_fee.amount,
+        });
+    }
+
+    // Settlement pays out the margin balances, so every margin call must be met and all variation
+    // margin paid before then
+    if let Some(margin_account) = retrieve_margin_account_list_state(deps.storage)?
+        .into_iter()
+        .find(|margin_account| {
+            margin_account.margin_call_deadline.is_some() || margin_account.owed > Decimal::ZERO
+        })
+    {
+        return Err(OutstandingMargin {
+            address: margin_account.owner.to_string(),
+        });
+    }
+
+    // Every pool must be held in escrow by the contract before it can be settled. An NFT is named by
+    // its token contract and token id
+    for pool_escrow in reconcile_escrow(deps.storage, &deps.querier, &env.contract.address)? {
+        let denom = match &pool_escrow.token_id {
+            None => pool_escrow.denom,
+            Some(token_id) => format!("{}/{}", pool_escrow.denom, token_id),
+        };
+        match pool_escrow.mismatch {
+            None => {}
+            Some(EscrowMismatchReason::WrongHolder) => {
+                return Err(EscrowMismatch {
+                    denom,
+                    holder: pool_escrow.holder.unwrap_or_default(),
+                })
+            }
+            Some(_) => return Err(UnreconciledEscrow { denom }),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    IllegalMarginDefault, MarginNotConfigured, UnauthorizedMarginRequest,
+};
+use crate::execute::disable_contract::disable_contract;
+use crate::storage::state_store::{
+    retrieve_contract_config, retrieve_optional_margin_account_state, save_margin_account_state,
+    MarginConfig,
+};
+use crate::util::helpers::{credit_counterparties, is_dealer, is_under_margined};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
+
+pub fn execute_declare_margin_default(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let margin_config = validate_declare_margin_default(deps.as_ref(), &info)?;
+
+    // The party must have let a margin call pass its deadline without topping up
+    let defaulting_address = deps.api.addr_validate(&address)?;
+    let mut defaulting_account =
+        match retrieve_optional_margin_account_state(deps.storage, &defaulting_address)? {
+            None => return Err(IllegalMarginDefault),
+            Some(margin_account) => margin_account,
+        };
+    match defaulting_account.margin_call_deadline {
+        Some(deadline)
+            if env.block.time >= deadline
+                && is_under_margined(deps.storage, &margin_config, &defaulting_account)? => {}
+        _ => return Err(IllegalMarginDefault),
+    }
+
+    // The defaulting party's margin goes to the other side of the trade in proportion to exposure.
+    // Only the margin the party actually holds can be paid, so anything it owes beyond that is
+    // reported as unpaid
+    let credited = credit_counterparties(
+        deps.storage,
+        &defaulting_address,
+        defaulting_account.balance,
+    )?;
+    defaulting_account.balance -= credited;
+    save_margin_account_state(deps.storage, &defaulting_account)?;
+    let unpaid_margin = defaulting_account.owed;
+
+    // The trade cannot go ahead, so unwind the contract and pay out the remaining margin
+    Ok(disable_contract(deps, &env)?
+        .add_attribute("margin_default", defaulting_address)
+        .add_attribute("unpaid_margin", unpaid_margin.to_string()))
+}
+
+/// Checks that the sender is a dealer of a trade that uses margin, returning the margin configuration.
+/// The same checks apply to a mark to market, which also changes the margin accounts
+pub fn validate_declare_margin_default(
+    deps: Deps,
+    info: &MessageInfo,
+) -> Result<MarginConfig, ContractError> {
+    // Only a dealer can declare a default
+    if !is_dealer(deps, &info.sender)? {
+        return Err(UnauthorizedMarginRequest);
+    }
+
+    retrieve_contract_config(deps.storage)?
+        .margin
+        .ok_or(MarginNotConfigured)
+}
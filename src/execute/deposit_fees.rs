@@ -0,0 +1,66 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{FeesNotConfigured, InvalidFeeDeposit, UnauthorizedFeeDeposit};
+use crate::storage::state_store::{
+    retrieve_contract_config, retrieve_optional_fee_deposit_state, save_fee_deposit_state,
+    FeeDeposit,
+};
+use crate::util::helpers::{create_escrow_deposit_message, is_buyer, is_seller};
+use cosmwasm_std::{coins, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn execute_deposit_fees(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    validate_deposit_fees(deps.as_ref(), &info)?;
+    let fee_config = retrieve_contract_config(deps.storage)?
+        .fees
+        .ok_or(FeesNotConfigured)?;
+
+    // Fees held in a restricted marker are pulled from the sender, otherwise they must be attached
+    let invalid_deposit = InvalidFeeDeposit {
+        denom: fee_config.denom.clone(),
+    };
+    if amount.is_zero() {
+        return Err(invalid_deposit);
+    }
+    let mut response = Response::new();
+    if fee_config.is_restricted_marker {
+        if !info.funds.is_empty() {
+            return Err(invalid_deposit);
+        }
+        response = response.add_message(create_escrow_deposit_message(
+            &fee_config.denom,
+            amount,
+            env.contract.address.to_string(),
+            info.sender.to_string(),
+        ));
+    } else if info.funds != coins(amount.u128(), &fee_config.denom) {
+        return Err(invalid_deposit);
+    }
+
+    let mut fee_deposit = retrieve_optional_fee_deposit_state(deps.storage, &info.sender)?
+        .unwrap_or(FeeDeposit {
+            payer: info.sender.clone(),
+            amount: Uint128::zero(),
+        });
+    fee_deposit.amount += amount;
+    save_fee_deposit_state(deps.storage, &fee_deposit)?;
+
+    Ok(response.add_attribute("fee_deposit", format!("{:?}", fee_deposit)))
+}
+
+/// Checks that fees are configured and that the sender is a party to the trade. The amount and the
+/// attached funds are checked by the route
+pub fn validate_deposit_fees(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if retrieve_contract_config(deps.storage)?.fees.is_none() {
+        return Err(FeesNotConfigured);
+    }
+
+    // Only the parties to the trade pay fees
+    if !is_buyer(deps.storage, &info.sender)? && !is_seller(deps.storage, &info.sender)? {
+        return Err(UnauthorizedFeeDeposit);
+    }
+    Ok(())
+}
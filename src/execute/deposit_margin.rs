@@ -0,0 +1,57 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    InvalidMarginDeposit, MarginNotConfigured, UnauthorizedMarginDeposit,
+};
+use crate::storage::state_store::{retrieve_contract_config, save_margin_account_state};
+use crate::util::helpers::{
+    credit_counterparties, is_buyer, is_seller, is_under_margined, retrieve_margin_account,
+};
+use cosmwasm_std::{Deps, DepsMut, MessageInfo, Response, Uint128};
+use rust_decimal::Decimal;
+
+pub fn execute_deposit_margin(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let deposit = validate_deposit_margin(deps.as_ref(), &info)?;
+    let margin_config = retrieve_contract_config(deps.storage)?
+        .margin
+        .ok_or(MarginNotConfigured)?;
+
+    // The deposit first pays the other side of the trade any variation margin the party owes
+    let deposit = Decimal::from(deposit.u128());
+    let mut margin_account = retrieve_margin_account(deps.storage, &info.sender)?;
+    let settlement =
+        credit_counterparties(deps.storage, &info.sender, deposit.min(margin_account.owed))?;
+    margin_account.owed -= settlement;
+    margin_account.balance += deposit - settlement;
+
+    // A deposit that brings the account back to the maintenance margin meets any open margin call
+    if margin_account.margin_call_deadline.is_some()
+        && !is_under_margined(deps.storage, &margin_config, &margin_account)?
+    {
+        margin_account.margin_call_deadline = None;
+    }
+    save_margin_account_state(deps.storage, &margin_account)?;
+
+    Ok(Response::new().add_attribute("margin_account", format!("{:?}", margin_account)))
+}
+
+/// Checks that margin is configured, that the sender is a party to the trade and that a single amount
+/// of the margin denom is attached, returning the amount deposited
+pub fn validate_deposit_margin(deps: Deps, info: &MessageInfo) -> Result<Uint128, ContractError> {
+    let margin_config = match retrieve_contract_config(deps.storage)?.margin {
+        None => return Err(MarginNotConfigured),
+        Some(margin_config) => margin_config,
+    };
+
+    // Only the parties to the trade hold margin
+    if !is_buyer(deps.storage, &info.sender)? && !is_seller(deps.storage, &info.sender)? {
+        return Err(UnauthorizedMarginDeposit);
+    }
+
+    // The deposit must be a single amount of the margin denom
+    match info.funds.as_slice() {
+        [coin] if coin.denom == margin_config.denom && !coin.amount.is_zero() => Ok(coin.amount),
+        _ => Err(InvalidMarginDeposit {
+            denom: margin_config.denom,
+        }),
+    }
+}
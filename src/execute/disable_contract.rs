@@ -1,47 +1,72 @@
 use crate::error::ContractError;
-use crate::error::ContractError::{InvalidDealerResetRequest, UnauthorizedDisableRequest};
+use crate::error::ContractError::UnauthorizedDisableRequest;
 use crate::storage::state_store::{
-    clear_buyer_state, retrieve_contract_config, retrieve_optional_seller_state,
-    save_contract_config, save_seller_state,
+    clear_buyer_state, retrieve_contract_config, retrieve_seller_list_state, save_contract_config,
+    save_seller_state,
 };
 use crate::util::helpers::{
-    create_send_coin_back_to_seller_messages, is_contract_admin, is_dealer, seller_has_finalized,
+    create_send_coin_back_to_seller_messages, is_contract_admin, is_dealer, release_bid_collateral,
+    release_fee_deposits, release_margin,
 };
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
 
 pub fn execute_disable_contract(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
+    validate_disable_contract(deps.as_ref(), &info)?;
+
+    disable_contract(deps, &env)
+}
+
+/// Checks that the sender can disable the contract
+pub fn validate_disable_contract(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
     // In order to disable the contract you must be either the contract admin or a dealer
-    if !is_contract_admin(&deps, &info)? && !is_dealer(&deps, &info)? {
+    if !is_contract_admin(deps.storage, &info.sender)? && !is_dealer(deps, &info.sender)? {
         return Err(UnauthorizedDisableRequest);
     }
+    Ok(())
+}
 
+/// Unwinds the contract by returning every escrowed asset to its owner, then marks it as disabled.
+/// Shared by the routes that can end the contract before settlement.
+pub fn disable_contract(deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
     let mut response: Response = Response::new();
-    if seller_has_finalized(&deps)? {
-        let mut seller = match retrieve_optional_seller_state(deps.storage)? {
-            None => return Err(InvalidDealerResetRequest),
-            Some(seller) => seller,
-        };
+
+    // Return each seller's finalized and received pools to that seller
+    for mut seller in retrieve_seller_list_state(deps.storage)? {
+        if seller.pools.is_empty() && seller.received_pools.is_empty() {
+            continue;
+        }
 
         let transfer_messages = create_send_coin_back_to_seller_messages(
             &deps,
             env.contract.address.to_string(),
             seller.seller_address.to_string(),
-            seller.pool_denoms,
+            [seller.pools, seller.received_pools].concat(),
         )?;
 
         if !transfer_messages.is_empty() {
             response = response.add_messages(transfer_messages);
         }
 
-        // The contract no longer owns the denoms, so clear the list
-        seller.pool_denoms = vec![];
+        // The contract no longer holds the pools, so clear the list
+        seller.pools = vec![];
+        seller.received_pools = vec![];
+        seller.scope_addresses = vec![];
         save_seller_state(deps.storage, &seller)?;
     }
 
+    // Return any bid collateral to the bidders that posted it
+    response = response.add_messages(release_bid_collateral(deps.storage, env, |_| true)?);
+
+    // Return the remaining margin to each party
+    response = response.add_messages(release_margin(deps.storage)?);
+
+    // No fees are owed on a contract that never settles, so every fee deposit is returned
+    response = response.add_messages(release_fee_deposits(deps.storage, env, &[])?);
+
     clear_buyer_state(deps.storage);
 
     // Contract is ok to disable, set the flag
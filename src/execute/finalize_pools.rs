@@ -1,11 +1,18 @@
 use crate::error::ContractError;
 use crate::error::ContractError::{
-    IllegalCoinOwnership, InvalidFinalizationRequest, PoolAlreadyFinalized, UnauthorizedAsSeller,
+    IllegalCoinOwnership, IneligiblePoolAsset, InvalidFinalizationRequest, PoolAlreadyAccepted,
+    PoolAlreadyFinalized, UnauthorizedAsSeller,
 };
-use crate::storage::state_store::{retrieve_seller_state, save_seller_state};
-use crate::util::helpers::{get_balance, is_seller, seller_has_finalized};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
-use provwasm_std::types::provenance::marker::v1::MsgTransferRequest;
+use crate::storage::state_store::{
+    retrieve_contract_config, retrieve_optional_seller_state, retrieve_seller_state,
+    save_seller_state, PoolAsset,
+};
+use crate::util::helpers::{
+    any_buyer_has_accepted, get_balance, get_eligible_pool_scopes, seller_has_finalized,
+    validate_marker_access,
+};
+use cosmwasm_std::{Addr, Deps, DepsMut, Env, MessageInfo, Response};
+use provwasm_std::types::provenance::marker::v1::{Access, MsgTransferRequest};
 
 pub fn execute_finalize_pools(
     deps: DepsMut,
@@ -13,21 +20,17 @@ pub fn execute_finalize_pools(
     info: MessageInfo,
     pool_denoms: &Vec<String>,
 ) -> Result<Response, ContractError> {
-    // Only the seller can finalize the seller pool denom list
-    if !is_seller(&deps, &info)? {
-        return Err(UnauthorizedAsSeller);
-    }
-
-    // In order for the transaction to take place, we need to have at least one pool
-    if pool_denoms.is_empty() {
+    // In order for the transaction to take place, we need to have at least one pool, either a marker
+    // denom, native coins deposited with the message or a pool received from a token contract
+    validate_finalize_pools(deps.as_ref(), &info.sender, !info.funds.is_empty())?;
+    let mut updated_seller = retrieve_seller_state(deps.storage, &info.sender)?;
+    if pool_denoms.is_empty() && info.funds.is_empty() && updated_seller.received_pools.is_empty() {
         return Err(InvalidFinalizationRequest);
     }
 
-    // Return an error if the seller has already finalized
-    if seller_has_finalized(&deps)? {
-        return Err(PoolAlreadyFinalized);
-    }
-
+    let config = retrieve_contract_config(deps.storage)?;
+    let mut pools = vec![];
+    let mut scope_addresses = vec![];
     let mut response = Response::new();
     // Iterate over the list of denoms so that we can transfer the coin to the contract
     for denom in pool_denoms {
@@ -38,6 +41,19 @@ pub fn execute_finalize_pools(
             return Err(IllegalCoinOwnership);
         }
 
+        // The contract can only take the pool into escrow if the marker allows it to broker transfers
+        validate_marker_access(
+            &deps,
+            denom,
+            env.contract.address.as_str(),
+            &[Access::Transfer],
+        )?;
+
+        // The pool must own an eligible set of scopes, which are recorded so the buyers can see the loans
+        if let Some(pool_eligibility) = &config.pool_eligibility {
+            scope_addresses.extend(get_eligible_pool_scopes(&deps, denom, pool_eligibility)?);
+        }
+
         // Transfer the coin to the contract
         response = response.add_message(MsgTransferRequest {
             amount: Some(held_coin.coin),
@@ -45,11 +61,58 @@ pub fn execute_finalize_pools(
             from_address: held_coin.address,
             to_address: env.contract.address.to_string(),
         });
+        pools.push(PoolAsset::Marker {
+            denom: denom.clone(),
+        });
     }
 
+    // Native coins are already in the contract's escrow once they are deposited with the message
+    pools.extend(
+        info.funds
+            .iter()
+            .map(|coin| PoolAsset::Native { coin: coin.clone() }),
+    );
+
+    // The pools received from token contracts are already in the contract's escrow
+    pools.append(&mut updated_seller.received_pools);
+
     // Set the state to show the seller has finalized
-    let mut updated_seller = retrieve_seller_state(deps.storage)?;
-    updated_seller.pool_denoms = pool_denoms.clone();
+    updated_seller.pools = pools;
+    updated_seller.scope_addresses = scope_addresses;
     save_seller_state(deps.storage, &updated_seller)?;
     Ok(response.add_attribute("seller_state", format!("{:?}", updated_seller)))
 }
+
+/// Checks that the seller can finalize a list of pools. The receive hooks share these checks, so a pool
+/// can only be received while the seller is still collecting the pools they will finalize, and pools
+/// can only be finalized again after they are rescinded
+pub fn validate_finalize_pools(
+    deps: Deps,
+    seller_address: &Addr,
+    includes_unmarked_pool: bool,
+) -> Result<(), ContractError> {
+    // Only a seller can finalize a list of pools
+    if retrieve_optional_seller_state(deps.storage, seller_address)?.is_none() {
+        return Err(UnauthorizedAsSeller);
+    }
+
+    // Return an error if the seller has already finalized
+    if seller_has_finalized(deps.storage, seller_address)? {
+        return Err(PoolAlreadyFinalized);
+    }
+
+    // Once the buyer has accepted the combined allocation, no further pools can be added to it
+    if any_buyer_has_accepted(deps.storage)? {
+        return Err(PoolAlreadyAccepted);
+    }
+
+    // Only a marker can own scopes, so any other pool cannot meet the pool eligibility requirements
+    if includes_unmarked_pool
+        && retrieve_contract_config(deps.storage)?
+            .pool_eligibility
+            .is_some()
+    {
+        return Err(IneligiblePoolAsset);
+    }
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{IllegalCollateralForfeit, UnauthorizedCollateralForfeit};
+use crate::storage::state_store::{
+    remove_bid_collateral_state, retrieve_buyer_list_state, retrieve_contract_config,
+    retrieve_optional_acceptance_deadline_state, retrieve_optional_bid_collateral_state,
+    retrieve_seller_list_state,
+};
+use crate::util::helpers::{
+    any_seller_has_finalized, create_escrow_payment_message, is_dealer, is_seller,
+};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn execute_forfeit_bid_collateral(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    validate_forfeit_bid_collateral(deps.as_ref(), &env, &info)?;
+
+    let collateral_config = match retrieve_contract_config(deps.storage)?.bid_collateral {
+        None => return Err(IllegalCollateralForfeit),
+        Some(collateral_config) => collateral_config,
+    };
+    let finalized_sellers: Vec<_> = retrieve_seller_list_state(deps.storage)?
+        .into_iter()
+        .filter(|seller| !seller.pools.is_empty())
+        .collect();
+
+    // Collect the collateral of every buyer that has not accepted the finalized pools
+    let mut forfeited_amount = Uint128::zero();
+    for buyer in retrieve_buyer_list_state(deps.storage)? {
+        if buyer.buyer_has_accepted_pools {
+            continue;
+        }
+        if let Some(bid_collateral) =
+            retrieve_optional_bid_collateral_state(deps.storage, &buyer.buyer_address)?
+        {
+            forfeited_amount += bid_collateral.collateral.amount;
+            remove_bid_collateral_state(deps.storage, &buyer.buyer_address);
+        }
+    }
+
+    // Split the forfeited collateral evenly across the finalized sellers, with any remainder going to
+    // the first seller
+    let mut response = Response::new();
+    if forfeited_amount.is_zero() {
+        return Ok(response.add_attribute("forfeited_collateral", forfeited_amount));
+    }
+    let seller_count = Uint128::from(finalized_sellers.len() as u128);
+    let share = forfeited_amount.multiply_ratio(1u128, seller_count);
+    let remainder = forfeited_amount - share * seller_count;
+    for (index, seller) in finalized_sellers.iter().enumerate() {
+        let amount = if index == 0 { share + remainder } else { share };
+        if amount.is_zero() {
+            continue;
+        }
+        response = response.add_message(create_escrow_payment_message(
+            &collateral_config.denom,
+            collateral_config.is_restricted_marker,
+            env.contract.address.to_string(),
+            seller.seller_address.to_string(),
+            amount,
+        ));
+    }
+
+    Ok(response.add_attribute("forfeited_collateral", forfeited_amount))
+}
+
+/// Checks that the sender can forfeit the collateral of the buyers that let the acceptance deadline
+/// pass without accepting the finalized pools
+pub fn validate_forfeit_bid_collateral(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
+    // Only a seller or a dealer can claim the collateral of a buyer that did not follow through
+    if !is_seller(deps.storage, &info.sender)? && !is_dealer(deps, &info.sender)? {
+        return Err(UnauthorizedCollateralForfeit);
+    }
+
+    if retrieve_contract_config(deps.storage)?
+        .bid_collateral
+        .is_none()
+    {
+        return Err(IllegalCollateralForfeit);
+    }
+
+    // The acceptance deadline is set when bids are accepted and must have passed
+    match retrieve_optional_acceptance_deadline_state(deps.storage)? {
+        Some(acceptance_deadline) if env.block.time >= acceptance_deadline => {}
+        _ => return Err(IllegalCollateralForfeit),
+    }
+
+    // Buyers can only be penalized for not accepting pools that have actually been finalized
+    if !any_seller_has_finalized(deps.storage)? {
+        return Err(IllegalCollateralForfeit);
+    }
+    Ok(())
+}
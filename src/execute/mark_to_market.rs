@@ -0,0 +1,128 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{IllegalMarkToMarket, InvalidMarkPrice};
+use crate::execute::declare_margin_default::validate_declare_margin_default;
+use crate::storage::state_store::{
+    retrieve_buyer_list_state, retrieve_optional_mark_price_state, retrieve_seller_list_state,
+    save_margin_account_state, save_mark_price_state, MarginConfig,
+};
+use crate::util::helpers::{
+    credit_counterparties, is_under_margined, margin_exposure, retrieve_margin_account,
+};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
+use rust_decimal::Decimal;
+
+pub fn execute_mark_to_market(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    price: Decimal,
+) -> Result<Response, ContractError> {
+    let margin_config = validate_mark_to_market(deps.as_ref(), &info)?;
+
+    // A forward market token cannot be worth nothing, so a mark at or below zero is a bad price
+    if price <= Decimal::ZERO {
+        return Err(InvalidMarkPrice);
+    }
+    let buyers = retrieve_buyer_list_state(deps.storage)?;
+
+    // The buyers are long the forward market tokens and the sellers are short, so a price increase
+    // moves margin from the sellers to the buyers and a decrease moves it back
+    let price_change = match retrieve_optional_mark_price_state(deps.storage)? {
+        None => Decimal::ZERO,
+        Some(previous_price) => price - previous_price,
+    };
+    save_mark_price_state(deps.storage, &price)?;
+
+    let mut variation_margins = vec![];
+    for buyer in buyers {
+        let exposure = margin_exposure(deps.storage, &buyer.buyer_address)?;
+        variation_margins.push((buyer.buyer_address, price_change * exposure));
+    }
+    for seller in retrieve_seller_list_state(deps.storage)? {
+        let exposure = margin_exposure(deps.storage, &seller.seller_address)?;
+        variation_margins.push((seller.seller_address, -(price_change * exposure)));
+    }
+
+    // A party can only pay the margin it holds, so any variation margin it cannot cover is owed
+    let mut margin_accounts = vec![];
+    let mut total_due = Decimal::ZERO;
+    let mut total_paid = Decimal::ZERO;
+    for (owner, variation_margin) in variation_margins {
+        let mut margin_account = retrieve_margin_account(deps.storage, &owner)?;
+        if variation_margin < Decimal::ZERO {
+            let payment = (-variation_margin).min(margin_account.balance.max(Decimal::ZERO));
+            margin_account.balance -= payment;
+            margin_account.owed += -variation_margin - payment;
+            total_paid += payment;
+        } else {
+            total_due += variation_margin;
+        }
+        margin_accounts.push((margin_account, variation_margin));
+    }
+
+    // The parties gaining margin share what was actually paid in proportion to what they are due, and
+    // anything a party receives first settles the margin it owes
+    let mut settlements = vec![];
+    for (margin_account, variation_margin) in &mut margin_accounts {
+        if *variation_margin <= Decimal::ZERO || total_due.is_zero() {
+            continue;
+        }
+        let credit = *variation_margin * total_paid.min(total_due) / total_due;
+        let settlement = credit.min(margin_account.owed);
+        margin_account.owed -= settlement;
+        margin_account.balance += credit - settlement;
+        settlements.push((margin_account.owner.clone(), settlement));
+    }
+    for (margin_account, _) in &margin_accounts {
+        save_margin_account_state(deps.storage, margin_account)?;
+    }
+    for (owner, settlement) in settlements {
+        let credited = credit_counterparties(deps.storage, &owner, settlement)?;
+        // Margin that cannot be passed on stays with the party rather than leaving the ledger
+        if credited < settlement {
+            let mut margin_account = retrieve_margin_account(deps.storage, &owner)?;
+            margin_account.owed += settlement - credited;
+            margin_account.balance += settlement - credited;
+            save_margin_account_state(deps.storage, &margin_account)?;
+        }
+    }
+
+    let mut response = Response::new().add_attribute("mark_price", price.to_string());
+    for (margin_account, _) in margin_accounts {
+        let mut margin_account = retrieve_margin_account(deps.storage, &margin_account.owner)?;
+
+        // Issue a margin call to any party left below the maintenance margin, keeping the deadline
+        // of a call that is already open
+        if is_under_margined(deps.storage, &margin_config, &margin_account)? {
+            if margin_account.margin_call_deadline.is_none() {
+                margin_account.margin_call_deadline = Some(
+                    env.block
+                        .time
+                        .plus_seconds(margin_config.margin_call_window),
+                );
+            }
+        } else {
+            margin_account.margin_call_deadline = None;
+        }
+        save_margin_account_state(deps.storage, &margin_account)?;
+        response = response.add_attribute("margin_account", format!("{:?}", margin_account));
+    }
+
+    Ok(response)
+}
+
+/// Checks that the sender is a dealer of a trade that uses margin and has buyers to mark, returning
+/// the margin configuration
+pub fn validate_mark_to_market(
+    deps: Deps,
+    info: &MessageInfo,
+) -> Result<MarginConfig, ContractError> {
+    // Only a dealer can submit the market price
+    let margin_config = validate_declare_margin_default(deps, info)?;
+
+    // There is no market risk to margin until a bid has been accepted
+    if retrieve_buyer_list_state(deps.storage)?.is_empty() {
+        return Err(IllegalMarkToMarket);
+    }
+    Ok(margin_config)
+}
@@ -4,7 +4,7 @@ use crate::storage::state_store::{
     retrieve_contract_config, retrieve_optional_token_data_state, save_token_data_state, TokenData,
 };
 use crate::util::helpers::{create_mint_tokens_messages, is_contract_admin};
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128};
 
 pub fn execute_mint_tokens(
     deps: DepsMut,
@@ -13,40 +13,45 @@ pub fn execute_mint_tokens(
     token_count: Uint128,
     token_denom: String,
 ) -> Result<Response, ContractError> {
-    if !is_contract_admin(&deps, &info)? {
-        return Err(UnauthorizedToMint);
+    validate_mint_tokens(deps.as_ref(), &info)?;
+
+    // We must mint at least one coin
+    if token_count <= Uint128::new(0) {
+        return Err(InvalidTokenCount);
     }
 
-    match retrieve_optional_token_data_state(deps.storage)? {
-        Some(data) => Err(TokensAlreadyMinted {
-            token_denom: data.token_denom,
-        }),
-        None => {
-            // We must mint at least one coin
-            if token_count <= Uint128::new(0) {
-                return Err(InvalidTokenCount);
-            }
+    // Save the token denom and amount in the state; the coins will not be transferred
+    // until the seller accepts a bid
+    save_token_data_state(
+        deps.storage,
+        &TokenData {
+            token_denom: token_denom.to_string(),
+            token_count,
+        },
+    )?;
+
+    let config = retrieve_contract_config(deps.storage)?;
+    Ok(Response::new().add_messages(
+        // Mint the coins
+        create_mint_tokens_messages(
+            token_denom,
+            token_count,
+            config.dealers,
+            env.contract.address.to_string(),
+        ),
+    ))
+}
 
-            // Save the token denom and amount in the state; the coins will not be transferred
-            // until the seller accepts a bid
-            save_token_data_state(
-                deps.storage,
-                &TokenData {
-                    token_denom: token_denom.to_string(),
-                    token_count: token_count.clone(),
-                },
-            )?;
+/// Checks that the sender is the contract admin and that the tokens have not been minted yet
+pub fn validate_mint_tokens(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    if !is_contract_admin(deps.storage, &info.sender)? {
+        return Err(UnauthorizedToMint);
+    }
 
-            let config = retrieve_contract_config(deps.storage)?;
-            Ok(Response::new().add_messages(
-                // Mint the coins
-                create_mint_tokens_messages(
-                    token_denom,
-                    token_count,
-                    config.dealers,
-                    env.contract.address.to_string(),
-                ),
-            ))
-        }
+    if let Some(data) = retrieve_optional_token_data_state(deps.storage)? {
+        return Err(TokensAlreadyMinted {
+            token_denom: data.token_denom,
+        });
     }
+    Ok(())
 }
@@ -1,12 +1,29 @@
+pub mod accept_best_bid;
 pub mod accept_bid;
 pub mod accept_finalized_pools;
+pub mod add_allowed_buyers;
+pub mod add_allowed_sellers;
 pub mod add_bidder;
 pub mod add_seller;
+pub mod commit_bid;
+pub mod create_trade;
 pub mod dealer_confirm;
+pub mod declare_margin_default;
+pub mod deposit_fees;
+pub mod deposit_margin;
 pub mod disable_contract;
 pub mod finalize_pools;
+pub mod forfeit_bid_collateral;
+pub mod mark_to_market;
 pub mod mint_tokens;
+pub mod receive_pool;
+pub mod remove_allowed_buyers;
+pub mod remove_allowed_sellers;
 pub mod rescind_finalized_pools;
+pub mod reveal_bid;
 pub mod update_allowed_buyers;
 pub mod update_allowed_sellers;
+pub mod update_max_bid_count;
 pub mod update_seller_offer_hash;
+pub mod update_visibility;
+pub mod withdraw_bid;
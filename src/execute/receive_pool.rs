@@ -0,0 +1,49 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{InvalidFinalizationRequest, UnacceptedTokenContract};
+use crate::execute::finalize_pools::validate_finalize_pools;
+use crate::msg::PoolReceiveMsg;
+use crate::storage::state_store::{
+    retrieve_contract_config, retrieve_seller_state, save_seller_state, PoolAsset,
+};
+use cosmwasm_std::{from_json, Binary, DepsMut, MessageInfo, Response};
+
+pub fn execute_receive_pool(
+    deps: DepsMut,
+    info: MessageInfo,
+    sender: String,
+    msg: &Binary,
+    pool: PoolAsset,
+) -> Result<Response, ContractError> {
+    // Anyone can call the hook directly and claim a send that never happened, so only the accepted
+    // token contracts are trusted to report the tokens they moved into the contract's escrow
+    let config = retrieve_contract_config(deps.storage)?;
+    if !config.accepted_token_contracts.contains(&info.sender)
+        || deps.querier.query_wasm_contract_info(&info.sender).is_err()
+    {
+        return Err(UnacceptedTokenContract {
+            contract: info.sender.to_string(),
+        });
+    }
+
+    // The sender must say what the tokens are for, so tokens sent without the payload are refused
+    let PoolReceiveMsg::FinalizePool { .. } = from_json(msg)?;
+
+    // A send of zero tokens does not add a pool
+    if let PoolAsset::Cw20 { amount, .. } = &pool {
+        if amount.is_zero() {
+            return Err(InvalidFinalizationRequest);
+        }
+    }
+
+    // A pool can only be received while the seller could still finalize it. A rejected pool reverts
+    // the send, so the seller keeps it
+    let seller_address = deps.api.addr_validate(&sender)?;
+    validate_finalize_pools(deps.as_ref(), &seller_address, true)?;
+
+    // The token contract has already moved the pool into the contract's escrow, where it is held with
+    // the seller's other received pools until the seller finalizes them with FinalizePools
+    let mut updated_seller = retrieve_seller_state(deps.storage, &seller_address)?;
+    updated_seller.received_pools.push(pool);
+    save_seller_state(deps.storage, &updated_seller)?;
+    Ok(Response::new().add_attribute("seller_state", format!("{:?}", updated_seller)))
+}
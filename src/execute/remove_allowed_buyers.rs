@@ -0,0 +1,53 @@
+use crate::error::ContractError;
+use crate::storage::state_store::{
+    is_allowed_buyer, remove_allowed_buyer_state, remove_bid_commitment_state,
+    retrieve_bid_list_state, save_bid_list_state, BidList,
+};
+use crate::util::helpers::{release_bid_collateral, validate_allowed_buyers_update};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+pub fn execute_remove_allowed_buyers(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    allowed_buyers: Vec<String>,
+    remove_bids: bool,
+) -> Result<Response, ContractError> {
+    validate_allowed_buyers_update(deps.storage, &info.sender)?;
+
+    // Removing an address that is not allowed has no effect, so only the addresses that were
+    // allowed are reported and have their bids dropped below
+    let mut removed_buyers = vec![];
+    for buyer_str in allowed_buyers {
+        let buyer_addr = deps.api.addr_validate(&buyer_str)?;
+        if is_allowed_buyer(deps.storage, &buyer_addr)? {
+            remove_allowed_buyer_state(deps.storage, &buyer_addr)?;
+            removed_buyers.push(buyer_addr);
+        }
+    }
+
+    let mut response = Response::new();
+    // Drop any outstanding bids or sealed bid commitments of the removed buyers and return their
+    // bid collateral
+    if remove_bids {
+        let bid_list = retrieve_bid_list_state(deps.storage)?;
+        save_bid_list_state(
+            deps.storage,
+            &BidList {
+                bids: bid_list
+                    .bids
+                    .into_iter()
+                    .filter(|bid| !removed_buyers.contains(&bid.buyer_address))
+                    .collect(),
+            },
+        )?;
+        for buyer_addr in &removed_buyers {
+            remove_bid_commitment_state(deps.storage, buyer_addr);
+        }
+        response = response.add_messages(release_bid_collateral(deps.storage, &env, |address| {
+            removed_buyers.contains(address)
+        })?);
+    }
+
+    Ok(response.add_attribute("removed_allowed_buyers", format!("{:?}", removed_buyers)))
+}
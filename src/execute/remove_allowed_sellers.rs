@@ -0,0 +1,25 @@
+use crate::error::ContractError;
+use crate::storage::state_store::{is_allowed_seller, remove_allowed_seller_state};
+use crate::util::helpers::validate_allowed_sellers_update;
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+pub fn execute_remove_allowed_sellers(
+    deps: DepsMut,
+    info: MessageInfo,
+    allowed_sellers: Vec<String>,
+) -> Result<Response, ContractError> {
+    validate_allowed_sellers_update(deps.storage, &info.sender)?;
+
+    // Removing an address that is not allowed has no effect, so only the addresses that were
+    // allowed are reported
+    let mut removed_sellers = vec![];
+    for seller_str in allowed_sellers {
+        let seller_addr = deps.api.addr_validate(&seller_str)?;
+        if is_allowed_seller(deps.storage, &seller_addr)? {
+            remove_allowed_seller_state(deps.storage, &seller_addr)?;
+            removed_sellers.push(seller_addr);
+        }
+    }
+
+    Ok(Response::new().add_attribute("removed_allowed_sellers", format!("{:?}", removed_sellers)))
+}
@@ -4,42 +4,54 @@ use crate::error::ContractError::{
 };
 use crate::storage::state_store::{retrieve_seller_state, save_seller_state};
 use crate::util::helpers::{
-    buyer_has_accepted, create_send_coin_back_to_seller_messages, is_seller,
+    any_buyer_has_accepted, create_send_coin_back_to_seller_messages, is_seller,
+    seller_has_finalized,
 };
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
 
 pub fn execute_rescind_finalized_pools(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
 ) -> Result<Response, ContractError> {
-    // Only the seller is allowed to rescind the finalized offer
-    if !is_seller(&deps, &info)? {
-        return Err(UnauthorizedAsSeller);
-    }
+    validate_rescind_finalized_pools(deps.as_ref(), &info)?;
 
-    // Pools cannot be rescinded if the buyer has already accepted
-    if buyer_has_accepted(&deps)? {
-        return Err(IllegalRescindRequest);
-    }
-
-    let mut updated_seller_state = retrieve_seller_state(deps.storage)?;
-    if updated_seller_state.pool_denoms.is_empty() {
-        return Err(InvalidRescindRequest);
-    }
+    let mut updated_seller_state = retrieve_seller_state(deps.storage, &info.sender)?;
 
     let transfer_messages = create_send_coin_back_to_seller_messages(
         &deps,
         env.contract.address.to_string(),
         updated_seller_state.seller_address.to_string(),
-        updated_seller_state.pool_denoms,
+        updated_seller_state.pools,
     )?;
 
     let response = Response::new().add_messages(transfer_messages);
 
-    // The contract no longer owns the denoms, so clear the list
-    updated_seller_state.pool_denoms = vec![];
+    // The contract no longer holds the pools, so clear the list
+    updated_seller_state.pools = vec![];
+    updated_seller_state.scope_addresses = vec![];
     save_seller_state(deps.storage, &updated_seller_state)?;
 
     Ok(response.add_attribute("seller_state", format!("{:?}", updated_seller_state)))
 }
+
+/// Checks that the sender is a seller with finalized pools that can still be rescinded
+pub fn validate_rescind_finalized_pools(
+    deps: Deps,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
+    // Only the seller is allowed to rescind the finalized offer
+    if !is_seller(deps.storage, &info.sender)? {
+        return Err(UnauthorizedAsSeller);
+    }
+
+    // Pools cannot be rescinded if the buyer has already accepted
+    if any_buyer_has_accepted(deps.storage)? {
+        return Err(IllegalRescindRequest);
+    }
+
+    if !seller_has_finalized(deps.storage, &info.sender)? {
+        return Err(InvalidRescindRequest);
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    AuctionNotConfigured, BidCommitmentDoesNotExist, InvalidBidReveal, RevealWindowClosed,
+};
+use crate::storage::state_store::{
+    remove_bid_commitment_state, retrieve_bid_list_state, retrieve_contract_config,
+    retrieve_optional_bid_commitment_state, save_bid_list_state, Bid, BidCommitment,
+};
+use crate::util::helpers::{create_bid_commitment, next_bid_revision};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+
+pub fn execute_reveal_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    agreement_terms_hash: String,
+    price: Option<Uint128>,
+    salt: String,
+) -> Result<Response, ContractError> {
+    let bid_commitment = validate_reveal_bid(deps.as_ref(), &env, &info)?;
+
+    // The revealed terms must hash to the commitment submitted during the commit window
+    if create_bid_commitment(&agreement_terms_hash, price, &salt) != bid_commitment.commitment {
+        return Err(InvalidBidReveal);
+    }
+
+    // The revealed bid is now visible to the sellers and can be accepted once the auction closes
+    let mut bid_list = retrieve_bid_list_state(deps.storage)?;
+    let revision = next_bid_revision(deps.storage, &bid_list, &info.sender)?;
+    bid_list.bids.push(Bid {
+        buyer_address: info.sender.clone(),
+        agreement_terms_hash,
+        price,
+        // Bids are ranked by when they were committed so that revealing early gives no advantage
+        submitted_at: bid_commitment.committed_at,
+        revision,
+    });
+    save_bid_list_state(deps.storage, &bid_list)?;
+    remove_bid_commitment_state(deps.storage, &info.sender);
+
+    Ok(Response::new().add_attribute("bid_list", format!("{:?}", bid_list.bids)))
+}
+
+/// Checks that the reveal window is open and that the sender committed a sealed bid, returning the
+/// commitment the revealed terms must match
+pub fn validate_reveal_bid(
+    deps: Deps,
+    env: &Env,
+    info: &MessageInfo,
+) -> Result<BidCommitment, ContractError> {
+    // Reveals are only accepted when the contract is running a sealed-bid auction
+    let auction = match retrieve_contract_config(deps.storage)?.auction {
+        None => return Err(AuctionNotConfigured),
+        Some(auction) => auction,
+    };
+
+    // Bids can only be revealed after every bidder has had the chance to commit
+    if env.block.time < auction.commit_deadline || env.block.time >= auction.reveal_deadline {
+        return Err(RevealWindowClosed);
+    }
+
+    match retrieve_optional_bid_commitment_state(deps.storage, &info.sender)? {
+        None => Err(BidCommitmentDoesNotExist {
+            address: info.sender.to_string(),
+        }),
+        Some(bid_commitment) => Ok(bid_commitment),
+    }
+}
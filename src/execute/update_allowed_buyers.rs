@@ -1,7 +1,8 @@
 use crate::error::ContractError;
-use crate::error::ContractError::InvalidVisibilityConfig;
-use crate::storage::state_store::retrieve_contract_config;
-use crate::util::helpers::update_config_as_admin;
+use crate::storage::state_store::{
+    clear_allowed_buyer_state, retrieve_allowed_buyer_list_state, save_allowed_buyer_state,
+};
+use crate::util::helpers::validate_allowed_buyers_update;
 use cosmwasm_std::{DepsMut, MessageInfo, Response};
 
 pub fn execute_update_allowed_buyers(
@@ -9,19 +10,22 @@ pub fn execute_update_allowed_buyers(
     info: MessageInfo,
     allowed_buyers: Vec<String>,
 ) -> Result<Response, ContractError> {
-    let config = retrieve_contract_config(deps.storage)?;
+    validate_allowed_buyers_update(deps.storage, &info.sender)?;
 
     let mut updated_buyers = vec![];
-    if config.use_private_buyers {
-        for buyer_str in allowed_buyers {
-            let seller_addr = deps.api.addr_validate(&buyer_str)?;
-            updated_buyers.push(seller_addr)
-        }
-    } else {
-        return Err(InvalidVisibilityConfig);
+    for buyer_str in allowed_buyers {
+        let buyer_addr = deps.api.addr_validate(&buyer_str)?;
+        updated_buyers.push(buyer_addr)
     }
 
-    let mut updated_config = config.clone();
-    updated_config.allowed_buyers = updated_buyers;
-    update_config_as_admin(deps, info, updated_config)
+    // Replace the full list of allowed buyers
+    clear_allowed_buyer_state(deps.storage)?;
+    for buyer_addr in &updated_buyers {
+        save_allowed_buyer_state(deps.storage, buyer_addr)?;
+    }
+
+    Ok(Response::new().add_attribute(
+        "allowed_buyers",
+        format!("{:?}", retrieve_allowed_buyer_list_state(deps.storage)?),
+    ))
 }
@@ -1,7 +1,8 @@
 use crate::error::ContractError;
-use crate::error::ContractError::InvalidVisibilityConfig;
-use crate::storage::state_store::retrieve_contract_config;
-use crate::util::helpers::update_config_as_admin;
+use crate::storage::state_store::{
+    clear_allowed_seller_state, retrieve_allowed_seller_list_state, save_allowed_seller_state,
+};
+use crate::util::helpers::validate_allowed_sellers_update;
 use cosmwasm_std::{DepsMut, MessageInfo, Response};
 
 pub fn execute_update_allowed_sellers(
@@ -9,19 +10,22 @@ pub fn execute_update_allowed_sellers(
     info: MessageInfo,
     allowed_sellers: Vec<String>,
 ) -> Result<Response, ContractError> {
-    let config = retrieve_contract_config(deps.storage)?;
+    validate_allowed_sellers_update(deps.storage, &info.sender)?;
 
     let mut updated_sellers = vec![];
-    if config.use_private_sellers {
-        for seller_str in allowed_sellers {
-            let seller_addr = deps.api.addr_validate(&seller_str)?;
-            updated_sellers.push(seller_addr)
-        }
-    } else {
-        return Err(InvalidVisibilityConfig);
+    for seller_str in allowed_sellers {
+        let seller_addr = deps.api.addr_validate(&seller_str)?;
+        updated_sellers.push(seller_addr)
     }
 
-    let mut updated_config = config.clone();
-    updated_config.allowed_sellers = updated_sellers;
-    update_config_as_admin(deps, info, updated_config)
+    // Replace the full list of allowed sellers
+    clear_allowed_seller_state(deps.storage)?;
+    for seller_addr in &updated_sellers {
+        save_allowed_seller_state(deps.storage, seller_addr)?;
+    }
+
+    Ok(Response::new().add_attribute(
+        "allowed_sellers",
+        format!("{:?}", retrieve_allowed_seller_list_state(deps.storage)?),
+    ))
 }
@@ -0,0 +1,20 @@
+use crate::error::ContractError;
+use crate::error::ContractError::InvalidMaxBidCount;
+use crate::storage::state_store::retrieve_contract_config;
+use crate::util::helpers::update_config_as_admin;
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+
+pub fn execute_update_max_bid_count(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_bid_count: Option<u32>,
+) -> Result<Response, ContractError> {
+    // A max bid count of zero would not allow any bids
+    if max_bid_count == Some(0) {
+        return Err(InvalidMaxBidCount);
+    }
+
+    let mut updated_config = retrieve_contract_config(deps.storage)?;
+    updated_config.max_bid_count = max_bid_count;
+    update_config_as_admin(deps, info, updated_config)
+}
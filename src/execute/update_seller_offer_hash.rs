@@ -1,33 +1,60 @@
 use crate::error::ContractError;
-use crate::error::ContractError::{IllegalOfferHashUpdate, UnauthorizedAsSeller};
+use crate::error::ContractError::{
+    IllegalOfferHashUpdate, PoolAlreadyFinalized, UnauthorizedAsSeller,
+};
 use crate::storage::state_store::{
-    retrieve_optional_buyer_state, retrieve_seller_state, save_seller_state,
+    retrieve_seller_state, save_offer_revision_state, save_seller_state, OfferRevision,
 };
-use crate::util::helpers::is_seller;
-use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use crate::util::helpers::{any_buyer_has_accepted, is_seller, seller_has_finalized};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
 
 pub fn execute_update_seller_offer_hash(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     offer_hash: String,
 ) -> Result<Response, ContractError> {
+    validate_update_seller_offer_hash(deps.as_ref(), &info)?;
+
+    // Keep the replaced offer so the buyers can see how it changed
+    let mut seller_state = retrieve_seller_state(deps.storage, &info.sender)?;
+    save_offer_revision_state(
+        deps.storage,
+        &OfferRevision {
+            seller_address: seller_state.seller_address.clone(),
+            offer_hash: seller_state.offer_hash.clone(),
+            revision: seller_state.offer_revision,
+            updated_at: seller_state.offer_updated_at,
+        },
+    )?;
+
+    seller_state.offer_hash = offer_hash;
+    seller_state.offer_revision += 1;
+    seller_state.offer_updated_at = env.block.time;
+    save_seller_state(deps.storage, &seller_state)?;
+    Ok(Response::new()
+        .add_attribute("offer_revision", seller_state.offer_revision.to_string())
+        .add_attribute("seller_state", format!("{:?}", seller_state)))
+}
+
+/// Checks that the sender is a seller whose offer can still be changed
+pub fn validate_update_seller_offer_hash(
+    deps: Deps,
+    info: &MessageInfo,
+) -> Result<(), ContractError> {
     // Only the seller can update the offer hash
-    if !is_seller(&deps, &info)? {
+    if !is_seller(deps.storage, &info.sender)? {
         return Err(UnauthorizedAsSeller);
     }
 
-    // Return an error if the seller has already accepted a bid
-    match retrieve_optional_buyer_state(deps.storage)? {
-        None => {}
-        Some(buyer) => {
-            if buyer.buyer_has_accepted_pools {
-                return Err(IllegalOfferHashUpdate);
-            }
-        }
+    // Return an error if a buyer has already accepted the offer
+    if any_buyer_has_accepted(deps.storage)? {
+        return Err(IllegalOfferHashUpdate);
     }
 
-    let mut seller_state = retrieve_seller_state(deps.storage)?;
-    seller_state.offer_hash = offer_hash;
-    save_seller_state(deps.storage, &seller_state)?;
-    Ok(Response::new().add_attribute("seller_state", format!("{:?}", seller_state)))
+    // The offer cannot change underneath a finalized list of pools, so the seller must rescind first
+    if seller_has_finalized(deps.storage, &info.sender)? {
+        return Err(PoolAlreadyFinalized);
+    }
+    Ok(())
 }
@@ -0,0 +1,100 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{
+    IllegalVisibilityUpdate, InvalidMaxBidCount, InvalidVisibilityConfig,
+};
+use crate::storage::state_store::{
+    clear_allowed_buyer_state, clear_allowed_seller_state, remove_bid_commitment_state,
+    retrieve_bid_commitment_list_state, retrieve_bid_list_state, retrieve_contract_config,
+    retrieve_seller_list_state, save_allowed_buyer_state, save_allowed_seller_state,
+    save_bid_list_state, save_contract_config, BidList,
+};
+use crate::util::helpers::{release_bid_collateral, validate_config_update};
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response};
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_visibility(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    private_sellers: bool,
+    private_buyers: bool,
+    allowed_sellers: Vec<String>,
+    allowed_buyers: Vec<String>,
+    max_bid_count: Option<u32>,
+) -> Result<Response, ContractError> {
+    validate_config_update(deps.storage, &info.sender)?;
+
+    // The allowed lists follow the same rules as at instantiation and must be empty when public
+    if (!private_sellers && !allowed_sellers.is_empty())
+        || (!private_buyers && !allowed_buyers.is_empty())
+    {
+        return Err(InvalidVisibilityConfig);
+    }
+    if max_bid_count == Some(0) {
+        return Err(InvalidMaxBidCount);
+    }
+    let allowed_sellers = allowed_sellers
+        .iter()
+        .map(|seller_str| deps.api.addr_validate(seller_str))
+        .collect::<Result<Vec<Addr>, _>>()?;
+    let allowed_buyers = allowed_buyers
+        .iter()
+        .map(|buyer_str| deps.api.addr_validate(buyer_str))
+        .collect::<Result<Vec<Addr>, _>>()?;
+
+    // Sellers may already hold finalized pools, so every registered seller must remain allowed
+    if private_sellers {
+        if let Some(seller) = retrieve_seller_list_state(deps.storage)?
+            .into_iter()
+            .find(|seller| !allowed_sellers.contains(&seller.seller_address))
+        {
+            return Err(IllegalVisibilityUpdate {
+                address: seller.seller_address.to_string(),
+            });
+        }
+    }
+
+    let mut config = retrieve_contract_config(deps.storage)?;
+    config.use_private_sellers = private_sellers;
+    config.use_private_buyers = private_buyers;
+    config.max_bid_count = max_bid_count;
+    save_contract_config(deps.storage, &config)?;
+
+    clear_allowed_seller_state(deps.storage)?;
+    for seller_address in &allowed_sellers {
+        save_allowed_seller_state(deps.storage, seller_address)?;
+    }
+    clear_allowed_buyer_state(deps.storage)?;
+    for buyer_address in &allowed_buyers {
+        save_allowed_buyer_state(deps.storage, buyer_address)?;
+    }
+
+    let mut response = Response::new();
+    // Bids and sealed bid commitments from buyers that are no longer allowed are dropped and their
+    // bid collateral is returned. The max bid count only limits bids placed after the update.
+    let mut dropped_bidders: Vec<Addr> = vec![];
+    if private_buyers {
+        let bid_list = retrieve_bid_list_state(deps.storage)?;
+        let (kept_bids, dropped_bids): (Vec<_>, Vec<_>) = bid_list
+            .bids
+            .into_iter()
+            .partition(|bid| allowed_buyers.contains(&bid.buyer_address));
+        dropped_bidders.extend(dropped_bids.into_iter().map(|bid| bid.buyer_address));
+        save_bid_list_state(deps.storage, &BidList { bids: kept_bids })?;
+
+        for bid_commitment in retrieve_bid_commitment_list_state(deps.storage)? {
+            if !allowed_buyers.contains(&bid_commitment.buyer_address) {
+                remove_bid_commitment_state(deps.storage, &bid_commitment.buyer_address);
+                dropped_bidders.push(bid_commitment.buyer_address);
+            }
+        }
+
+        response = response.add_messages(release_bid_collateral(deps.storage, &env, |address| {
+            !allowed_buyers.contains(address)
+        })?);
+    }
+
+    Ok(response
+        .add_attribute("contract_config", format!("{:?}", config))
+        .add_attribute("dropped_bidders", format!("{:?}", dropped_bidders)))
+}
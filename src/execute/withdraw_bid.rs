@@ -0,0 +1,64 @@
+use crate::error::ContractError;
+use crate::error::ContractError::{BidDoesNotExist, IllegalBidWithdrawal};
+use crate::storage::state_store::{
+    remove_bid_commitment_state, retrieve_bid_list_state, retrieve_optional_bid_commitment_state,
+    retrieve_optional_buyer_state, save_bid_list_state, save_bid_revision_state, Bid, BidList,
+};
+use crate::util::helpers::release_bid_collateral;
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response};
+
+pub fn execute_withdraw_bid(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    validate_withdraw_bid(deps.as_ref(), &info)?;
+
+    let bid_list = retrieve_bid_list_state(deps.storage)?;
+    let (withdrawn_bids, remaining_bids): (Vec<Bid>, Vec<Bid>) = bid_list
+        .bids
+        .into_iter()
+        .partition(|bid| bid.buyer_address == info.sender);
+
+    // The withdrawn bid is kept as a prior revision so a later bid continues from its revision
+    for withdrawn_bid in &withdrawn_bids {
+        save_bid_revision_state(deps.storage, withdrawn_bid)?;
+    }
+    save_bid_list_state(
+        deps.storage,
+        &BidList {
+            bids: remaining_bids.clone(),
+        },
+    )?;
+    remove_bid_commitment_state(deps.storage, &info.sender);
+
+    // Give the bidder back any collateral they posted with their bid
+    let collateral_messages = release_bid_collateral(deps.storage, &env, |bidder_address| {
+        bidder_address == info.sender
+    })?;
+
+    Ok(Response::new()
+        .add_messages(collateral_messages)
+        .add_attribute("bid_list", format!("{:?}", remaining_bids)))
+}
+
+/// Checks that the sender has a bid or a sealed bid commitment that has not been accepted
+pub fn validate_withdraw_bid(deps: Deps, info: &MessageInfo) -> Result<(), ContractError> {
+    // Once a bid has been accepted the buyer is committed to the transaction
+    if retrieve_optional_buyer_state(deps.storage, &info.sender)?.is_some() {
+        return Err(IllegalBidWithdrawal);
+    }
+
+    let has_bid = retrieve_bid_list_state(deps.storage)?
+        .bids
+        .iter()
+        .any(|bid| bid.buyer_address == info.sender);
+    let has_commitment =
+        retrieve_optional_bid_commitment_state(deps.storage, &info.sender)?.is_some();
+    if !has_bid && !has_commitment {
+        return Err(BidDoesNotExist {
+            address: info.sender.to_string(),
+        });
+    }
+    Ok(())
+}
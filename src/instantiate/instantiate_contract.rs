@@ -1,13 +1,20 @@
 use crate::error::ContractError;
-use crate::error::ContractError::{InvalidEmptyDealerConfig, InvalidVisibilityConfig};
+use crate::error::ContractError::{
+    InvalidAuctionConfig, InvalidBidCollateralConfig, InvalidEmptyDealerConfig, InvalidFeeConfig,
+    InvalidMarginConfig, InvalidMaxBidCount, InvalidPoolEligibilityConfig,
+    InvalidRequiredAttributesConfig, InvalidVisibilityConfig,
+};
 use crate::msg::InstantiateContractMsg;
-use crate::storage::state_store::{save_bid_list_state, save_contract_config, BidList, Config};
+use crate::storage::state_store::{
+    save_allowed_buyer_state, save_allowed_seller_state, save_bid_list_state, save_contract_config,
+    BidList, Config, Fee,
+};
 use crate::version_info::{set_version_info, VersionInfoV1, CRATE_NAME, PACKAGE_VERSION};
 use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response};
 
 pub fn instantiate_contract(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: InstantiateContractMsg,
 ) -> Result<Response, ContractError> {
@@ -27,6 +34,11 @@ pub fn instantiate_contract(
         return Err(InvalidVisibilityConfig);
     }
 
+    // A max bid count of zero would not allow any bids
+    if msg.max_buyer_count == Some(0) {
+        return Err(InvalidMaxBidCount);
+    }
+
     // A dealer is required to initiate the transfer so make sure at least one is defined
     if msg.dealers.is_empty() {
         return Err(InvalidEmptyDealerConfig);
@@ -34,20 +46,33 @@ pub fn instantiate_contract(
 
     // Convert the list of dealers to addresses
     let dealer_addresses = validate_and_map_address(msg.dealers, &deps)?;
+    let accepted_token_contracts = validate_and_map_address(msg.accepted_token_contracts, &deps)?;
 
     // Store the initial configuration
     let config = Config {
         use_private_sellers: msg.use_private_sellers,
         use_private_buyers: msg.use_private_buyers,
-        allowed_sellers,
-        allowed_buyers,
         max_bid_count: msg.max_buyer_count,
         dealers: dealer_addresses,
         is_disabled: false,
         contract_admin: info.sender,
+        auction: msg.auction,
+        bid_collateral: msg.bid_collateral,
+        margin: msg.margin,
+        fees: msg.fees,
+        pool_eligibility: msg.pool_eligibility,
+        required_attributes: msg.required_attributes,
+        accepted_token_contracts,
     };
+    validate_trade_config(&deps, &env, &config)?;
     save_contract_config(deps.storage, &config)?;
     save_bid_list_state(deps.storage, &BidList { bids: vec![] })?;
+    for seller_address in &allowed_sellers {
+        save_allowed_seller_state(deps.storage, seller_address)?;
+    }
+    for buyer_address in &allowed_buyers {
+        save_allowed_buyer_state(deps.storage, buyer_address)?;
+    }
 
     set_version_info(
         deps.storage,
@@ -60,6 +85,77 @@ pub fn instantiate_contract(
     Ok(Response::new().add_attribute("contract_config", format!("{:?}", config)))
 }
 
+/// Checks the optional sections of the configuration of a trade, which are validated the same way
+/// whether they are set at instantiation or read by a trade created later
+pub fn validate_trade_config(
+    deps: &DepsMut,
+    env: &Env,
+    config: &Config,
+) -> Result<(), ContractError> {
+    // The commit window of a sealed-bid auction must still be open and close before the reveal window
+    if let Some(auction) = &config.auction {
+        if auction.commit_deadline <= env.block.time
+            || auction.reveal_deadline <= auction.commit_deadline
+        {
+            return Err(InvalidAuctionConfig);
+        }
+    }
+
+    // Bid collateral must be a non-zero amount of a denom
+    if let Some(bid_collateral) = &config.bid_collateral {
+        if bid_collateral.denom.is_empty() || bid_collateral.amount.is_zero() {
+            return Err(InvalidBidCollateralConfig);
+        }
+    }
+
+    // Margin must be held in a denom and the maintenance margin cannot be negative
+    if let Some(margin) = &config.margin {
+        if margin.denom.is_empty() || margin.maintenance_margin.is_sign_negative() {
+            return Err(InvalidMarginConfig);
+        }
+    }
+
+    // Fees must be paid in a denom, cannot exceed the notional, and a platform share needs a platform
+    if let Some(fees) = &config.fees {
+        let is_invalid_fee =
+            |fee: &Option<Fee>| matches!(fee, Some(Fee::BasisPoints { bps }) if *bps > 10_000);
+        if fees.denom.is_empty()
+            || is_invalid_fee(&fees.buyer_fee)
+            || is_invalid_fee(&fees.seller_fee)
+            || fees.platform_share_bps > 10_000
+            || (fees.platform_share_bps > 0 && fees.platform_address.is_none())
+        {
+            return Err(InvalidFeeConfig);
+        }
+        if let Some(platform_address) = &fees.platform_address {
+            deps.api.addr_validate(platform_address.as_str())?;
+        }
+    }
+
+    // Every pool must own at least one scope and the scope count range cannot be empty
+    if let Some(pool_eligibility) = &config.pool_eligibility {
+        if pool_eligibility.min_scope_count == 0
+            || pool_eligibility.max_scope_count < pool_eligibility.min_scope_count
+        {
+            return Err(InvalidPoolEligibilityConfig);
+        }
+    }
+
+    // Required attributes must be named
+    if let Some(required_attributes) = &config.required_attributes {
+        if required_attributes
+            .sellers
+            .iter()
+            .chain(&required_attributes.buyers)
+            .chain(&required_attributes.dealers)
+            .any(|attribute| attribute.trim().is_empty())
+        {
+            return Err(InvalidRequiredAttributesConfig);
+        }
+    }
+    Ok(())
+}
+
 fn validate_and_map_address(
     address_strings: Vec<String>,
     deps: &DepsMut,
@@ -69,5 +165,5 @@ fn validate_and_map_address(
         let seller_addr = deps.api.addr_validate(&seller_str)?;
         addresses.push(seller_addr)
     }
-    return Ok(addresses);
+    Ok(addresses)
 }
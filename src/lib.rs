@@ -24,7 +24,7 @@ pub mod msg;
 // Defines the contract query process
 mod query;
 // Defines the state storage for the contract
-mod storage;
+pub mod storage;
 // Defines the tests for the contract
 pub mod tests;
 // Utility methods that give access to shared logic
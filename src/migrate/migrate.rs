@@ -1,11 +1,61 @@
 use crate::error::ContractError;
-use crate::error::ContractError::IllegalMigrationVersion;
+use crate::error::ContractError::{IllegalMigrationVersion, StorageError};
+use crate::storage::state_store::{
+    retrieve_optional_token_data_state, save_allowed_buyer_state, save_allowed_seller_state,
+    save_buyer_state, save_contract_config, save_seller_state, Buyer, Config, PoolAsset, Seller,
+};
 use crate::version_info::{get_version_info, set_version_info, VersionInfoV1, PACKAGE_VERSION};
-use cosmwasm_std::{DepsMut, Response};
+use cosmwasm_std::{Addr, DepsMut, Response, Storage, Timestamp};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+/// The seller state stored before contracts supported multiple sellers
+#[derive(Serialize, Deserialize)]
+struct LegacySeller {
+    seller_address: Addr,
+    pool_denoms: Vec<String>,
+    offer_hash: String,
+}
+
+/// The buyer state stored before contracts supported multiple buyers
+#[derive(Serialize, Deserialize)]
+struct LegacyBuyer {
+    buyer_address: Addr,
+    buyer_has_accepted_pools: bool,
+    agreement_terms_hash: String,
+}
+
+/// The configuration stored before the allow-lists were moved to their own maps and the maximum bid
+/// count became an optional cap
+#[derive(Serialize, Deserialize)]
+struct LegacyConfig {
+    use_private_sellers: bool,
+    use_private_buyers: bool,
+    #[serde(default)]
+    allowed_sellers: Vec<Addr>,
+    #[serde(default)]
+    allowed_buyers: Vec<Addr>,
+    max_bid_count: i32,
+    dealers: Vec<Addr>,
+    is_disabled: bool,
+    contract_admin: Addr,
+}
+
+/// The configuration slot, which is shared with the current configuration
+const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+
+/// The single seller slot used before contracts supported multiple sellers
+const LEGACY_SELLER: Item<LegacySeller> = Item::new("seller");
+
+/// The single buyer slot used before contracts supported multiple buyers
+const LEGACY_BUYER: Item<LegacyBuyer> = Item::new("buyer");
 
 pub fn migrate_contract(deps: DepsMut) -> Result<Response, ContractError> {
     let current_version_info = get_version_info(deps.storage)?;
     validate_migration(current_version_info.version)?;
+    migrate_legacy_seller(deps.storage)?;
+    migrate_legacy_buyer(deps.storage)?;
+    migrate_legacy_config(deps.storage)?;
     set_version_info(
         deps.storage,
         &VersionInfoV1 {
@@ -26,3 +76,95 @@ fn validate_migration(current_version: String) -> Result<(), ContractError> {
     }
     Ok(())
 }
+
+fn migrate_legacy_seller(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let legacy_seller = LEGACY_SELLER.may_load(storage).map_err(|e| StorageError {
+        message: format!("{e:?}"),
+    })?;
+    if let Some(seller) = legacy_seller {
+        save_seller_state(
+            storage,
+            &Seller {
+                seller_address: seller.seller_address,
+                pools: seller
+                    .pool_denoms
+                    .into_iter()
+                    .map(|denom| PoolAsset::Marker { denom })
+                    .collect(),
+                offer_hash: seller.offer_hash,
+                scope_addresses: vec![],
+                // The legacy offer hash is the first revision, as it is for a newly added seller
+                offer_revision: 1,
+                offer_updated_at: Timestamp::default(),
+                received_pools: vec![],
+            },
+        )?;
+        LEGACY_SELLER.remove(storage);
+    }
+    Ok(())
+}
+
+fn migrate_legacy_config(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let legacy_config = LEGACY_CONFIG.may_load(storage).map_err(|e| StorageError {
+        message: format!("{e:?}"),
+    })?;
+    if let Some(legacy_config) = legacy_config {
+        for seller_address in &legacy_config.allowed_sellers {
+            save_allowed_seller_state(storage, seller_address)?;
+        }
+        for buyer_address in &legacy_config.allowed_buyers {
+            save_allowed_buyer_state(storage, buyer_address)?;
+        }
+        // The maximum bid count was only enforced for private buyers, and a count that is not
+        // positive cannot be kept as a cap that must be greater than zero
+        let max_bid_count = if legacy_config.use_private_buyers {
+            u32::try_from(legacy_config.max_bid_count)
+                .ok()
+                .filter(|max_bid_count| *max_bid_count > 0)
+        } else {
+            None
+        };
+        save_contract_config(
+            storage,
+            &Config {
+                use_private_sellers: legacy_config.use_private_sellers,
+                use_private_buyers: legacy_config.use_private_buyers,
+                max_bid_count,
+                dealers: legacy_config.dealers,
+                is_disabled: legacy_config.is_disabled,
+                contract_admin: legacy_config.contract_admin,
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )?;
+    }
+    Ok(())
+}
+
+fn migrate_legacy_buyer(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    let legacy_buyer = LEGACY_BUYER.may_load(storage).map_err(|e| StorageError {
+        message: format!("{e:?}"),
+    })?;
+    if let Some(buyer) = legacy_buyer {
+        // A legacy buyer was given every minted forward market token when their bid was accepted
+        let token_amount = retrieve_optional_token_data_state(storage)?
+            .map(|token_data| token_data.token_count)
+            .unwrap_or_default();
+        save_buyer_state(
+            storage,
+            &Buyer {
+                buyer_address: buyer.buyer_address,
+                buyer_has_accepted_pools: buyer.buyer_has_accepted_pools,
+                agreement_terms_hash: buyer.agreement_terms_hash,
+                token_amount,
+            },
+        )?;
+        LEGACY_BUYER.remove(storage);
+    }
+    Ok(())
+}
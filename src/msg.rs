@@ -1,7 +1,13 @@
-use crate::storage::state_store::{Bid, Buyer, Config, Seller, SettlementData, TokenData};
+use crate::storage::state_store::{
+    AuctionConfig, Bid, BidCollateral, BidCollateralConfig, BidCommitment, Buyer, Config,
+    FeeConfig, HistoryEntry, MarginAccount, MarginConfig, OfferRevision, PoolEligibilityConfig,
+    RequiredAttributesConfig, Seller, SettlementData, TokenData,
+};
 use crate::version_info::VersionInfoV1;
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{from_json, Addr, BankMsg, Binary, Coin, Timestamp, Uint128, WasmMsg};
+use provwasm_std::types::provenance::marker::v1::MsgTransferRequest;
+use rust_decimal::Decimal;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -19,55 +25,496 @@ pub struct InstantiateContractMsg {
     /// A list of addresses allowed to be a buyer in the contract. This is only valid if the use_private_buyers field is set to
     /// true and must be empty when use_private_buyers is false
     pub allowed_buyers: Vec<String>,
-    /// The max number of potential buyers allowed to submit bids to the contract
-    pub max_buyer_count: i32,
+    /// The max number of potential buyers allowed to submit bids to the contract. No limit is applied
+    /// when it is not set
+    pub max_buyer_count: Option<u32>,
     /// The list of addresses allowed to confirm and reset the contract
     pub dealers: Vec<String>,
+    /// When set, bids are submitted through a sealed-bid auction: bidders commit to a hash of their
+    /// bid before the commit deadline and reveal it before the reveal deadline
+    pub auction: Option<AuctionConfig>,
+    /// When set, bidders must escrow this collateral with the contract to place a bid
+    pub bid_collateral: Option<BidCollateralConfig>,
+    /// When set, buyers and sellers must hold margin that is moved between them as the dealers mark
+    /// the contract to market
+    pub margin: Option<MarginConfig>,
+    /// When set, the buyers and sellers pay fees to the settling dealer and the platform at settlement
+    pub fees: Option<FeeConfig>,
+    /// When set, each pool marker must own a number of scopes within the configured range, optionally
+    /// restricted to a list of scope specifications, before the seller can finalize it
+    pub pool_eligibility: Option<PoolEligibilityConfig>,
+    /// When set, sellers, buyers and dealers must hold the listed account attributes to take part in
+    /// the contract
+    pub required_attributes: Option<RequiredAttributesConfig>,
+    /// The CW20 and CW721 contracts whose tokens sellers can send to the contract as pools. Tokens
+    /// sent by any other address are refused
+    #[serde(default)]
+    pub accepted_token_contracts: Vec<String>,
 }
 
-/// All defined payloads to be used when executing routes on this contract instance.
+/// All defined payloads to be used when executing routes on this contract instance. Every route
+/// other than CreateTrade takes an optional trade_id that selects the trade it is executed against.
+/// Without a trade_id the route is executed against the trade created when the contract was
+/// instantiated.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum ExecuteMsg {
-    /// A route that adds the sender as the seller on the contract
-    AddSeller { offer_hash: String },
+    /// A route that can be used by the contract admin to create a new trade. The trade reads the shared
+    /// configuration and allowed lists of the contract, and any of the fields that are set override
+    /// the shared values for the new trade only. The resulting configuration is validated as it is at
+    /// instantiation, so a shared auction whose commit window has closed must be overridden
+    CreateTrade {
+        use_private_sellers: Option<bool>,
+        use_private_buyers: Option<bool>,
+        allowed_sellers: Option<Vec<String>>,
+        allowed_buyers: Option<Vec<String>>,
+        max_bid_count: Option<u32>,
+        dealers: Option<Vec<String>>,
+        accepted_token_contracts: Option<Vec<String>>,
+        auction: Option<AuctionConfig>,
+        bid_collateral: Option<BidCollateralConfig>,
+        margin: Option<MarginConfig>,
+        fees: Option<Box<FeeConfig>>,
+        pool_eligibility: Option<PoolEligibilityConfig>,
+        required_attributes: Option<Box<RequiredAttributesConfig>>,
+    },
+    /// A route that adds the sender as one of the sellers on the contract
+    AddSeller {
+        offer_hash: String,
+        trade_id: Option<u64>,
+    },
     /// A route that allows the seller to update the offer hash prior to accepting a bid
-    UpdateSellerOfferHash { offer_hash: String },
+    UpdateSellerOfferHash {
+        offer_hash: String,
+        trade_id: Option<u64>,
+    },
     /// A route that allows the seller to finalize a list of pools
-    FinalizePools { pool_denoms: Vec<String> },
+    FinalizePools {
+        pool_denoms: Vec<String>,
+        trade_id: Option<u64>,
+    },
     /// A route executed by the dealer that causes the settlement of the transaction
-    DealerConfirm {},
+    DealerConfirm { trade_id: Option<u64> },
     /// A route that can be used by the contract admin to update the allowed seller's list
-    UpdateAllowedSellers { allowed_sellers: Vec<String> },
+    UpdateAllowedSellers {
+        allowed_sellers: Vec<String>,
+        trade_id: Option<u64>,
+    },
     /// A route that can be used by the contract admin to update the allowed buyer's list
-    UpdateAllowedBuyers { allowed_buyers: Vec<String> },
-    /// A route used by the buyer to accept a seller's finalized list of pools
-    AcceptFinalizedPools { offer_hash: String },
+    UpdateAllowedBuyers {
+        allowed_buyers: Vec<String>,
+        trade_id: Option<u64>,
+    },
+    /// A route that can be used by the contract admin to switch the sellers and buyers between private
+    /// and public, replacing the allowed lists and the max bid count. Registered sellers must remain
+    /// allowed, and the bids and sealed bid commitments of buyers that are no longer allowed are
+    /// dropped with their bid collateral returned
+    UpdateVisibility {
+        private_sellers: bool,
+        private_buyers: bool,
+        allowed_sellers: Vec<String>,
+        allowed_buyers: Vec<String>,
+        max_bid_count: Option<u32>,
+        trade_id: Option<u64>,
+    },
+    /// A route that can be used by the contract admin to update the max number of bids, or to remove
+    /// the limit
+    UpdateMaxBidCount {
+        max_bid_count: Option<u32>,
+        trade_id: Option<u64>,
+    },
+    /// A route that can be used by the contract admin to add addresses to the allowed seller's list
+    AddAllowedSellers {
+        allowed_sellers: Vec<String>,
+        trade_id: Option<u64>,
+    },
+    /// A route that can be used by the contract admin to remove addresses from the allowed seller's list
+    RemoveAllowedSellers {
+        allowed_sellers: Vec<String>,
+        trade_id: Option<u64>,
+    },
+    /// A route that can be used by the contract admin to add addresses to the allowed buyer's list
+    AddAllowedBuyers {
+        allowed_buyers: Vec<String>,
+        trade_id: Option<u64>,
+    },
+    /// A route that can be used by the contract admin to remove addresses from the allowed buyer's list.
+    /// When remove_bids is set, any outstanding bid or sealed bid commitment of a removed buyer is
+    /// dropped and their bid collateral is returned
+    RemoveAllowedBuyers {
+        allowed_buyers: Vec<String>,
+        remove_bids: bool,
+        trade_id: Option<u64>,
+    },
+    /// A route used by the buyer to accept the combined allocation of every seller's finalized list of pools
+    AcceptFinalizedPools {
+        offers: Vec<AcceptedOffer>,
+        trade_id: Option<u64>,
+    },
     /// A route used by the seller to rescind a finalized list of pools before the buyer has accepted
-    RescindFinalizedPools {},
+    RescindFinalizedPools { trade_id: Option<u64> },
     /// A route used by either the admin or a dealer to disable the contract. The seller must not have a
     /// finalized list of pools in order for the contract to be disabled (if the seller does have a
     /// finalized list of pools, the seller must rescind the offer before the disable operation will be allowed).
-    ContractDisable {},
-    /// A route used by the seller to accept a bid from a bidder in the list of buyer bids
+    ContractDisable { trade_id: Option<u64> },
+    /// A route used by a seller to accept one or more bids from the list of buyer bids. The forward
+    /// market tokens are split across the accepted bids in proportion to their allocations
     AcceptBid {
-        bidder_address: String,
-        agreement_terms_hash: String,
+        accepted_bids: Vec<AcceptedBid>,
+        trade_id: Option<u64>,
     },
     /// A route used by a potential buyer to add their bid to the list of buyer bids
-    AddBid { agreement_terms_hash: String },
+    AddBid {
+        agreement_terms_hash: String,
+        price: Option<Uint128>,
+        trade_id: Option<u64>,
+    },
+    /// A route used by a bidder to withdraw their bid, or their sealed bid commitment, and reclaim any
+    /// bid collateral they posted
+    WithdrawBid { trade_id: Option<u64> },
+    /// A route used by a seller or a dealer to forfeit the bid collateral of every accepted buyer that
+    /// has not accepted the finalized pools by the acceptance deadline. The collateral is split evenly
+    /// across the sellers that have finalized their pools
+    ForfeitBidCollateral { trade_id: Option<u64> },
+    /// A route used by a buyer or a seller to deposit the fees they will owe at settlement. Fees held
+    /// in a restricted marker are transferred from the sender instead of being attached to the message
+    DepositFees {
+        amount: Uint128,
+        trade_id: Option<u64>,
+    },
+    /// A route used by a buyer or a seller to deposit margin in the margin denom
+    DepositMargin { trade_id: Option<u64> },
+    /// A route used by a dealer to mark the contract to the current price of a forward market token.
+    /// The change in value since the previous mark is moved from the sellers to the buyers, or from the
+    /// buyers to the sellers when the price falls, and any party left below the maintenance margin is
+    /// issued a margin call. The first mark sets the reference price.
+    MarkToMarket {
+        #[schemars(with = "String")]
+        price: Decimal,
+        trade_id: Option<u64>,
+    },
+    /// A route used by a dealer to declare a party in default after they failed to meet a margin call
+    /// by its deadline. The defaulting party's margin is paid to the other side of the trade and the
+    /// contract is disabled
+    DeclareMarginDefault {
+        address: String,
+        trade_id: Option<u64>,
+    },
+    /// A route used by a seller to accept the highest priced bid. Ties are broken in favor of the
+    /// bid that was submitted first and the winning bidder is given every forward market token
+    AcceptBestBid { trade_id: Option<u64> },
+    /// A route used by a potential buyer to commit to a sealed bid during the commit window of an
+    /// auction. The commitment is the hex encoded sha256 hash of
+    /// `{agreement_terms_hash}:{price}:{salt}`, where the price is empty for a bid without a price
+    CommitBid {
+        commitment: String,
+        trade_id: Option<u64>,
+    },
+    /// A route used by a potential buyer to reveal their sealed bid during the reveal window of an
+    /// auction, which adds it to the list of buyer bids
+    RevealBid {
+        agreement_terms_hash: String,
+        price: Option<Uint128>,
+        salt: String,
+        trade_id: Option<u64>,
+    },
     /// A route used the admin of the contract to mint the tokens used in the forward market transaction
     MintTokens {
         token_count: Uint128,
         token_denom: String,
+        trade_id: Option<u64>,
     },
+    /// The hook called by a CW20 contract when a seller sends it tokens. The tokens are added to the
+    /// seller's finalized pools
+    #[serde(rename = "receive")]
+    Receive(Cw20ReceiveMsg),
+    /// The hook called by a CW721 contract when a seller sends it an NFT. The NFT is added to the
+    /// seller's finalized pools
+    #[serde(rename = "receive_nft")]
+    ReceiveNft(Cw721ReceiveMsg),
+}
+
+/// Declares the routes of the contract from a single list, so the name of every route and the table
+/// of all routes cannot drift apart
+macro_rules! actions {
+    ($($action:ident),* $(,)?) => {
+        /// A route of the contract, named after its [ExecuteMsg] variant. The name is recorded in the
+        /// history of the trade and listed by the GetPermissions query.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum Action {
+            $($action),*
+        }
+
+        impl Action {
+            /// Every route, in the order they are listed by the GetPermissions query
+            pub const ALL: &'static [Action] = &[$(Action::$action),*];
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Action::$action => stringify!($action)),*
+                }
+            }
+        }
+    };
+}
+
+actions!(
+    CreateTrade,
+    AddSeller,
+    UpdateSellerOfferHash,
+    FinalizePools,
+    DealerConfirm,
+    UpdateAllowedSellers,
+    UpdateAllowedBuyers,
+    UpdateVisibility,
+    UpdateMaxBidCount,
+    AddAllowedSellers,
+    RemoveAllowedSellers,
+    AddAllowedBuyers,
+    RemoveAllowedBuyers,
+    AcceptFinalizedPools,
+    RescindFinalizedPools,
+    ContractDisable,
+    AcceptBid,
+    AddBid,
+    WithdrawBid,
+    ForfeitBidCollateral,
+    DepositFees,
+    DepositMargin,
+    MarkToMarket,
+    DeclareMarginDefault,
+    AcceptBestBid,
+    CommitBid,
+    RevealBid,
+    MintTokens,
+    Receive,
+    ReceiveNft,
+);
+
+impl ExecuteMsg {
+    /// The route the message is executed by
+    pub fn action(&self) -> Action {
+        match self {
+            ExecuteMsg::CreateTrade { .. } => Action::CreateTrade,
+            ExecuteMsg::AddSeller { .. } => Action::AddSeller,
+            ExecuteMsg::UpdateSellerOfferHash { .. } => Action::UpdateSellerOfferHash,
+            ExecuteMsg::FinalizePools { .. } => Action::FinalizePools,
+            ExecuteMsg::DealerConfirm { .. } => Action::DealerConfirm,
+            ExecuteMsg::UpdateAllowedSellers { .. } => Action::UpdateAllowedSellers,
+            ExecuteMsg::UpdateAllowedBuyers { .. } => Action::UpdateAllowedBuyers,
+            ExecuteMsg::UpdateVisibility { .. } => Action::UpdateVisibility,
+            ExecuteMsg::UpdateMaxBidCount { .. } => Action::UpdateMaxBidCount,
+            ExecuteMsg::AddAllowedSellers { .. } => Action::AddAllowedSellers,
+            ExecuteMsg::RemoveAllowedSellers { .. } => Action::RemoveAllowedSellers,
+            ExecuteMsg::AddAllowedBuyers { .. } => Action::AddAllowedBuyers,
+            ExecuteMsg::RemoveAllowedBuyers { .. } => Action::RemoveAllowedBuyers,
+            ExecuteMsg::AcceptFinalizedPools { .. } => Action::AcceptFinalizedPools,
+            ExecuteMsg::RescindFinalizedPools { .. } => Action::RescindFinalizedPools,
+            ExecuteMsg::ContractDisable { .. } => Action::ContractDisable,
+            ExecuteMsg::AcceptBid { .. } => Action::AcceptBid,
+            ExecuteMsg::AddBid { .. } => Action::AddBid,
+            ExecuteMsg::WithdrawBid { .. } => Action::WithdrawBid,
+            ExecuteMsg::ForfeitBidCollateral { .. } => Action::ForfeitBidCollateral,
+            ExecuteMsg::DepositFees { .. } => Action::DepositFees,
+            ExecuteMsg::DepositMargin { .. } => Action::DepositMargin,
+            ExecuteMsg::MarkToMarket { .. } => Action::MarkToMarket,
+            ExecuteMsg::DeclareMarginDefault { .. } => Action::DeclareMarginDefault,
+            ExecuteMsg::AcceptBestBid { .. } => Action::AcceptBestBid,
+            ExecuteMsg::CommitBid { .. } => Action::CommitBid,
+            ExecuteMsg::RevealBid { .. } => Action::RevealBid,
+            ExecuteMsg::MintTokens { .. } => Action::MintTokens,
+            ExecuteMsg::Receive(..) => Action::Receive,
+            ExecuteMsg::ReceiveNft(..) => Action::ReceiveNft,
+        }
+    }
+
+    /// The trade the route is executed against, if any
+    pub fn trade_id(&self) -> Option<u64> {
+        match self {
+            ExecuteMsg::CreateTrade { .. } => None,
+            ExecuteMsg::AddSeller { trade_id, .. }
+            | ExecuteMsg::UpdateSellerOfferHash { trade_id, .. }
+            | ExecuteMsg::FinalizePools { trade_id, .. }
+            | ExecuteMsg::DealerConfirm { trade_id, .. }
+            | ExecuteMsg::UpdateAllowedSellers { trade_id, .. }
+            | ExecuteMsg::UpdateAllowedBuyers { trade_id, .. }
+            | ExecuteMsg::UpdateVisibility { trade_id, .. }
+            | ExecuteMsg::UpdateMaxBidCount { trade_id, .. }
+            | ExecuteMsg::AddAllowedSellers { trade_id, .. }
+            | ExecuteMsg::RemoveAllowedSellers { trade_id, .. }
+            | ExecuteMsg::AddAllowedBuyers { trade_id, .. }
+            | ExecuteMsg::RemoveAllowedBuyers { trade_id, .. }
+            | ExecuteMsg::AcceptFinalizedPools { trade_id, .. }
+            | ExecuteMsg::RescindFinalizedPools { trade_id, .. }
+            | ExecuteMsg::ContractDisable { trade_id, .. }
+            | ExecuteMsg::AcceptBid { trade_id, .. }
+            | ExecuteMsg::AddBid { trade_id, .. }
+            | ExecuteMsg::WithdrawBid { trade_id, .. }
+            | ExecuteMsg::ForfeitBidCollateral { trade_id, .. }
+            | ExecuteMsg::DepositFees { trade_id, .. }
+            | ExecuteMsg::DepositMargin { trade_id, .. }
+            | ExecuteMsg::MarkToMarket { trade_id, .. }
+            | ExecuteMsg::DeclareMarginDefault { trade_id, .. }
+            | ExecuteMsg::AcceptBestBid { trade_id, .. }
+            | ExecuteMsg::CommitBid { trade_id, .. }
+            | ExecuteMsg::RevealBid { trade_id, .. }
+            | ExecuteMsg::MintTokens { trade_id, .. } => *trade_id,
+            // The trade of a received pool is selected by the payload the seller attached to it
+            ExecuteMsg::Receive(Cw20ReceiveMsg { msg, .. })
+            | ExecuteMsg::ReceiveNft(Cw721ReceiveMsg { msg, .. }) => {
+                match from_json::<PoolReceiveMsg>(msg) {
+                    Ok(PoolReceiveMsg::FinalizePool { trade_id }) => trade_id,
+                    Err(_) => None,
+                }
+            }
+        }
+    }
+}
+
+/// The message a CW20 contract sends to the contract when tokens are sent to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw20ReceiveMsg {
+    /// The address that sent the tokens
+    pub sender: String,
+    pub amount: Uint128,
+    /// The payload attached by the sender, which must be a [PoolReceiveMsg]
+    pub msg: Binary,
+}
+
+/// The message a CW721 contract sends to the contract when an NFT is sent to it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw721ReceiveMsg {
+    /// The address that sent the NFT
+    pub sender: String,
+    pub token_id: String,
+    /// The payload attached by the sender, which must be a [PoolReceiveMsg]
+    pub msg: Binary,
+}
+
+/// The payload a seller attaches when sending CW20 tokens or a CW721 NFT to the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum PoolReceiveMsg {
+    /// Adds the received tokens to the pools of the seller that sent them, which are finalized along
+    /// with any marker or native pools by the seller's next FinalizePools
+    FinalizePool { trade_id: Option<u64> },
+}
+
+/// The CW20 message the contract sends to move tokens out of its escrow
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20ExecuteMsg {
+    Transfer { recipient: String, amount: Uint128 },
+}
+
+/// The CW20 query the contract uses to check the tokens it holds in escrow
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20QueryMsg {
+    Balance { address: String },
 }
 
-/// All defined payloads to be used when querying routes on this contract instance.
+/// The response of a CW20 balance query
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw20BalanceResponse {
+    pub balance: Uint128,
+}
+
+/// The CW721 message the contract sends to move an NFT out of its escrow
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721ExecuteMsg {
+    TransferNft { recipient: String, token_id: String },
+}
+
+/// The CW721 query the contract uses to check that it holds an NFT in escrow
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw721QueryMsg {
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+}
+
+/// The response of a CW721 owner query. The approvals of the NFT are not needed, so they are not read
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Cw721OwnerOfResponse {
+    pub owner: String,
+}
+
+/// The offer of a single seller that the buyer is accepting as part of the combined allocation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AcceptedOffer {
+    /// The address of the seller that finalized the list of pools
+    pub seller_address: String,
+    /// The current offer hash of that seller
+    pub offer_hash: String,
+    /// The revision of the offer hash that the buyer reviewed
+    pub offer_revision: u64,
+}
+
+/// A bid that a seller is accepting, along with its share of the forward market tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AcceptedBid {
+    /// The address of the bidder whose bid is being accepted
+    pub bidder_address: String,
+    /// The agreement terms hash of the bid being accepted
+    pub agreement_terms_hash: String,
+    /// The weight used to split the forward market tokens pro rata. Allocations can be expressed as
+    /// shares (e.g. 25 and 75) or as token amounts that add up to the minted token count
+    pub allocation: Uint128,
+    /// When set, the bid is only accepted if it is still at this revision, so the seller accepts
+    /// exactly the terms they reviewed
+    pub expected_revision: Option<u64>,
+}
+
+/// All defined payloads to be used when querying routes on this contract instance. Without a trade_id
+/// the trade created when the contract was instantiated is read.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, QueryResponses)]
 pub enum QueryMsg {
     /// A route used to ready the internal state of the contract
     #[returns(GetContractStateResponse)]
-    GetContractState {},
+    GetContractState { trade_id: Option<u64> },
+    /// A route used to read the fees each party owes at settlement and what they have deposited
+    #[returns(GetSettlementFeesResponse)]
+    GetSettlementFees { trade_id: Option<u64> },
+    /// A route used to page through the history of every route executed against the trade, oldest
+    /// first. Entries are numbered from one and a page holds 10 entries by default and at most 30
+    #[returns(GetHistoryResponse)]
+    GetHistory {
+        trade_id: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// A route used to read the roles an address holds in the trade and which routes it can currently
+    /// execute with the given native coins attached, along with the reason each blocked route would fail
+    #[returns(GetPermissionsResponse)]
+    GetPermissions {
+        trade_id: Option<u64>,
+        address: String,
+        #[serde(default)]
+        funds: Vec<Coin>,
+    },
+    /// A route used to compare the holder of every pool with the contract, which is expected to hold
+    /// the pools in escrow until settlement
+    #[returns(GetEscrowReconciliationResponse)]
+    GetEscrowReconciliation { trade_id: Option<u64> },
+    /// A route used to dry run FinalizePools as the seller with the given native coins attached,
+    /// returning the transfers it would emit or the error it would fail with
+    #[returns(SimulationResponse)]
+    SimulateFinalizePools {
+        trade_id: Option<u64>,
+        seller: String,
+        pool_denoms: Vec<String>,
+        #[serde(default)]
+        funds: Vec<Coin>,
+    },
+    /// A route used to dry run DealerConfirm as the dealer, returning the transfers it would emit or
+    /// the error it would fail with
+    #[returns(SimulationResponse)]
+    SimulateDealerConfirm {
+        trade_id: Option<u64>,
+        dealer: String,
+    },
 }
 
 /// All defined payloads to be used when migrating to a new instance of this contract.
@@ -82,40 +529,112 @@ pub enum MigrateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetContractStateResponse {
     pub bids: Vec<Bid>,
-    pub seller: Option<Seller>,
+    pub bid_revisions: Vec<Bid>,
+    pub offer_revisions: Vec<OfferRevision>,
+    pub allowed_sellers: Vec<Addr>,
+    pub allowed_buyers: Vec<Addr>,
+    pub bid_commitments: Vec<BidCommitment>,
+    pub bid_collateral: Vec<BidCollateral>,
+    pub acceptance_deadline: Option<Timestamp>,
+    pub margin_accounts: Vec<MarginAccount>,
+    #[schemars(with = "Option<String>")]
+    pub mark_price: Option<Decimal>,
+    pub sellers: Vec<Seller>,
     pub config: Config,
     pub settlement_data: Option<SettlementData>,
     pub version_info: VersionInfoV1,
-    pub buyer: Option<Buyer>,
+    pub buyers: Vec<Buyer>,
     pub token_data: Option<TokenData>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct MetadataAddress {
-    pub bech32: String,
-    pub bytes: Vec<u8>,
-    pub key_type: KeyType,
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GetHistoryResponse {
+    pub entries: Vec<HistoryEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GetPermissionsResponse {
+    pub address: Addr,
+    pub roles: Vec<Role>,
+    pub actions: Vec<ActionPermission>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum KeyType {
-    Scope = 0x00,
-    Session = 0x01,
-    Record = 0x02,
-    ContractSpecification = 0x03,
-    ScopeSpecification = 0x04,
-    RecordSpecification = 0x05,
+/// A role that an address can hold in a trade
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    Admin,
+    Dealer,
+    Seller,
+    Buyer,
+    Bidder,
+    AllowedSeller,
+    AllowedBuyer,
 }
 
-impl KeyType {
-    pub fn to_str(&self) -> &str {
-        match self {
-            KeyType::Scope => "scope",
-            KeyType::Session => "session",
-            KeyType::Record => "record",
-            KeyType::ContractSpecification => "contractspec",
-            KeyType::ScopeSpecification => "scopespec",
-            KeyType::RecordSpecification => "recspec",
-        }
-    }
+/// Whether an address can currently execute a route, and if not, the error the route would return
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ActionPermission {
+    pub action: String,
+    pub allowed: bool,
+    pub reason: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GetEscrowReconciliationResponse {
+    pub pools: Vec<PoolEscrow>,
+}
+
+/// The holding of a finalized pool compared with the escrow of the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PoolEscrow {
+    pub seller_address: Addr,
+    /// The denom of a marker or native pool, or the token contract of a CW20 or CW721 pool
+    pub denom: String,
+    /// The id of the NFT held by a CW721 pool
+    pub token_id: Option<String>,
+    pub expected_holder: Addr,
+    /// The address holding the pool, when it is held by a single address
+    pub holder: Option<String>,
+    /// The amount of the pool's coins or tokens held by the holder. Native and CW20 pools of the same
+    /// denom or token contract share the contract's balance, which must cover all of them
+    pub amount: Option<Uint128>,
+    pub mismatch: Option<EscrowMismatchReason>,
+}
+
+/// Why the holding of a pool does not reconcile with the escrow of the contract
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EscrowMismatchReason {
+    /// No address holds the pool coins
+    NotHeld,
+    /// The pool coins are split across more than one address
+    MultipleHolders,
+    /// The pool coins are held by an address other than the contract
+    WrongHolder,
+    /// The contract holds less of the denom or token than the pools escrowed with it
+    InsufficientBalance,
+}
+
+/// The outcome of a dry run. A route that would succeed lists the marker transfers it would emit,
+/// while a route that would fail has the error it would fail with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SimulationResponse {
+    pub transfers: Vec<MsgTransferRequest>,
+    pub bank_sends: Vec<BankMsg>,
+    pub token_transfers: Vec<WasmMsg>,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct GetSettlementFeesResponse {
+    pub denom: Option<String>,
+    pub fees: Vec<SettlementFee>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct SettlementFee {
+    pub payer: Addr,
+    pub amount: Uint128,
+    pub deposited: Uint128,
 }
@@ -1,28 +1,50 @@
 use crate::error::ContractError;
 use crate::msg::GetContractStateResponse;
 use crate::storage::state_store::{
-    retrieve_bid_list_state, retrieve_contract_config, retrieve_optional_buyer_state,
-    retrieve_optional_seller_state, retrieve_optional_settlement_data_state,
-    retrieve_optional_token_data_state,
+    retrieve_allowed_buyer_list_state, retrieve_allowed_seller_list_state,
+    retrieve_bid_collateral_list_state, retrieve_bid_commitment_list_state,
+    retrieve_bid_list_state, retrieve_bid_revision_list_state, retrieve_buyer_list_state,
+    retrieve_contract_config, retrieve_margin_account_list_state,
+    retrieve_offer_revision_list_state, retrieve_optional_acceptance_deadline_state,
+    retrieve_optional_mark_price_state, retrieve_optional_settlement_data_state,
+    retrieve_optional_token_data_state, retrieve_seller_list_state,
 };
 use crate::version_info::get_version_info;
 use cosmwasm_std::Deps;
 
 pub fn query_contract_state(deps: Deps) -> Result<GetContractStateResponse, ContractError> {
-    let buyers = retrieve_bid_list_state(deps.storage)?;
-    let seller = retrieve_optional_seller_state(deps.storage)?;
+    let bid_list = retrieve_bid_list_state(deps.storage)?;
+    let bid_revisions = retrieve_bid_revision_list_state(deps.storage)?;
+    let offer_revisions = retrieve_offer_revision_list_state(deps.storage)?;
+    let allowed_sellers = retrieve_allowed_seller_list_state(deps.storage)?;
+    let allowed_buyers = retrieve_allowed_buyer_list_state(deps.storage)?;
+    let bid_commitments = retrieve_bid_commitment_list_state(deps.storage)?;
+    let bid_collateral = retrieve_bid_collateral_list_state(deps.storage)?;
+    let acceptance_deadline = retrieve_optional_acceptance_deadline_state(deps.storage)?;
+    let margin_accounts = retrieve_margin_account_list_state(deps.storage)?;
+    let mark_price = retrieve_optional_mark_price_state(deps.storage)?;
+    let sellers = retrieve_seller_list_state(deps.storage)?;
     let config = retrieve_contract_config(deps.storage)?;
     let settlement_data = retrieve_optional_settlement_data_state(deps.storage)?;
     let version_info = get_version_info(deps.storage)?;
-    let buyer = retrieve_optional_buyer_state(deps.storage)?;
+    let buyers = retrieve_buyer_list_state(deps.storage)?;
     let token_data = retrieve_optional_token_data_state(deps.storage)?;
     let response = GetContractStateResponse {
-        bids: buyers.bids,
-        seller,
+        bids: bid_list.bids,
+        bid_revisions,
+        offer_revisions,
+        allowed_sellers,
+        allowed_buyers,
+        bid_commitments,
+        bid_collateral,
+        acceptance_deadline,
+        margin_accounts,
+        mark_price,
+        sellers,
         config,
         settlement_data,
         version_info,
-        buyer,
+        buyers,
         token_data,
     };
     Ok(response)
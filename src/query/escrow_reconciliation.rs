@@ -0,0 +1,12 @@
+use crate::error::ContractError;
+use crate::msg::GetEscrowReconciliationResponse;
+use crate::util::helpers::reconcile_escrow;
+use cosmwasm_std::{Deps, Env};
+
+pub fn query_escrow_reconciliation(
+    deps: Deps,
+    env: Env,
+) -> Result<GetEscrowReconciliationResponse, ContractError> {
+    let pools = reconcile_escrow(deps.storage, &deps.querier, &env.contract.address)?;
+    Ok(GetEscrowReconciliationResponse { pools })
+}
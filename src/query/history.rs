@@ -0,0 +1,13 @@
+use crate::error::ContractError;
+use crate::msg::GetHistoryResponse;
+use crate::storage::state_store::retrieve_history_page;
+use cosmwasm_std::Deps;
+
+pub fn query_history(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<GetHistoryResponse, ContractError> {
+    let entries = retrieve_history_page(deps.storage, start_after, limit)?;
+    Ok(GetHistoryResponse { entries })
+}
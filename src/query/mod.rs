@@ -1 +1,6 @@
 pub mod contract_state;
+pub mod escrow_reconciliation;
+pub mod history;
+pub mod permissions;
+pub mod settlement_fees;
+pub mod simulate;
@@ -0,0 +1,163 @@
+use crate::error::ContractError;
+use crate::execute::accept_best_bid::validate_accept_best_bid;
+use crate::execute::accept_bid::validate_accept_bid;
+use crate::execute::accept_finalized_pools::validate_accept_finalized_pools;
+use crate::execute::add_bidder::validate_add_bid;
+use crate::execute::add_seller::validate_add_seller;
+use crate::execute::commit_bid::validate_commit_bid;
+use crate::execute::create_trade::validate_create_trade;
+use crate::execute::dealer_confirm::validate_dealer_confirm;
+use crate::execute::declare_margin_default::validate_declare_margin_default;
+use crate::execute::deposit_fees::validate_deposit_fees;
+use crate::execute::deposit_margin::validate_deposit_margin;
+use crate::execute::disable_contract::validate_disable_contract;
+use crate::execute::finalize_pools::validate_finalize_pools;
+use crate::execute::forfeit_bid_collateral::validate_forfeit_bid_collateral;
+use crate::execute::mark_to_market::validate_mark_to_market;
+use crate::execute::mint_tokens::validate_mint_tokens;
+use crate::execute::rescind_finalized_pools::validate_rescind_finalized_pools;
+use crate::execute::reveal_bid::validate_reveal_bid;
+use crate::execute::update_seller_offer_hash::validate_update_seller_offer_hash;
+use crate::execute::withdraw_bid::validate_withdraw_bid;
+use crate::msg::{Action, ActionPermission, GetPermissionsResponse, Role};
+use crate::storage::state_store::{
+    is_allowed_buyer, is_allowed_seller, retrieve_bid_list_state, retrieve_contract_config,
+    retrieve_optional_bid_commitment_state,
+};
+use crate::util::helpers::{
+    is_buyer, is_contract_admin, is_dealer, is_seller, validate_allowed_buyers_update,
+    validate_allowed_sellers_update, validate_config_update, validate_trade_is_open,
+};
+use cosmwasm_std::{Coin, Deps, Env, MessageInfo};
+
+/// Returns the error a route would fail with when executed by the sender with the attached funds, or
+/// nothing if it can be executed. Each check runs the same validation as the route itself
+type PermissionCheck = fn(Deps, &Env, &MessageInfo) -> Result<(), ContractError>;
+
+pub fn query_permissions(
+    deps: Deps,
+    env: Env,
+    address: String,
+    funds: Vec<Coin>,
+) -> Result<GetPermissionsResponse, ContractError> {
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&address)?,
+        funds,
+    };
+    let address = &info.sender;
+    let config = retrieve_contract_config(deps.storage)?;
+
+    let mut roles = vec![];
+    if is_contract_admin(deps.storage, address)? {
+        roles.push(Role::Admin);
+    }
+    if is_dealer(deps, address)? {
+        roles.push(Role::Dealer);
+    }
+    if is_seller(deps.storage, address)? {
+        roles.push(Role::Seller);
+    }
+    if is_buyer(deps.storage, address)? {
+        roles.push(Role::Buyer);
+    }
+    let has_bid = retrieve_bid_list_state(deps.storage)?
+        .bids
+        .iter()
+        .any(|bid| bid.buyer_address == *address);
+    if has_bid || retrieve_optional_bid_commitment_state(deps.storage, address)?.is_some() {
+        roles.push(Role::Bidder);
+    }
+    if config.use_private_sellers && is_allowed_seller(deps.storage, address)? {
+        roles.push(Role::AllowedSeller);
+    }
+    if config.use_private_buyers && is_allowed_buyer(deps.storage, address)? {
+        roles.push(Role::AllowedBuyer);
+    }
+
+    let actions = Action::ALL
+        .iter()
+        .map(|action| {
+            let check = permission_check(*action);
+            // New trades can still be created once this trade has been settled or disabled
+            let result = match action {
+                Action::CreateTrade => check(deps, &env, &info),
+                _ => validate_trade_is_open(deps.storage).and_then(|_| check(deps, &env, &info)),
+            };
+            ActionPermission {
+                action: action.name().to_string(),
+                allowed: result.is_ok(),
+                reason: result.err().map(|error| error.to_string()),
+            }
+        })
+        .collect();
+
+    Ok(GetPermissionsResponse {
+        address: info.sender,
+        roles,
+        actions,
+    })
+}
+
+/// The permission check of each route. The match covers every route, so a new route cannot be
+/// added without a check
+fn permission_check(action: Action) -> PermissionCheck {
+    match action {
+        Action::CreateTrade => |deps, _, info| validate_create_trade(deps, info),
+        Action::AddSeller => |deps, _, info| validate_add_seller(deps, info),
+        Action::UpdateSellerOfferHash => {
+            |deps, _, info| validate_update_seller_offer_hash(deps, info)
+        }
+        Action::FinalizePools => {
+            |deps, _, info| validate_finalize_pools(deps, &info.sender, !info.funds.is_empty())
+        }
+        Action::DealerConfirm => validate_dealer_confirm,
+        Action::UpdateAllowedSellers => {
+            |deps, _, info| validate_allowed_sellers_update(deps.storage, &info.sender)
+        }
+        Action::UpdateAllowedBuyers => {
+            |deps, _, info| validate_allowed_buyers_update(deps.storage, &info.sender)
+        }
+        Action::UpdateVisibility => {
+            |deps, _, info| validate_config_update(deps.storage, &info.sender)
+        }
+        Action::UpdateMaxBidCount => {
+            |deps, _, info| validate_config_update(deps.storage, &info.sender)
+        }
+        Action::AddAllowedSellers => {
+            |deps, _, info| validate_allowed_sellers_update(deps.storage, &info.sender)
+        }
+        Action::RemoveAllowedSellers => {
+            |deps, _, info| validate_allowed_sellers_update(deps.storage, &info.sender)
+        }
+        Action::AddAllowedBuyers => {
+            |deps, _, info| validate_allowed_buyers_update(deps.storage, &info.sender)
+        }
+        Action::RemoveAllowedBuyers => {
+            |deps, _, info| validate_allowed_buyers_update(deps.storage, &info.sender)
+        }
+        Action::AcceptFinalizedPools => |deps, _, info| validate_accept_finalized_pools(deps, info),
+        Action::RescindFinalizedPools => {
+            |deps, _, info| validate_rescind_finalized_pools(deps, info)
+        }
+        Action::ContractDisable => |deps, _, info| validate_disable_contract(deps, info),
+        Action::AcceptBid => validate_accept_bid,
+        Action::AddBid => |deps, _, info| validate_add_bid(deps, info),
+        Action::WithdrawBid => |deps, _, info| validate_withdraw_bid(deps, info),
+        Action::ForfeitBidCollateral => validate_forfeit_bid_collateral,
+        Action::DepositFees => |deps, _, info| validate_deposit_fees(deps, info),
+        Action::DepositMargin => |deps, _, info| validate_deposit_margin(deps, info).map(|_| ()),
+        Action::MarkToMarket => |deps, _, info| validate_mark_to_market(deps, info).map(|_| ()),
+        Action::DeclareMarginDefault => {
+            |deps, _, info| validate_declare_margin_default(deps, info).map(|_| ())
+        }
+        Action::AcceptBestBid => {
+            |deps, env, info| validate_accept_best_bid(deps, env, info).map(|_| ())
+        }
+        Action::CommitBid => validate_commit_bid,
+        Action::RevealBid => |deps, env, info| validate_reveal_bid(deps, env, info).map(|_| ()),
+        Action::MintTokens => |deps, _, info| validate_mint_tokens(deps, info),
+        // The hooks are called by the token contract on behalf of the seller, so the seller is checked
+        Action::Receive => |deps, _, info| validate_finalize_pools(deps, &info.sender, true),
+        Action::ReceiveNft => |deps, _, info| validate_finalize_pools(deps, &info.sender, true),
+    }
+}
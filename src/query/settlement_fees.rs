@@ -0,0 +1,13 @@
+use crate::error::ContractError;
+use crate::msg::GetSettlementFeesResponse;
+use crate::storage::state_store::retrieve_contract_config;
+use crate::util::helpers::calculate_settlement_fees;
+use cosmwasm_std::Deps;
+
+pub fn query_settlement_fees(deps: Deps) -> Result<GetSettlementFeesResponse, ContractError> {
+    let denom = retrieve_contract_config(deps.storage)?
+        .fees
+        .map(|fee_config| fee_config.denom);
+    let fees = calculate_settlement_fees(deps.storage)?;
+    Ok(GetSettlementFeesResponse { denom, fees })
+}
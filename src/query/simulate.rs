@@ -0,0 +1,105 @@
+use crate::error::ContractError;
+use crate::execute::dealer_confirm::execute_dealer_confirm;
+use crate::execute::finalize_pools::execute_finalize_pools;
+use crate::msg::SimulationResponse;
+use crate::storage::simulation_storage::SimulationStorage;
+use crate::util::helpers::validate_trade_is_open;
+use cosmwasm_std::{
+    AnyMsg, BankMsg, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, WasmMsg,
+};
+use provwasm_std::types::provenance::marker::v1::MsgTransferRequest;
+
+pub fn query_simulate_finalize_pools(
+    deps: Deps,
+    env: Env,
+    seller: String,
+    pool_denoms: Vec<String>,
+    funds: Vec<Coin>,
+) -> Result<SimulationResponse, ContractError> {
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&seller)?,
+        funds,
+    };
+    simulate(deps, |deps| {
+        execute_finalize_pools(deps, env, info, &pool_denoms)
+    })
+}
+
+pub fn query_simulate_dealer_confirm(
+    deps: Deps,
+    env: Env,
+    dealer: String,
+) -> Result<SimulationResponse, ContractError> {
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&dealer)?,
+        funds: vec![],
+    };
+    simulate(deps, |deps| execute_dealer_confirm(deps, env, info))
+}
+
+/// Runs the route against a copy of the trade's storage that is thrown away afterwards, so the
+/// route performs exactly the same checks and queries as it would when executed
+fn simulate<F>(deps: Deps, route: F) -> Result<SimulationResponse, ContractError>
+where
+    F: FnOnce(DepsMut) -> Result<Response, ContractError>,
+{
+    let mut simulation_storage = SimulationStorage::new(deps.storage);
+    let simulation_deps = DepsMut {
+        storage: &mut simulation_storage,
+        api: deps.api,
+        querier: deps.querier,
+    };
+    let result =
+        validate_trade_is_open(simulation_deps.storage).and_then(|_| route(simulation_deps));
+    match result {
+        Ok(response) => Ok(SimulationResponse {
+            transfers: planned_transfers(&response)?,
+            bank_sends: planned_bank_sends(&response),
+            token_transfers: planned_token_transfers(&response),
+            error: None,
+        }),
+        Err(error) => Ok(SimulationResponse {
+            transfers: vec![],
+            bank_sends: vec![],
+            token_transfers: vec![],
+            error: Some(error.to_string()),
+        }),
+    }
+}
+
+/// The marker transfers among the messages of the response
+fn planned_transfers(response: &Response) -> Result<Vec<MsgTransferRequest>, ContractError> {
+    let mut transfers = vec![];
+    for sub_message in &response.messages {
+        if let CosmosMsg::Any(AnyMsg { type_url, value }) = &sub_message.msg {
+            if type_url == MsgTransferRequest::TYPE_URL {
+                transfers.push(MsgTransferRequest::try_from(value.clone())?);
+            }
+        }
+    }
+    Ok(transfers)
+}
+
+/// The native coin payouts among the messages of the response
+fn planned_bank_sends(response: &Response) -> Vec<BankMsg> {
+    response
+        .messages
+        .iter()
+        .filter_map(|sub_message| match &sub_message.msg {
+            CosmosMsg::Bank(bank_msg @ BankMsg::Send { .. }) => Some(bank_msg.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The calls into token contracts among the messages of the response, which move received pools
+fn planned_token_transfers(response: &Response) -> Vec<WasmMsg> {
+    response
+        .messages
+        .iter()
+        .filter_map(|sub_message| match &sub_message.msg {
+            CosmosMsg::Wasm(wasm_msg @ WasmMsg::Execute { .. }) => Some(wasm_msg.clone()),
+            _ => None,
+        })
+        .collect()
+}
@@ -0,0 +1,71 @@
+use cosmwasm_std::{Order, Record, Storage};
+use std::collections::BTreeMap;
+
+/// Storage used while a route is executed so its history can be recorded. Reads and writes go
+/// straight through to the storage of the trade, while the value each written key held before the
+/// route is kept so only the entries the route changed are recorded.
+pub struct HistoryStorage<'a> {
+    storage: &'a mut dyn Storage,
+    original_values: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+/// A key written by a route, with the value it held before and after the route. A missing value
+/// means the key was not set.
+pub struct StorageWrite {
+    pub key: Vec<u8>,
+    pub before: Option<Vec<u8>>,
+    pub after: Option<Vec<u8>>,
+}
+
+impl<'a> HistoryStorage<'a> {
+    pub fn new(storage: &'a mut dyn Storage) -> Self {
+        HistoryStorage {
+            storage,
+            original_values: BTreeMap::new(),
+        }
+    }
+
+    /// The keys whose value was changed by the writes, in key order
+    pub fn into_writes(self) -> Vec<StorageWrite> {
+        let storage = self.storage;
+        self.original_values
+            .into_iter()
+            .filter_map(|(key, before)| {
+                let after = storage.get(&key);
+                (before != after).then_some(StorageWrite { key, before, after })
+            })
+            .collect()
+    }
+
+    fn keep_original_value(&mut self, key: &[u8]) {
+        if !self.original_values.contains_key(key) {
+            let original_value = self.storage.get(key);
+            self.original_values.insert(key.to_vec(), original_value);
+        }
+    }
+}
+
+impl Storage for HistoryStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.storage.get(key)
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        self.storage.range(start, end, order)
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.keep_original_value(key);
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.keep_original_value(key);
+        self.storage.remove(key)
+    }
+}
@@ -1 +1,4 @@
+pub mod history_storage;
+pub mod simulation_storage;
 pub mod state_store;
+pub mod trade_storage;
@@ -0,0 +1,58 @@
+use cosmwasm_std::{Order, Record, Storage};
+use std::collections::BTreeMap;
+
+/// Storage used to dry run a route from a query. Reads fall through to the storage of the trade,
+/// while writes and removals are kept in memory so the trade is left unchanged.
+pub struct SimulationStorage<'a> {
+    storage: &'a dyn Storage,
+    changes: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
+impl<'a> SimulationStorage<'a> {
+    pub fn new(storage: &'a dyn Storage) -> Self {
+        SimulationStorage {
+            storage,
+            changes: BTreeMap::new(),
+        }
+    }
+}
+
+impl Storage for SimulationStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        match self.changes.get(key) {
+            Some(change) => change.clone(),
+            None => self.storage.get(key),
+        }
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        let mut records: BTreeMap<Vec<u8>, Vec<u8>> =
+            self.storage.range(start, end, Order::Ascending).collect();
+        let in_range = |key: &[u8]| {
+            start.map_or(true, |start| key >= start) && end.map_or(true, |end| key < end)
+        };
+        for (key, change) in self.changes.iter().filter(|(key, _)| in_range(key)) {
+            match change {
+                Some(value) => records.insert(key.clone(), value.clone()),
+                None => records.remove(key),
+            };
+        }
+        match order {
+            Order::Ascending => Box::new(records.into_iter()),
+            Order::Descending => Box::new(records.into_iter().rev()),
+        }
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.changes.insert(key.to_vec(), Some(value.to_vec()));
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.changes.insert(key.to_vec(), None);
+    }
+}
@@ -1,25 +1,74 @@
 use crate::error::ContractError;
 use crate::error::ContractError::StorageError;
-use cosmwasm_std::{Addr, Storage, Uint128};
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Coin, Order, Storage, Timestamp, Uint128};
+use cw_storage_plus::{Bound, Item, Map};
+use rust_decimal::Decimal;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+/// The configuration of the trade, which is the shared configuration of the contract with the
+/// overrides of the trade applied
 pub fn retrieve_contract_config(storage: &dyn Storage) -> Result<Config, ContractError> {
-    CONFIG.load(storage).map_err(|e| StorageError {
+    let mut config = CONFIG.load(storage).map_err(|e| StorageError {
         message: format!("{e:?}"),
-    })
+    })?;
+    if let Some(trade_overrides) = retrieve_optional_trade_overrides(storage)? {
+        for config_override in &trade_overrides.config {
+            config_override.apply(&mut config);
+        }
+    }
+    Ok(config)
 }
 
+/// Saves the configuration of the trade. The trade created at instantiation holds the shared
+/// configuration, while any other trade records the fields that changed as overrides so the rest of
+/// the shared configuration still reaches it.
 pub fn save_contract_config(
     storage: &mut dyn Storage,
     config: &Config,
 ) -> Result<(), ContractError> {
-    CONFIG.save(storage, config).map_err(|e| StorageError {
+    let mut trade_overrides = match retrieve_optional_trade_overrides(storage)? {
+        None => {
+            return CONFIG.save(storage, config).map_err(|e| StorageError {
+                message: format!("{e:?}"),
+            })
+        }
+        Some(trade_overrides) => trade_overrides,
+    };
+    let current_config = retrieve_contract_config(storage)?;
+    for config_override in ConfigOverride::all(config) {
+        let mut updated_config = current_config.clone();
+        config_override.apply(&mut updated_config);
+        if updated_config != current_config {
+            trade_overrides.config.retain(|existing_override| {
+                std::mem::discriminant(existing_override)
+                    != std::mem::discriminant(&config_override)
+            });
+            trade_overrides.config.push(config_override);
+        }
+    }
+    save_trade_overrides(storage, &trade_overrides)
+}
+
+pub fn retrieve_optional_trade_overrides(
+    storage: &dyn Storage,
+) -> Result<Option<TradeOverrides>, ContractError> {
+    TRADE_OVERRIDES.may_load(storage).map_err(|e| StorageError {
         message: format!("{e:?}"),
     })
 }
 
+pub fn save_trade_overrides(
+    storage: &mut dyn Storage,
+    trade_overrides: &TradeOverrides,
+) -> Result<(), ContractError> {
+    TRADE_OVERRIDES
+        .save(storage, trade_overrides)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
 pub fn retrieve_bid_list_state(storage: &dyn Storage) -> Result<BidList, ContractError> {
     BID_LIST.load(storage).map_err(|e| StorageError {
         message: format!("{e:?}"),
@@ -35,24 +84,113 @@ pub fn save_bid_list_state(
     })
 }
 
-pub fn retrieve_optional_seller_state(
+pub fn retrieve_optional_bid_list_state(
     storage: &dyn Storage,
-) -> Result<Option<Seller>, ContractError> {
-    SELLER.may_load(storage).map_err(|e| StorageError {
+) -> Result<Option<BidList>, ContractError> {
+    BID_LIST.may_load(storage).map_err(|e| StorageError {
         message: format!("{e:?}"),
     })
 }
 
-pub fn retrieve_seller_state(storage: &dyn Storage) -> Result<Seller, ContractError> {
-    SELLER.load(storage).map_err(|e| StorageError {
-        message: format!("{e:?}"),
-    })
+pub fn retrieve_bid_revision_list_state(storage: &dyn Storage) -> Result<Vec<Bid>, ContractError> {
+    BID_REVISIONS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, bid)| bid))
+        .collect::<Result<Vec<Bid>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_latest_bid_revision(
+    storage: &dyn Storage,
+    buyer_address: &Addr,
+) -> Result<Option<u64>, ContractError> {
+    BID_REVISIONS
+        .prefix(buyer_address)
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+/// Keeps a bid that has been replaced or withdrawn so the sellers can see how its terms changed
+pub fn save_bid_revision_state(storage: &mut dyn Storage, bid: &Bid) -> Result<(), ContractError> {
+    BID_REVISIONS
+        .save(storage, (&bid.buyer_address, bid.revision), bid)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_offer_revision_list_state(
+    storage: &dyn Storage,
+) -> Result<Vec<OfferRevision>, ContractError> {
+    OFFER_REVISIONS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, offer_revision)| offer_revision))
+        .collect::<Result<Vec<OfferRevision>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+/// Keeps an offer hash that a seller has replaced so the buyers can see how the offer changed
+pub fn save_offer_revision_state(
+    storage: &mut dyn Storage,
+    offer_revision: &OfferRevision,
+) -> Result<(), ContractError> {
+    OFFER_REVISIONS
+        .save(
+            storage,
+            (&offer_revision.seller_address, offer_revision.revision),
+            offer_revision,
+        )
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_optional_seller_state(
+    storage: &dyn Storage,
+    seller_address: &Addr,
+) -> Result<Option<Seller>, ContractError> {
+    SELLERS
+        .may_load(storage, seller_address)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_seller_state(
+    storage: &dyn Storage,
+    seller_address: &Addr,
+) -> Result<Seller, ContractError> {
+    SELLERS
+        .load(storage, seller_address)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_seller_list_state(storage: &dyn Storage) -> Result<Vec<Seller>, ContractError> {
+    SELLERS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, seller)| seller))
+        .collect::<Result<Vec<Seller>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
 }
 
 pub fn save_seller_state(storage: &mut dyn Storage, seller: &Seller) -> Result<(), ContractError> {
-    SELLER.save(storage, seller).map_err(|e| StorageError {
-        message: format!("{e:?}"),
-    })
+    SELLERS
+        .save(storage, &seller.seller_address, seller)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
 }
 
 pub fn retrieve_optional_settlement_data_state(
@@ -76,20 +214,434 @@ pub fn save_settlement_data_state(
 
 pub fn retrieve_optional_buyer_state(
     storage: &dyn Storage,
+    buyer_address: &Addr,
 ) -> Result<Option<Buyer>, ContractError> {
-    BUYER_STATE.may_load(storage).map_err(|e| StorageError {
-        message: format!("{e:?}"),
-    })
+    BUYER_STATE
+        .may_load(storage, buyer_address)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_buyer_list_state(storage: &dyn Storage) -> Result<Vec<Buyer>, ContractError> {
+    BUYER_STATE
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, buyer)| buyer))
+        .collect::<Result<Vec<Buyer>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
 }
 
 pub fn save_buyer_state(storage: &mut dyn Storage, buyer: &Buyer) -> Result<(), ContractError> {
-    BUYER_STATE.save(storage, buyer).map_err(|e| StorageError {
+    BUYER_STATE
+        .save(storage, &buyer.buyer_address, buyer)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn clear_buyer_state(storage: &mut dyn Storage) {
+    BUYER_STATE.clear(storage)
+}
+
+pub fn retrieve_optional_bid_commitment_state(
+    storage: &dyn Storage,
+    buyer_address: &Addr,
+) -> Result<Option<BidCommitment>, ContractError> {
+    BID_COMMITMENTS
+        .may_load(storage, buyer_address)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_bid_commitment_list_state(
+    storage: &dyn Storage,
+) -> Result<Vec<BidCommitment>, ContractError> {
+    BID_COMMITMENTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, commitment)| commitment))
+        .collect::<Result<Vec<BidCommitment>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn save_bid_commitment_state(
+    storage: &mut dyn Storage,
+    bid_commitment: &BidCommitment,
+) -> Result<(), ContractError> {
+    BID_COMMITMENTS
+        .save(storage, &bid_commitment.buyer_address, bid_commitment)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn remove_bid_commitment_state(storage: &mut dyn Storage, buyer_address: &Addr) {
+    BID_COMMITMENTS.remove(storage, buyer_address)
+}
+
+pub fn retrieve_optional_bid_collateral_state(
+    storage: &dyn Storage,
+    bidder_address: &Addr,
+) -> Result<Option<BidCollateral>, ContractError> {
+    BID_COLLATERAL
+        .may_load(storage, bidder_address)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_bid_collateral_list_state(
+    storage: &dyn Storage,
+) -> Result<Vec<BidCollateral>, ContractError> {
+    BID_COLLATERAL
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, collateral)| collateral))
+        .collect::<Result<Vec<BidCollateral>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn save_bid_collateral_state(
+    storage: &mut dyn Storage,
+    bid_collateral: &BidCollateral,
+) -> Result<(), ContractError> {
+    BID_COLLATERAL
+        .save(storage, &bid_collateral.bidder_address, bid_collateral)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn remove_bid_collateral_state(storage: &mut dyn Storage, bidder_address: &Addr) {
+    BID_COLLATERAL.remove(storage, bidder_address)
+}
+
+pub fn retrieve_optional_acceptance_deadline_state(
+    storage: &dyn Storage,
+) -> Result<Option<Timestamp>, ContractError> {
+    ACCEPTANCE_DEADLINE
+        .may_load(storage)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn save_acceptance_deadline_state(
+    storage: &mut dyn Storage,
+    acceptance_deadline: &Timestamp,
+) -> Result<(), ContractError> {
+    ACCEPTANCE_DEADLINE
+        .save(storage, acceptance_deadline)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_optional_margin_account_state(
+    storage: &dyn Storage,
+    owner: &Addr,
+) -> Result<Option<MarginAccount>, ContractError> {
+    MARGIN_ACCOUNTS
+        .may_load(storage, owner)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_margin_account_list_state(
+    storage: &dyn Storage,
+) -> Result<Vec<MarginAccount>, ContractError> {
+    MARGIN_ACCOUNTS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, margin_account)| margin_account))
+        .collect::<Result<Vec<MarginAccount>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn save_margin_account_state(
+    storage: &mut dyn Storage,
+    margin_account: &MarginAccount,
+) -> Result<(), ContractError> {
+    MARGIN_ACCOUNTS
+        .save(storage, &margin_account.owner, margin_account)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn clear_margin_account_state(storage: &mut dyn Storage) {
+    MARGIN_ACCOUNTS.clear(storage)
+}
+
+pub fn retrieve_optional_mark_price_state(
+    storage: &dyn Storage,
+) -> Result<Option<Decimal>, ContractError> {
+    MARK_PRICE.may_load(storage).map_err(|e| StorageError {
         message: format!("{e:?}"),
     })
 }
 
-pub fn clear_buyer_state(storage: &mut dyn Storage) -> () {
-    BUYER_STATE.remove(storage)
+pub fn save_mark_price_state(
+    storage: &mut dyn Storage,
+    mark_price: &Decimal,
+) -> Result<(), ContractError> {
+    MARK_PRICE
+        .save(storage, mark_price)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_optional_fee_deposit_state(
+    storage: &dyn Storage,
+    payer: &Addr,
+) -> Result<Option<FeeDeposit>, ContractError> {
+    FEE_DEPOSITS
+        .may_load(storage, payer)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_fee_deposit_list_state(
+    storage: &dyn Storage,
+) -> Result<Vec<FeeDeposit>, ContractError> {
+    FEE_DEPOSITS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, fee_deposit)| fee_deposit))
+        .collect::<Result<Vec<FeeDeposit>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn save_fee_deposit_state(
+    storage: &mut dyn Storage,
+    fee_deposit: &FeeDeposit,
+) -> Result<(), ContractError> {
+    FEE_DEPOSITS
+        .save(storage, &fee_deposit.payer, fee_deposit)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn clear_fee_deposit_state(storage: &mut dyn Storage) {
+    FEE_DEPOSITS.clear(storage)
+}
+
+/// The allowed seller list the trade reads, which is the shared list of the contract unless the trade
+/// has been given its own
+fn allowed_sellers(storage: &dyn Storage) -> Result<Map<&'static Addr, ()>, ContractError> {
+    Ok(match retrieve_optional_trade_overrides(storage)? {
+        Some(trade_overrides) if trade_overrides.allowed_sellers => TRADE_ALLOWED_SELLERS,
+        _ => ALLOWED_SELLERS,
+    })
+}
+
+/// The allowed seller list the trade writes to. A trade that reads the shared list is given its own
+/// copy of it first, so the shared list is only changed from the trade created at instantiation.
+fn own_allowed_sellers(storage: &mut dyn Storage) -> Result<Map<&'static Addr, ()>, ContractError> {
+    let mut trade_overrides = match retrieve_optional_trade_overrides(storage)? {
+        None => return Ok(ALLOWED_SELLERS),
+        Some(trade_overrides) if trade_overrides.allowed_sellers => {
+            return Ok(TRADE_ALLOWED_SELLERS)
+        }
+        Some(trade_overrides) => trade_overrides,
+    };
+    for seller_address in retrieve_allowed_seller_list_state(storage)? {
+        TRADE_ALLOWED_SELLERS
+            .save(storage, &seller_address, &())
+            .map_err(|e| StorageError {
+                message: format!("{e:?}"),
+            })?;
+    }
+    trade_overrides.allowed_sellers = true;
+    save_trade_overrides(storage, &trade_overrides)?;
+    Ok(TRADE_ALLOWED_SELLERS)
+}
+
+pub fn retrieve_allowed_seller_list_state(
+    storage: &dyn Storage,
+) -> Result<Vec<Addr>, ContractError> {
+    allowed_sellers(storage)?
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<Addr>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn is_allowed_seller(
+    storage: &dyn Storage,
+    seller_address: &Addr,
+) -> Result<bool, ContractError> {
+    Ok(allowed_sellers(storage)?.has(storage, seller_address))
+}
+
+pub fn save_allowed_seller_state(
+    storage: &mut dyn Storage,
+    seller_address: &Addr,
+) -> Result<(), ContractError> {
+    own_allowed_sellers(storage)?
+        .save(storage, seller_address, &())
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn remove_allowed_seller_state(
+    storage: &mut dyn Storage,
+    seller_address: &Addr,
+) -> Result<(), ContractError> {
+    own_allowed_sellers(storage)?.remove(storage, seller_address);
+    Ok(())
+}
+
+pub fn clear_allowed_seller_state(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    own_allowed_sellers(storage)?.clear(storage);
+    Ok(())
+}
+
+/// The allowed buyer list the trade reads, which is the shared list of the contract unless the trade
+/// has been given its own
+fn allowed_buyers(storage: &dyn Storage) -> Result<Map<&'static Addr, ()>, ContractError> {
+    Ok(match retrieve_optional_trade_overrides(storage)? {
+        Some(trade_overrides) if trade_overrides.allowed_buyers => TRADE_ALLOWED_BUYERS,
+        _ => ALLOWED_BUYERS,
+    })
+}
+
+/// The allowed buyer list the trade writes to. A trade that reads the shared list is given its own
+/// copy of it first, so the shared list is only changed from the trade created at instantiation.
+fn own_allowed_buyers(storage: &mut dyn Storage) -> Result<Map<&'static Addr, ()>, ContractError> {
+    let mut trade_overrides = match retrieve_optional_trade_overrides(storage)? {
+        None => return Ok(ALLOWED_BUYERS),
+        Some(trade_overrides) if trade_overrides.allowed_buyers => return Ok(TRADE_ALLOWED_BUYERS),
+        Some(trade_overrides) => trade_overrides,
+    };
+    for buyer_address in retrieve_allowed_buyer_list_state(storage)? {
+        TRADE_ALLOWED_BUYERS
+            .save(storage, &buyer_address, &())
+            .map_err(|e| StorageError {
+                message: format!("{e:?}"),
+            })?;
+    }
+    trade_overrides.allowed_buyers = true;
+    save_trade_overrides(storage, &trade_overrides)?;
+    Ok(TRADE_ALLOWED_BUYERS)
+}
+
+pub fn retrieve_allowed_buyer_list_state(
+    storage: &dyn Storage,
+) -> Result<Vec<Addr>, ContractError> {
+    allowed_buyers(storage)?
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<Result<Vec<Addr>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn is_allowed_buyer(
+    storage: &dyn Storage,
+    buyer_address: &Addr,
+) -> Result<bool, ContractError> {
+    Ok(allowed_buyers(storage)?.has(storage, buyer_address))
+}
+
+pub fn save_allowed_buyer_state(
+    storage: &mut dyn Storage,
+    buyer_address: &Addr,
+) -> Result<(), ContractError> {
+    own_allowed_buyers(storage)?
+        .save(storage, buyer_address, &())
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn remove_allowed_buyer_state(
+    storage: &mut dyn Storage,
+    buyer_address: &Addr,
+) -> Result<(), ContractError> {
+    own_allowed_buyers(storage)?.remove(storage, buyer_address);
+    Ok(())
+}
+
+pub fn clear_allowed_buyer_state(storage: &mut dyn Storage) -> Result<(), ContractError> {
+    own_allowed_buyers(storage)?.clear(storage);
+    Ok(())
+}
+
+pub fn retrieve_trade_count(storage: &dyn Storage) -> Result<u64, ContractError> {
+    TRADE_COUNT
+        .may_load(storage)
+        .map(|trade_count| trade_count.unwrap_or_default())
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn save_trade_count(storage: &mut dyn Storage, trade_count: u64) -> Result<(), ContractError> {
+    TRADE_COUNT
+        .save(storage, &trade_count)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_history_page(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> Result<Vec<HistoryEntry>, ContractError> {
+    let limit = limit
+        .unwrap_or(DEFAULT_HISTORY_PAGE_LIMIT)
+        .min(MAX_HISTORY_PAGE_LIMIT);
+    HISTORY
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .map(|item| item.map(|(_, history_entry)| history_entry))
+        .collect::<Result<Vec<HistoryEntry>, _>>()
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+/// Appends the entry to the history. Entries are numbered from one in the order they were recorded
+pub fn append_history_entry(
+    storage: &mut dyn Storage,
+    history_entry: &HistoryEntry,
+) -> Result<(), ContractError> {
+    HISTORY
+        .save(storage, history_entry.id, history_entry)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
+}
+
+pub fn retrieve_next_history_id(storage: &dyn Storage) -> Result<u64, ContractError> {
+    HISTORY
+        .keys(storage, None, None, Order::Descending)
+        .next()
+        .transpose()
+        .map(|last_id| last_id.unwrap_or_default() + 1)
+        .map_err(|e| StorageError {
+            message: format!("{e:?}"),
+        })
 }
 
 pub fn save_token_data_state(
@@ -121,19 +673,216 @@ pub fn retrieve_token_data_state(storage: &dyn Storage) -> Result<TokenData, Con
 pub struct Config {
     pub use_private_sellers: bool,
     pub use_private_buyers: bool,
-    pub allowed_sellers: Vec<Addr>,
-    pub allowed_buyers: Vec<Addr>,
-    pub max_bid_count: i32,
+    pub max_bid_count: Option<u32>,
     pub dealers: Vec<Addr>,
     pub is_disabled: bool,
     pub contract_admin: Addr,
+    pub auction: Option<AuctionConfig>,
+    pub bid_collateral: Option<BidCollateralConfig>,
+    pub margin: Option<MarginConfig>,
+    pub fees: Option<FeeConfig>,
+    pub pool_eligibility: Option<PoolEligibilityConfig>,
+    pub required_attributes: Option<RequiredAttributesConfig>,
+    /// The CW20 and CW721 contracts whose tokens sellers can send to the contract as pools
+    #[serde(default)]
+    pub accepted_token_contracts: Vec<Addr>,
+}
+
+/// The parts of a trade created with CreateTrade that differ from the shared configuration and
+/// allowed lists of the contract. Everything that is not overridden is read from the trade created at
+/// instantiation, so updates made there reach the trade.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct TradeOverrides {
+    /// The configuration fields the trade sets for itself
+    pub config: Vec<ConfigOverride>,
+    /// Whether the trade keeps its own allowed seller list instead of the shared list
+    pub allowed_sellers: bool,
+    /// Whether the trade keeps its own allowed buyer list instead of the shared list
+    pub allowed_buyers: bool,
+}
+
+/// A configuration field a trade sets for itself. The contract admin is always shared.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigOverride {
+    UsePrivateSellers(bool),
+    UsePrivateBuyers(bool),
+    MaxBidCount(Option<u32>),
+    Dealers(Vec<Addr>),
+    IsDisabled(bool),
+    Auction(Option<AuctionConfig>),
+    BidCollateral(Option<BidCollateralConfig>),
+    Margin(Option<MarginConfig>),
+    Fees(Option<FeeConfig>),
+    PoolEligibility(Option<PoolEligibilityConfig>),
+    RequiredAttributes(Option<RequiredAttributesConfig>),
+    AcceptedTokenContracts(Vec<Addr>),
+}
+
+impl ConfigOverride {
+    /// An override for every field of the configuration that a trade can set for itself
+    pub fn all(config: &Config) -> Vec<ConfigOverride> {
+        vec![
+            ConfigOverride::UsePrivateSellers(config.use_private_sellers),
+            ConfigOverride::UsePrivateBuyers(config.use_private_buyers),
+            ConfigOverride::MaxBidCount(config.max_bid_count),
+            ConfigOverride::Dealers(config.dealers.clone()),
+            ConfigOverride::IsDisabled(config.is_disabled),
+            ConfigOverride::Auction(config.auction.clone()),
+            ConfigOverride::BidCollateral(config.bid_collateral.clone()),
+            ConfigOverride::Margin(config.margin.clone()),
+            ConfigOverride::Fees(config.fees.clone()),
+            ConfigOverride::PoolEligibility(config.pool_eligibility.clone()),
+            ConfigOverride::RequiredAttributes(config.required_attributes.clone()),
+            ConfigOverride::AcceptedTokenContracts(config.accepted_token_contracts.clone()),
+        ]
+    }
+
+    pub fn apply(&self, config: &mut Config) {
+        match self {
+            ConfigOverride::UsePrivateSellers(use_private_sellers) => {
+                config.use_private_sellers = *use_private_sellers
+            }
+            ConfigOverride::UsePrivateBuyers(use_private_buyers) => {
+                config.use_private_buyers = *use_private_buyers
+            }
+            ConfigOverride::MaxBidCount(max_bid_count) => config.max_bid_count = *max_bid_count,
+            ConfigOverride::Dealers(dealers) => config.dealers = dealers.clone(),
+            ConfigOverride::IsDisabled(is_disabled) => config.is_disabled = *is_disabled,
+            ConfigOverride::Auction(auction) => config.auction = auction.clone(),
+            ConfigOverride::BidCollateral(bid_collateral) => {
+                config.bid_collateral = bid_collateral.clone()
+            }
+            ConfigOverride::Margin(margin) => config.margin = margin.clone(),
+            ConfigOverride::Fees(fees) => config.fees = fees.clone(),
+            ConfigOverride::PoolEligibility(pool_eligibility) => {
+                config.pool_eligibility = pool_eligibility.clone()
+            }
+            ConfigOverride::RequiredAttributes(required_attributes) => {
+                config.required_attributes = required_attributes.clone()
+            }
+            ConfigOverride::AcceptedTokenContracts(accepted_token_contracts) => {
+                config.accepted_token_contracts = accepted_token_contracts.clone()
+            }
+        }
+    }
+}
+
+/// The windows of a sealed-bid auction. Bidders commit to a hash of their bid before the commit
+/// deadline and reveal it before the reveal deadline.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct AuctionConfig {
+    pub commit_deadline: Timestamp,
+    pub reveal_deadline: Timestamp,
+}
+
+/// The collateral a bidder must escrow with the contract to place a bid. Collateral held in a
+/// restricted marker is pulled from the bidder with a marker transfer instead of being attached to the
+/// message. An accepted buyer that has not accepted the finalized pools within the acceptance window
+/// (in seconds) of their bid being accepted forfeits their collateral to the sellers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BidCollateralConfig {
+    pub denom: String,
+    pub amount: Uint128,
+    pub is_restricted_marker: bool,
+    pub acceptance_window: u64,
+}
+
+/// The margin both sides of the trade must hold between bid acceptance and settlement. Every party
+/// must keep the maintenance margin per forward market token they are exposed to, and a party that
+/// falls below it has the margin call window (in seconds) to top up before they can be declared in
+/// default.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MarginConfig {
+    pub denom: String,
+    #[schemars(with = "String")]
+    pub maintenance_margin: Decimal,
+    pub margin_call_window: u64,
+}
+
+/// The requirements a pool marker must meet before it can be finalized. Each pool marker must be the
+/// value owner of between the minimum and maximum number of scopes and, when the list of allowed
+/// scope specifications is not empty, every scope must use one of those specifications.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct PoolEligibilityConfig {
+    pub min_scope_count: u32,
+    pub max_scope_count: u32,
+    pub allowed_scope_specs: Vec<String>,
+}
+
+/// The Provenance account attributes each role must hold. An address must hold every attribute listed
+/// for its role, and an empty list places no requirement on that role. These requirements apply on top
+/// of any private seller or buyer lists.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct RequiredAttributesConfig {
+    pub sellers: Vec<String>,
+    pub buyers: Vec<String>,
+    pub dealers: Vec<String>,
+}
+
+/// The fees paid at settlement. Buyers and sellers pay their fee out of the fees they deposited with
+/// the contract. The platform address receives its share of every fee, in basis points, and the
+/// settling dealer receives the rest.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeConfig {
+    pub denom: String,
+    pub is_restricted_marker: bool,
+    pub buyer_fee: Option<Fee>,
+    pub seller_fee: Option<Fee>,
+    pub platform_address: Option<Addr>,
+    pub platform_share_bps: u16,
+}
+
+/// A fee is either a flat amount or basis points of the payer's notional, where the notional is the
+/// number of forward market tokens the payer is settling
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Fee {
+    Flat { amount: Uint128 },
+    BasisPoints { bps: u16 },
+}
+
+/// A pool that a seller has moved into the contract's escrow
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolAsset {
+    /// A Provenance marker denom, moved in and out of escrow with marker transfers
+    Marker { denom: String },
+    /// Bank coins such as IBC vouchers, deposited with the finalization and paid out with bank sends
+    Native { coin: Coin },
+    /// CW20 tokens received through the receive hook of the token contract
+    Cw20 { contract: Addr, amount: Uint128 },
+    /// A CW721 NFT received through the receive hook of the token contract
+    Cw721 { contract: Addr, token_id: String },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct Seller {
     pub seller_address: Addr,
-    pub pool_denoms: Vec<String>,
+    /// The pools the seller has finalized, which are empty until the seller finalizes
+    pub pools: Vec<PoolAsset>,
+    pub offer_hash: String,
+    #[serde(default)]
+    pub scope_addresses: Vec<String>,
+    /// The revision of the seller's offer, which increases every time the offer hash is updated
+    #[serde(default)]
+    pub offer_revision: u64,
+    /// The block time the current offer hash was set
+    #[serde(default)]
+    pub offer_updated_at: Timestamp,
+    /// The pools received from token contracts that are held in escrow until the seller's next
+    /// FinalizePools adds them to the finalized pools
+    #[serde(default)]
+    pub received_pools: Vec<PoolAsset>,
+}
+
+/// An offer hash that a seller has since replaced, kept so the buyers can see how the offer changed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct OfferRevision {
+    pub seller_address: Addr,
     pub offer_hash: String,
+    pub revision: u64,
+    pub updated_at: Timestamp,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -145,6 +894,46 @@ pub struct BidList {
 pub struct Bid {
     pub buyer_address: Addr,
     pub agreement_terms_hash: String,
+    /// The optional price offered by the bidder, used to rank bids when the best bid is accepted
+    #[serde(default)]
+    pub price: Option<Uint128>,
+    /// The block time the bid was submitted, used to break ties between bids with the same price
+    #[serde(default)]
+    pub submitted_at: Timestamp,
+    /// The revision of the bidder's bid, which increases every time the bidder replaces it
+    #[serde(default)]
+    pub revision: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BidCommitment {
+    pub buyer_address: Addr,
+    pub commitment: String,
+    pub committed_at: Timestamp,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct BidCollateral {
+    pub bidder_address: Addr,
+    pub collateral: Coin,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MarginAccount {
+    pub owner: Addr,
+    #[schemars(with = "String")]
+    pub balance: Decimal,
+    pub margin_call_deadline: Option<Timestamp>,
+    /// The variation margin the party had to pay but did not hold
+    #[serde(default)]
+    #[schemars(with = "String")]
+    pub owed: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct FeeDeposit {
+    pub payer: Addr,
+    pub amount: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -164,11 +953,57 @@ pub struct Buyer {
     pub buyer_address: Addr,
     pub buyer_has_accepted_pools: bool,
     pub agreement_terms_hash: String,
+    pub token_amount: Uint128,
+}
+
+/// A successful execution of a route against a trade. The changes hold the JSON encoded value of
+/// every entry of the trade's state that the route changed, before and after it was executed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct HistoryEntry {
+    pub id: u64,
+    pub actor: Addr,
+    pub action: String,
+    pub block_height: u64,
+    pub block_time: Timestamp,
+    pub changes: Vec<HistoryChange>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct HistoryChange {
+    pub field: String,
+    /// The address of the changed entry for the parts of the state kept per address, such as the
+    /// sellers and bids
+    #[serde(default)]
+    pub key: Option<String>,
+    /// The value before the route was executed, which is missing when the entry did not exist
+    pub before: Option<String>,
+    /// The value after the route was executed, which is missing when the entry was removed
+    pub after: Option<String>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
-pub const SELLER: Item<Seller> = Item::new("seller");
+pub const SELLERS: Map<&Addr, Seller> = Map::new("sellers");
 pub const BID_LIST: Item<BidList> = Item::new("buyer_list");
 pub const SETTLEMENT_DATA: Item<SettlementData> = Item::new("settlement_data");
-pub const BUYER_STATE: Item<Buyer> = Item::new("buyer");
+pub const BUYER_STATE: Map<&Addr, Buyer> = Map::new("buyers");
 pub const TOKEN_DATA: Item<TokenData> = Item::new("token_data");
+pub const BID_COMMITMENTS: Map<&Addr, BidCommitment> = Map::new("bid_commitments");
+pub const BID_COLLATERAL: Map<&Addr, BidCollateral> = Map::new("bid_collateral");
+pub const ACCEPTANCE_DEADLINE: Item<Timestamp> = Item::new("acceptance_deadline");
+pub const MARGIN_ACCOUNTS: Map<&Addr, MarginAccount> = Map::new("margin_accounts");
+pub const MARK_PRICE: Item<Decimal> = Item::new("mark_price");
+pub const FEE_DEPOSITS: Map<&Addr, FeeDeposit> = Map::new("fee_deposits");
+pub const ALLOWED_SELLERS: Map<&Addr, ()> = Map::new("allowed_sellers");
+pub const ALLOWED_BUYERS: Map<&Addr, ()> = Map::new("allowed_buyers");
+pub const TRADE_OVERRIDES: Item<TradeOverrides> = Item::new("trade_overrides");
+pub const TRADE_ALLOWED_SELLERS: Map<&Addr, ()> = Map::new("trade_allowed_sellers");
+pub const TRADE_ALLOWED_BUYERS: Map<&Addr, ()> = Map::new("trade_allowed_buyers");
+pub const TRADE_COUNT: Item<u64> = Item::new("trade_count");
+pub const HISTORY: Map<u64, HistoryEntry> = Map::new("history");
+pub const BID_REVISIONS: Map<(&Addr, u64), Bid> = Map::new("bid_revisions");
+pub const OFFER_REVISIONS: Map<(&Addr, u64), OfferRevision> = Map::new("offer_revisions");
+
+/// The number of history entries returned by a page when no limit is given
+pub const DEFAULT_HISTORY_PAGE_LIMIT: u32 = 10;
+/// The largest number of history entries a page can return
+pub const MAX_HISTORY_PAGE_LIMIT: u32 = 30;
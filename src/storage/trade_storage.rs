@@ -0,0 +1,142 @@
+use crate::storage::state_store::{ALLOWED_BUYERS, ALLOWED_SELLERS, CONFIG};
+use crate::version_info::VERSION_INFO_NAMESPACE;
+use cosmwasm_std::storage_keys::{to_length_prefixed, to_length_prefixed_nested};
+use cosmwasm_std::{Order, Record, Storage};
+
+/// Storage for a single trade of the contract. Every key is stored under the namespace of the
+/// trade, so the state store functions can be used for any trade without knowing about trade ids.
+/// Without a trade id the keys are left as they are, which is where the trade created when the
+/// contract was instantiated is stored. The version info describes the stored code rather than a
+/// trade, so it is always kept in the contract's own storage. The configuration and allowed lists of
+/// that first trade are shared with every other trade, so they are also read from the contract's own
+/// storage, and a trade keeps any values it sets for itself under its own keys.
+pub struct TradeStorage<'a> {
+    storage: &'a mut dyn Storage,
+    namespace: Vec<u8>,
+}
+
+/// The read only version of [TradeStorage] used by queries
+pub struct ReadonlyTradeStorage<'a> {
+    storage: &'a dyn Storage,
+    namespace: Vec<u8>,
+}
+
+impl<'a> TradeStorage<'a> {
+    pub fn new(storage: &'a mut dyn Storage, trade_id: Option<u64>) -> Self {
+        TradeStorage {
+            storage,
+            namespace: trade_namespace(trade_id),
+        }
+    }
+}
+
+impl<'a> ReadonlyTradeStorage<'a> {
+    pub fn new(storage: &'a dyn Storage, trade_id: Option<u64>) -> Self {
+        ReadonlyTradeStorage {
+            storage,
+            namespace: trade_namespace(trade_id),
+        }
+    }
+}
+
+impl Storage for TradeStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.storage.get(&namespaced_key(&self.namespace, key))
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        range_namespace(self.storage, &self.namespace, start, end, order)
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.storage
+            .set(&namespaced_key(&self.namespace, key), value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.storage.remove(&namespaced_key(&self.namespace, key))
+    }
+}
+
+impl Storage for ReadonlyTradeStorage<'_> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.storage.get(&namespaced_key(&self.namespace, key))
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        range_namespace(self.storage, &self.namespace, start, end, order)
+    }
+
+    // Queries are only given a shared reference to the storage, so writes can never reach it
+    fn set(&mut self, _key: &[u8], _value: &[u8]) {}
+
+    fn remove(&mut self, _key: &[u8]) {}
+}
+
+fn trade_namespace(trade_id: Option<u64>) -> Vec<u8> {
+    match trade_id {
+        None => vec![],
+        Some(trade_id) => to_length_prefixed_nested(&[b"trade", &trade_id.to_be_bytes()]),
+    }
+}
+
+fn namespaced_key(namespace: &[u8], key: &[u8]) -> Vec<u8> {
+    if is_shared_key(key) {
+        return key.to_vec();
+    }
+    [namespace, key].concat()
+}
+
+/// Whether the key is kept in the contract's own storage for every trade
+fn is_shared_key(key: &[u8]) -> bool {
+    key == VERSION_INFO_NAMESPACE.as_bytes()
+        || key == CONFIG.as_slice()
+        || [ALLOWED_SELLERS, ALLOWED_BUYERS]
+            .iter()
+            .any(|map| key.starts_with(&to_length_prefixed(map.namespace_bytes())))
+}
+
+fn range_namespace<'b>(
+    storage: &'b dyn Storage,
+    namespace: &[u8],
+    start: Option<&[u8]>,
+    end: Option<&[u8]>,
+    order: Order,
+) -> Box<dyn Iterator<Item = Record> + 'b> {
+    if namespace.is_empty() || start.is_some_and(is_shared_key) {
+        return storage.range(start, end, order);
+    }
+    let start = [namespace, start.unwrap_or_default()].concat();
+    let end = match end {
+        Some(end) => [namespace, end].concat(),
+        None => namespace_upper_bound(namespace),
+    };
+    let namespace_length = namespace.len();
+    Box::new(
+        storage
+            .range(Some(&start), Some(&end), order)
+            .map(move |(key, value)| (key[namespace_length..].to_vec(), value)),
+    )
+}
+
+/// The first key after every key in the namespace
+fn namespace_upper_bound(namespace: &[u8]) -> Vec<u8> {
+    let mut upper_bound = namespace.to_vec();
+    while let Some(last_byte) = upper_bound.pop() {
+        if last_byte < u8::MAX {
+            upper_bound.push(last_byte + 1);
+            break;
+        }
+    }
+    upper_bound
+}
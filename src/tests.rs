@@ -1,2 +1,5 @@
 mod execute;
 mod instantiate;
+mod migrate;
+mod query;
+mod util;
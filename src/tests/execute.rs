@@ -1,9 +1,23 @@
+mod execute_accept_best_bid;
 mod execute_accept_bid;
+mod execute_accept_finalized_pools;
+mod execute_add_allowed_sellers;
 mod execute_add_bidder;
 mod execute_add_seller;
+mod execute_commit_bid;
+mod execute_create_trade;
 mod execute_dealer_confirm;
+mod execute_declare_margin_default;
+mod execute_deposit_fees;
 mod execute_disable_contract;
 mod execute_finalize_pools;
+mod execute_forfeit_bid_collateral;
+mod execute_mark_to_market;
 mod execute_mint_tokens;
+mod execute_receive_pool;
+mod execute_remove_allowed_buyers;
+mod execute_reveal_bid;
 mod execute_update_allowed_sellers;
 mod execute_update_seller_offer_hash;
+mod execute_update_visibility;
+mod execute_withdraw_bid;
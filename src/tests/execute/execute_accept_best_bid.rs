@@ -0,0 +1,260 @@
+#[cfg(test)]
+mod execute_accept_best_bid_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::AcceptBestBid;
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_contract_config, save_seller_state, save_token_data_state, Bid,
+        BidList, Buyer, Config, PoolAsset, Seller, TokenData,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{
+        to_json_binary, Binary, ContractResult, MessageInfo, SystemResult, Uint128,
+    };
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+    use provwasm_std::shim::Any;
+    use provwasm_std::types::provenance::marker::v1::{
+        Access, AccessGrant, MarkerAccount, MarkerStatus, MarkerType, QueryMarkerRequest,
+        QueryMarkerResponse,
+    };
+
+    fn register_token_marker(querier: &mut MockProvenanceQuerier, permissions: Vec<i32>) {
+        let cb_marker = Box::new(
+            move |bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+                let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+                let marker = MarkerAccount {
+                    base_account: None,
+                    manager: "".to_string(),
+                    access_control: vec![AccessGrant {
+                        address: mock_env().contract.address.to_string(),
+                        permissions: permissions.clone(),
+                    }],
+                    status: MarkerStatus::Active.into(),
+                    denom: message.id.to_string(),
+                    supply: "10".to_string(),
+                    marker_type: MarkerType::Coin.into(),
+                    supply_fixed: true,
+                    allow_governance_control: true,
+                    allow_forced_transfer: false,
+                    required_attributes: vec![],
+                };
+                let response = QueryMarkerResponse {
+                    marker: Some(Any {
+                        type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                        value: marker.to_proto_bytes(),
+                    }),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+            },
+        );
+        querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb_marker);
+    }
+
+    #[test]
+    fn accept_highest_priced_bid() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("seller_address");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(4),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address,
+                pools: vec![PoolAsset::Marker {
+                    denom: "mock.denom".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        // The two highest bids share a price, so the one submitted first should win even though
+        // it appears later in the bid list
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: vec![
+                    Bid {
+                        buyer_address: deps.api.addr_make("buyer-address-0"),
+                        agreement_terms_hash: "mock-hash-buyers-0".to_string(),
+                        price: Some(Uint128::new(90)),
+                        submitted_at: env.block.time.minus_seconds(30),
+                        revision: 0,
+                    },
+                    Bid {
+                        buyer_address: deps.api.addr_make("buyer-address-1"),
+                        agreement_terms_hash: "mock-hash-buyers-1".to_string(),
+                        price: Some(Uint128::new(100)),
+                        submitted_at: env.block.time.minus_seconds(10),
+                        revision: 0,
+                    },
+                    Bid {
+                        buyer_address: deps.api.addr_make("buyer-address-2"),
+                        agreement_terms_hash: "mock-hash-buyers-2".to_string(),
+                        price: None,
+                        submitted_at: env.block.time.minus_seconds(40),
+                        revision: 0,
+                    },
+                    Bid {
+                        buyer_address: deps.api.addr_make("buyer-address-3"),
+                        agreement_terms_hash: "mock-hash-buyers-3".to_string(),
+                        price: Some(Uint128::new(100)),
+                        submitted_at: env.block.time.minus_seconds(20),
+                        revision: 0,
+                    },
+                ],
+            },
+        )
+        .unwrap();
+
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        register_token_marker(&mut deps.querier, vec![Access::Withdraw as i32]);
+        match execute(deps.as_mut(), env, info, AcceptBestBid { trade_id: None }) {
+            Ok(_) => {
+                assert_eq!(
+                    query_contract_state(deps.as_ref()).unwrap().buyers,
+                    vec![Buyer {
+                        buyer_address: deps.api.addr_make("buyer-address-3"),
+                        buyer_has_accepted_pools: false,
+                        agreement_terms_hash: "mock-hash-buyers-3".to_string(),
+                        token_amount: Uint128::new(10),
+                    }]
+                );
+            }
+            Err(error) => {
+                panic!("failed to accept the best bid: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn accept_best_bid_without_priced_bids() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("seller_address");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(2),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address,
+                pools: vec![PoolAsset::Marker {
+                    denom: "mock.denom".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: vec![Bid {
+                    buyer_address: deps.api.addr_make("buyer-address-0"),
+                    agreement_terms_hash: "mock-hash-buyers-0".to_string(),
+                    price: None,
+                    submitted_at: env.block.time,
+                    revision: 0,
+                }],
+            },
+        )
+        .unwrap();
+
+        match execute(deps.as_mut(), env, info, AcceptBestBid { trade_id: None }) {
+            Ok(_) => {
+                panic!("failed to detect that there are no priced bids to accept")
+            }
+            Err(error) => match error {
+                ContractError::NoPricedBids => {}
+                _ => {
+                    panic!("an unexpected error was returned when there are no priced bids")
+                }
+            },
+        }
+    }
+}
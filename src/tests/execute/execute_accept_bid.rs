@@ -2,16 +2,61 @@
 mod execute_accept_bid_tests {
     use crate::contract::execute;
     use crate::error::ContractError;
+    use crate::msg::AcceptedBid;
     use crate::msg::ExecuteMsg::AcceptBid;
     use crate::query::contract_state::query_contract_state;
     use crate::storage::state_store::{
-        save_bid_list_state, save_buyer_state, save_contract_config, save_seller_state,
-        save_token_data_state, Bid, BidList, Buyer, Config, Seller, TokenData,
+        save_allowed_buyer_state, save_bid_list_state, save_buyer_state, save_contract_config,
+        save_seller_state, save_token_data_state, Bid, BidList, Buyer, Config, PoolAsset, Seller,
+        TokenData,
     };
     use crate::version_info::{set_version_info, VersionInfoV1};
-    use cosmwasm_std::testing::mock_env;
-    use cosmwasm_std::{MessageInfo, Uint128};
-    use provwasm_mocks::mock_provenance_dependencies;
+    use cosmwasm_std::testing::{mock_env, MockApi};
+    use cosmwasm_std::{
+        to_json_binary, Addr, Binary, ContractResult, CosmosMsg, MemoryStorage, MessageInfo,
+        OwnedDeps, SystemResult, Uint128,
+    };
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+    use provwasm_std::shim::Any;
+    use provwasm_std::types::cosmos::base::v1beta1::Coin;
+    use provwasm_std::types::provenance::marker::v1::{
+        Access, AccessGrant, MarkerAccount, MarkerStatus, MarkerType, MsgWithdrawRequest,
+        QueryMarkerRequest, QueryMarkerResponse,
+    };
+
+    fn register_token_marker(querier: &mut MockProvenanceQuerier, permissions: Vec<i32>) {
+        let cb_marker = Box::new(
+            move |bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+                let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+                let marker = MarkerAccount {
+                    base_account: None,
+                    manager: "".to_string(),
+                    access_control: vec![AccessGrant {
+                        address: mock_env().contract.address.to_string(),
+                        permissions: permissions.clone(),
+                    }],
+                    status: MarkerStatus::Active.into(),
+                    denom: message.id.to_string(),
+                    supply: "10".to_string(),
+                    marker_type: MarkerType::Coin.into(),
+                    supply_fixed: true,
+                    allow_governance_control: true,
+                    allow_forced_transfer: false,
+                    required_attributes: vec![],
+                };
+                let response = QueryMarkerResponse {
+                    marker: Some(Any {
+                        type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                        value: marker.to_proto_bytes(),
+                    }),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+            },
+        );
+        querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb_marker);
+    }
 
     #[test]
     fn accept_bid() {
@@ -28,15 +73,21 @@ mod execute_accept_bid_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: true,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![deps.api.addr_make("bidder_address")],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_buyer_state(&mut deps.storage, &deps.api.addr_make("bidder_address")).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -51,8 +102,14 @@ mod execute_accept_bid_tests {
             &mut deps.storage,
             &Seller {
                 seller_address,
-                pool_denoms: vec!["mock.denom".to_string()],
+                pools: vec![PoolAsset::Marker {
+                    denom: "mock.denom".to_string(),
+                }],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -65,10 +122,16 @@ mod execute_accept_bid_tests {
                     Bid {
                         buyer_address: existing_buyer_0_address.clone(),
                         agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
                     },
                     Bid {
                         buyer_address: deps.api.addr_make("existing-buyer-address-1"),
                         agreement_terms_hash: "mock-hash-existing-buyers-1".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
                     },
                 ],
             },
@@ -85,18 +148,25 @@ mod execute_accept_bid_tests {
         .unwrap();
 
         let accept_bid_message = AcceptBid {
-            bidder_address: existing_buyer_0_address.clone().to_string(),
-            agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+            accepted_bids: vec![AcceptedBid {
+                bidder_address: existing_buyer_0_address.clone().to_string(),
+                agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+                allocation: Uint128::new(1),
+                expected_revision: None,
+            }],
+            trade_id: None,
         };
+        register_token_marker(&mut deps.querier, vec![Access::Withdraw as i32]);
         match execute(deps.as_mut(), env, info, accept_bid_message) {
             Ok(_) => {
                 assert_eq!(
-                    query_contract_state(deps.as_ref()).unwrap().buyer.unwrap(),
-                    Buyer {
+                    query_contract_state(deps.as_ref()).unwrap().buyers,
+                    vec![Buyer {
                         buyer_address: deps.api.addr_make("existing-buyer-address-0"),
                         buyer_has_accepted_pools: false,
                         agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
-                    }
+                        token_amount: Uint128::new(10),
+                    }]
                 );
             }
             Err(error) => {
@@ -120,15 +190,21 @@ mod execute_accept_bid_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: true,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![deps.api.addr_make("bidder-address")],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_buyer_state(&mut deps.storage, &deps.api.addr_make("bidder-address")).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -143,8 +219,14 @@ mod execute_accept_bid_tests {
             &mut deps.storage,
             &Seller {
                 seller_address,
-                pool_denoms: vec!["mock.denom".to_string()],
+                pools: vec![PoolAsset::Marker {
+                    denom: "mock.denom".to_string(),
+                }],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -155,6 +237,9 @@ mod execute_accept_bid_tests {
                 bids: vec![Bid {
                     buyer_address: deps.api.addr_make("existing-bidder-address-0"),
                     agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -170,11 +255,16 @@ mod execute_accept_bid_tests {
         .unwrap();
 
         let accept_buyer_message = AcceptBid {
-            bidder_address: deps
-                .api
-                .addr_make("non-existing-bidder-address")
-                .to_string(),
-            agreement_terms_hash: "mock-hash".to_string(),
+            accepted_bids: vec![AcceptedBid {
+                bidder_address: deps
+                    .api
+                    .addr_make("non-existing-bidder-address")
+                    .to_string(),
+                agreement_terms_hash: "mock-hash".to_string(),
+                allocation: Uint128::new(1),
+                expected_revision: None,
+            }],
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, accept_buyer_message) {
             Ok(_) => {
@@ -211,15 +301,21 @@ mod execute_accept_bid_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: true,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![deps.api.addr_make("bidder_address")],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_buyer_state(&mut deps.storage, &deps.api.addr_make("bidder_address")).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -234,8 +330,14 @@ mod execute_accept_bid_tests {
             &mut deps.storage,
             &Seller {
                 seller_address,
-                pool_denoms: vec!["mock.denom".to_string()],
+                pools: vec![PoolAsset::Marker {
+                    denom: "mock.denom".to_string(),
+                }],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -246,6 +348,9 @@ mod execute_accept_bid_tests {
                 bids: vec![Bid {
                     buyer_address: deps.api.addr_make("existing-buyer-address-0"),
                     agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -261,8 +366,13 @@ mod execute_accept_bid_tests {
         .unwrap();
 
         let accept_buyer_message = AcceptBid {
-            bidder_address: deps.api.addr_make("existing-buyer-address-0").to_string(),
-            agreement_terms_hash: "stale-hash".to_string(),
+            accepted_bids: vec![AcceptedBid {
+                bidder_address: deps.api.addr_make("existing-buyer-address-0").to_string(),
+                agreement_terms_hash: "stale-hash".to_string(),
+                allocation: Uint128::new(1),
+                expected_revision: None,
+            }],
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, accept_buyer_message) {
             Ok(_) => {
@@ -277,6 +387,114 @@ mod execute_accept_bid_tests {
         }
     }
 
+    #[test]
+    fn accept_buyer_with_stale_revision() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("seller_address");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: true,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(2),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_buyer_state(&mut deps.storage, &deps.api.addr_make("bidder_address")).unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address,
+                pools: vec![PoolAsset::Marker {
+                    denom: "mock.denom".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: vec![Bid {
+                    buyer_address: deps.api.addr_make("existing-buyer-address-0"),
+                    agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 2,
+                }],
+            },
+        )
+        .unwrap();
+
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        let accept_buyer_message = AcceptBid {
+            accepted_bids: vec![AcceptedBid {
+                bidder_address: deps.api.addr_make("existing-buyer-address-0").to_string(),
+                agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+                allocation: Uint128::new(1),
+                expected_revision: Some(1),
+            }],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, accept_buyer_message) {
+            Ok(_) => {
+                panic!("failed to detect error when accepting a buyer with a stale bid revision")
+            }
+            Err(error) => match error {
+                ContractError::BidRevisionMismatch {
+                    expected_revision,
+                    revision,
+                    ..
+                } => {
+                    assert_eq!(expected_revision, 1);
+                    assert_eq!(revision, 2);
+                }
+                _ => {
+                    panic!("unexpected error encountered when accepting a buyer with a stale bid revision")
+                }
+            },
+        }
+    }
+
     #[test]
     fn accept_buyer_when_buyer_previously_accepted() {
         let mut deps = mock_provenance_dependencies();
@@ -294,15 +512,21 @@ mod execute_accept_bid_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: true,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![deps.api.addr_make("bidder-address")],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_buyer_state(&mut deps.storage, &deps.api.addr_make("bidder-address")).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -317,8 +541,14 @@ mod execute_accept_bid_tests {
             &mut deps.storage,
             &Seller {
                 seller_address,
-                pool_denoms: vec!["mock.denom".to_string()],
+                pools: vec![PoolAsset::Marker {
+                    denom: "mock.denom".to_string(),
+                }],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -330,10 +560,16 @@ mod execute_accept_bid_tests {
                     Bid {
                         buyer_address: buyer_address_0.clone(),
                         agreement_terms_hash: "mock-hash-buyers-0".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
                     },
                     Bid {
                         buyer_address: buyer_address_1.clone(),
                         agreement_terms_hash: "mock-hash-buyers-1".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
                     },
                 ],
             },
@@ -346,6 +582,7 @@ mod execute_accept_bid_tests {
                 buyer_address: buyer_address_0.clone(),
                 buyer_has_accepted_pools: false,
                 agreement_terms_hash: "mock-hash-buyers-0".to_string(),
+                token_amount: Uint128::new(10),
             },
         )
         .unwrap();
@@ -360,8 +597,13 @@ mod execute_accept_bid_tests {
         .unwrap();
 
         let accept_buyer_message = AcceptBid {
-            bidder_address: buyer_address_1.to_string(),
-            agreement_terms_hash: "mock-hash-buyers-1".to_string(),
+            accepted_bids: vec![AcceptedBid {
+                bidder_address: buyer_address_1.to_string(),
+                agreement_terms_hash: "mock-hash-buyers-1".to_string(),
+                allocation: Uint128::new(1),
+                expected_revision: None,
+            }],
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, accept_buyer_message) {
             Ok(_) => {
@@ -377,4 +619,375 @@ mod execute_accept_bid_tests {
             },
         }
     }
+
+    #[test]
+    fn accept_multiple_bids_pro_rata() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("seller_address");
+        let buyer_address_0 = deps.api.addr_make("buyer-address-0");
+        let buyer_address_1 = deps.api.addr_make("buyer-address-1");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(2),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address,
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: vec![
+                    Bid {
+                        buyer_address: buyer_address_0.clone(),
+                        agreement_terms_hash: "mock-hash-buyers-0".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
+                    },
+                    Bid {
+                        buyer_address: buyer_address_1.clone(),
+                        agreement_terms_hash: "mock-hash-buyers-1".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
+                    },
+                ],
+            },
+        )
+        .unwrap();
+
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        let accept_bid_message = AcceptBid {
+            accepted_bids: vec![
+                AcceptedBid {
+                    bidder_address: buyer_address_0.to_string(),
+                    agreement_terms_hash: "mock-hash-buyers-0".to_string(),
+                    allocation: Uint128::new(1),
+                    expected_revision: None,
+                },
+                AcceptedBid {
+                    bidder_address: buyer_address_1.to_string(),
+                    agreement_terms_hash: "mock-hash-buyers-1".to_string(),
+                    allocation: Uint128::new(2),
+                    expected_revision: None,
+                },
+            ],
+            trade_id: None,
+        };
+        register_token_marker(&mut deps.querier, vec![Access::Withdraw as i32]);
+        match execute(deps.as_mut(), env.clone(), info, accept_bid_message) {
+            Ok(response) => {
+                // 10 tokens split 1:2 rounds down to 3 and 6, and the remaining token goes to
+                // the first accepted bid
+                assert_eq!(
+                    response.messages[0].msg,
+                    CosmosMsg::from(MsgWithdrawRequest {
+                        denom: "test.token.fm".to_string(),
+                        administrator: env.contract.address.to_string(),
+                        to_address: buyer_address_0.to_string(),
+                        amount: vec![Coin {
+                            denom: "test.token.fm".to_string(),
+                            amount: "4".to_string(),
+                        }],
+                    })
+                );
+                let buyers = query_contract_state(deps.as_ref()).unwrap().buyers;
+                assert_eq!(buyers.len(), 2);
+                assert!(buyers.contains(&Buyer {
+                    buyer_address: buyer_address_0.clone(),
+                    buyer_has_accepted_pools: false,
+                    agreement_terms_hash: "mock-hash-buyers-0".to_string(),
+                    token_amount: Uint128::new(4),
+                }));
+                assert!(buyers.contains(&Buyer {
+                    buyer_address: buyer_address_1.clone(),
+                    buyer_has_accepted_pools: false,
+                    agreement_terms_hash: "mock-hash-buyers-1".to_string(),
+                    token_amount: Uint128::new(6),
+                }));
+            }
+            Err(error) => {
+                panic!("failed to accept multiple bids: {:?}", error)
+            }
+        }
+    }
+
+    /// Saves a public trade with three bids on the given number of minted forward market tokens and
+    /// returns the seller and the three bidder addresses
+    fn setup_three_bids(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        token_count: u128,
+    ) -> (Addr, Vec<Addr>) {
+        let seller_address = deps.api.addr_make("seller_address");
+        let bidder_addresses: Vec<Addr> = (0..3)
+            .map(|index| deps.api.addr_make(&format!("buyer-address-{index}")))
+            .collect();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: bidder_addresses
+                    .iter()
+                    .map(|bidder_address| Bid {
+                        buyer_address: bidder_address.clone(),
+                        agreement_terms_hash: format!("mock-hash-{bidder_address}"),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
+                    })
+                    .collect(),
+            },
+        )
+        .unwrap();
+
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(token_count),
+            },
+        )
+        .unwrap();
+        register_token_marker(&mut deps.querier, vec![Access::Withdraw as i32]);
+
+        (seller_address, bidder_addresses)
+    }
+
+    fn accepted_bid(bidder_address: &Addr, allocation: u128) -> AcceptedBid {
+        AcceptedBid {
+            bidder_address: bidder_address.to_string(),
+            agreement_terms_hash: format!("mock-hash-{bidder_address}"),
+            allocation: Uint128::new(allocation),
+            expected_revision: None,
+        }
+    }
+
+    #[test]
+    fn accept_bids_distributes_rounding_remainder() {
+        let mut deps = mock_provenance_dependencies();
+        let (seller_address, bidder_addresses) = setup_three_bids(&mut deps, 11);
+        let info = MessageInfo {
+            sender: seller_address,
+            funds: vec![],
+        };
+
+        // 11 tokens split evenly three ways rounds down to 3 each, and the two leftover tokens go
+        // to the first two accepted bids in order
+        let accept_bid_message = AcceptBid {
+            accepted_bids: bidder_addresses
+                .iter()
+                .map(|bidder_address| accepted_bid(bidder_address, 1))
+                .collect(),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), mock_env(), info, accept_bid_message) {
+            Ok(_) => {
+                let buyers = query_contract_state(deps.as_ref()).unwrap().buyers;
+                let token_amount = |bidder_address: &Addr| {
+                    buyers
+                        .iter()
+                        .find(|buyer| buyer.buyer_address == *bidder_address)
+                        .unwrap()
+                        .token_amount
+                };
+                assert_eq!(token_amount(&bidder_addresses[0]), Uint128::new(4));
+                assert_eq!(token_amount(&bidder_addresses[1]), Uint128::new(4));
+                assert_eq!(token_amount(&bidder_addresses[2]), Uint128::new(3));
+            }
+            Err(error) => {
+                panic!(
+                    "failed to accept bids with a rounding remainder: {:?}",
+                    error
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn accept_bids_with_invalid_allocation() {
+        let mut deps = mock_provenance_dependencies();
+        let (seller_address, bidder_addresses) = setup_three_bids(&mut deps, 10);
+        let info = MessageInfo {
+            sender: seller_address,
+            funds: vec![],
+        };
+
+        let invalid_allocations = [
+            ("no accepted bids", vec![]),
+            (
+                "a zero allocation",
+                vec![
+                    accepted_bid(&bidder_addresses[0], 1),
+                    accepted_bid(&bidder_addresses[1], 0),
+                ],
+            ),
+            (
+                "a duplicate bidder",
+                vec![
+                    accepted_bid(&bidder_addresses[0], 1),
+                    accepted_bid(&bidder_addresses[0], 1),
+                ],
+            ),
+            (
+                "an allocation too small to yield a token",
+                vec![
+                    accepted_bid(&bidder_addresses[0], 1000),
+                    accepted_bid(&bidder_addresses[1], 1000),
+                    accepted_bid(&bidder_addresses[2], 1),
+                ],
+            ),
+        ];
+        for (description, accepted_bids) in invalid_allocations {
+            let accept_bid_message = AcceptBid {
+                accepted_bids,
+                trade_id: None,
+            };
+            match execute(deps.as_mut(), mock_env(), info.clone(), accept_bid_message) {
+                Ok(_) => {
+                    panic!("failed to detect {description} when accepting bids")
+                }
+                Err(error) => match error {
+                    ContractError::InvalidBidAllocation => {}
+                    _ => {
+                        panic!(
+                            "unexpected error encountered when accepting bids with {description}: {:?}",
+                            error
+                        )
+                    }
+                },
+            }
+        }
+        assert!(query_contract_state(deps.as_ref())
+            .unwrap()
+            .buyers
+            .is_empty());
+    }
+
+    #[test]
+    fn accept_bids_without_withdraw_access() {
+        let mut deps = mock_provenance_dependencies();
+        let (seller_address, bidder_addresses) = setup_three_bids(&mut deps, 10);
+        let info = MessageInfo {
+            sender: seller_address,
+            funds: vec![],
+        };
+
+        // The buyers' tokens cannot be withdrawn once the contract loses withdraw access
+        register_token_marker(&mut deps.querier, vec![Access::Transfer as i32]);
+        let accept_bid_message = AcceptBid {
+            accepted_bids: vec![accepted_bid(&bidder_addresses[0], 1)],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), mock_env(), info, accept_bid_message) {
+            Ok(_) => {
+                panic!("failed to detect a forward market marker without withdraw access")
+            }
+            Err(error) => match error {
+                ContractError::MissingMarkerAccess { denom, access } => {
+                    assert_eq!(denom, "test.token.fm");
+                    assert_eq!(access, "ACCESS_WITHDRAW");
+                    assert!(query_contract_state(deps.as_ref())
+                        .unwrap()
+                        .buyers
+                        .is_empty());
+                }
+                _ => {
+                    panic!("unexpected error when accepting without withdraw access: {error:?}")
+                }
+            },
+        }
+    }
 }
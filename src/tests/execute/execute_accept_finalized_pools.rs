@@ -0,0 +1,307 @@
+#[cfg(test)]
+mod execute_accept_finalized_pools_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::AcceptedOffer;
+    use crate::msg::ExecuteMsg::AcceptFinalizedPools;
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_buyer_state, save_contract_config, save_seller_state, BidList,
+        Buyer, Config, PoolAsset, Seller,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+    use cosmwasm_std::{Addr, MessageInfo, OwnedDeps, Uint128};
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+
+    fn setup_sellers_and_buyer(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockProvenanceQuerier>,
+        buyer_address: &Addr,
+        sellers: Vec<Seller>,
+    ) {
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        for seller in sellers {
+            save_seller_state(&mut deps.storage, &seller).unwrap();
+        }
+
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn accept_combined_allocation() {
+        let mut deps = mock_provenance_dependencies();
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let seller_address_0 = deps.api.addr_make("seller-0");
+        let seller_address_1 = deps.api.addr_make("seller-1");
+        let unfinalized_seller_address = deps.api.addr_make("seller-2");
+        let info = MessageInfo {
+            sender: buyer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        setup_sellers_and_buyer(
+            &mut deps,
+            &buyer_address,
+            vec![
+                Seller {
+                    seller_address: seller_address_0.clone(),
+                    pools: vec![PoolAsset::Marker {
+                        denom: "test.denom.pool.0".to_string(),
+                    }],
+                    offer_hash: "offer-hash-0".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
+                },
+                Seller {
+                    seller_address: seller_address_1.clone(),
+                    pools: vec![PoolAsset::Marker {
+                        denom: "test.denom.pool.1".to_string(),
+                    }],
+                    offer_hash: "offer-hash-1".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
+                },
+                Seller {
+                    seller_address: unfinalized_seller_address,
+                    pools: vec![],
+                    offer_hash: "offer-hash-2".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
+                },
+            ],
+        );
+
+        let accept_message = AcceptFinalizedPools {
+            offers: vec![
+                AcceptedOffer {
+                    seller_address: seller_address_1.to_string(),
+                    offer_hash: "offer-hash-1".to_string(),
+                    offer_revision: 1,
+                },
+                AcceptedOffer {
+                    seller_address: seller_address_0.to_string(),
+                    offer_hash: "offer-hash-0".to_string(),
+                    offer_revision: 1,
+                },
+            ],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, accept_message) {
+            Ok(_) => {
+                assert!(
+                    query_contract_state(deps.as_ref()).unwrap().buyers[0].buyer_has_accepted_pools
+                );
+            }
+            Err(error) => {
+                panic!("failed to accept the combined allocation: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn reject_allocation_missing_a_seller() {
+        let mut deps = mock_provenance_dependencies();
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let seller_address_0 = deps.api.addr_make("seller-0");
+        let seller_address_1 = deps.api.addr_make("seller-1");
+        let info = MessageInfo {
+            sender: buyer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        setup_sellers_and_buyer(
+            &mut deps,
+            &buyer_address,
+            vec![
+                Seller {
+                    seller_address: seller_address_0.clone(),
+                    pools: vec![PoolAsset::Marker {
+                        denom: "test.denom.pool.0".to_string(),
+                    }],
+                    offer_hash: "offer-hash-0".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
+                },
+                Seller {
+                    seller_address: seller_address_1,
+                    pools: vec![PoolAsset::Marker {
+                        denom: "test.denom.pool.1".to_string(),
+                    }],
+                    offer_hash: "offer-hash-1".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
+                },
+            ],
+        );
+
+        let accept_message = AcceptFinalizedPools {
+            offers: vec![AcceptedOffer {
+                seller_address: seller_address_0.to_string(),
+                offer_hash: "offer-hash-0".to_string(),
+                offer_revision: 1,
+            }],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, accept_message) {
+            Ok(_) => {
+                panic!("failed to detect an accepted allocation that omits a finalized seller")
+            }
+            Err(error) => match error {
+                ContractError::InvalidAcceptedOffers => {}
+                _ => {
+                    panic!("an unexpected error was returned when a finalized seller was omitted")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn reject_allocation_with_stale_offer_hash() {
+        let mut deps = mock_provenance_dependencies();
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let seller_address = deps.api.addr_make("seller-0");
+        let info = MessageInfo {
+            sender: buyer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        setup_sellers_and_buyer(
+            &mut deps,
+            &buyer_address,
+            vec![Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool.0".to_string(),
+                }],
+                offer_hash: "offer-hash-0".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            }],
+        );
+
+        let accept_message = AcceptFinalizedPools {
+            offers: vec![AcceptedOffer {
+                seller_address: seller_address.to_string(),
+                offer_hash: "stale-offer-hash".to_string(),
+                offer_revision: 1,
+            }],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, accept_message) {
+            Ok(_) => {
+                panic!("failed to detect an accepted offer with a stale offer hash")
+            }
+            Err(error) => match error {
+                ContractError::InvalidOfferHash => {}
+                _ => {
+                    panic!("an unexpected error was returned when accepting a stale offer hash")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn reject_allocation_with_stale_offer_revision() {
+        let mut deps = mock_provenance_dependencies();
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let seller_address = deps.api.addr_make("seller-0");
+        let info = MessageInfo {
+            sender: buyer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        setup_sellers_and_buyer(
+            &mut deps,
+            &buyer_address,
+            vec![Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool.0".to_string(),
+                }],
+                offer_hash: "offer-hash-0".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 2,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            }],
+        );
+
+        let accept_message = AcceptFinalizedPools {
+            offers: vec![AcceptedOffer {
+                seller_address: seller_address.to_string(),
+                offer_hash: "offer-hash-0".to_string(),
+                offer_revision: 1,
+            }],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, accept_message) {
+            Ok(_) => {
+                panic!("failed to detect an accepted offer with a stale offer revision")
+            }
+            Err(error) => match error {
+                ContractError::OfferRevisionMismatch {
+                    expected_revision,
+                    revision,
+                    ..
+                } => {
+                    assert_eq!(expected_revision, 1);
+                    assert_eq!(revision, 2);
+                }
+                _ => {
+                    panic!("an unexpected error was returned when accepting a stale offer revision")
+                }
+            },
+        }
+    }
+}
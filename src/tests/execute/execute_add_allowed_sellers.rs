@@ -0,0 +1,164 @@
+#[cfg(test)]
+mod execute_add_allowed_sellers_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{AddAllowedSellers, RemoveAllowedSellers};
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_allowed_seller_state, save_bid_list_state, save_contract_config, BidList, Config,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{Attribute, MessageInfo};
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn add_and_remove_allowed_sellers() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let info = MessageInfo {
+            sender: contract_admin.clone(),
+            funds: vec![],
+        };
+        let allowed_seller_0_addr = deps.api.addr_make("allowed-seller-0");
+        let allowed_seller_1_addr = deps.api.addr_make("allowed-seller-1");
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(8),
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_0_addr).unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        // Sellers that are already allowed or repeated in the request are only added once
+        let add_allowed_sellers = AddAllowedSellers {
+            allowed_sellers: vec![
+                allowed_seller_0_addr.to_string(),
+                allowed_seller_1_addr.to_string(),
+                allowed_seller_1_addr.to_string(),
+            ],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), mock_env(), info.clone(), add_allowed_sellers) {
+            Ok(response) => {
+                assert_eq!(
+                    response.attributes,
+                    vec![Attribute::new(
+                        "added_allowed_sellers",
+                        format!("{:?}", vec![allowed_seller_1_addr.clone()])
+                    )]
+                );
+                let mut expected_allowed_sellers =
+                    vec![allowed_seller_0_addr.clone(), allowed_seller_1_addr.clone()];
+                expected_allowed_sellers.sort();
+                assert_eq!(
+                    query_contract_state(deps.as_ref()).unwrap().allowed_sellers,
+                    expected_allowed_sellers
+                );
+            }
+            Err(error) => {
+                panic!("failed to add allowed sellers: {:?}", error)
+            }
+        }
+
+        // Sellers that are not allowed are not reported as removed
+        let remove_allowed_sellers = RemoveAllowedSellers {
+            allowed_sellers: vec![
+                allowed_seller_0_addr.to_string(),
+                deps.api.addr_make("not-allowed-seller").to_string(),
+            ],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), mock_env(), info, remove_allowed_sellers) {
+            Ok(response) => {
+                assert_eq!(
+                    response.attributes,
+                    vec![Attribute::new(
+                        "removed_allowed_sellers",
+                        format!("{:?}", vec![allowed_seller_0_addr.clone()])
+                    )]
+                );
+                assert_eq!(
+                    query_contract_state(deps.as_ref()).unwrap().allowed_sellers,
+                    vec![allowed_seller_1_addr]
+                );
+            }
+            Err(error) => {
+                panic!("failed to remove allowed sellers: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn add_allowed_sellers_unauthorized() {
+        let mut deps = mock_provenance_dependencies();
+        let info = MessageInfo {
+            sender: deps.api.addr_make("not-the-admin"),
+            funds: vec![],
+        };
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(8),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        let add_allowed_sellers = AddAllowedSellers {
+            allowed_sellers: vec![deps.api.addr_make("allowed-seller-0").to_string()],
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), mock_env(), info, add_allowed_sellers) {
+            Ok(_) => {
+                panic!("failed to detect an unauthorized update to the allowed sellers")
+            }
+            Err(error) => match error {
+                ContractError::UnauthorizedConfigUpdate => {}
+                _ => {
+                    panic!(
+                        "an unexpected error was returned when an address other than the admin \
+                            updated the allowed sellers"
+                    )
+                }
+            },
+        }
+    }
+}
@@ -2,15 +2,19 @@
 mod execute_add_bidder_tests {
     use crate::contract::execute;
     use crate::error::ContractError;
-    use crate::msg::ExecuteMsg::AddBid;
+    use crate::msg::ExecuteMsg::{AddBid, UpdateMaxBidCount};
     use crate::query::contract_state::query_contract_state;
     use crate::storage::state_store::{
-        save_bid_list_state, save_contract_config, Bid, BidList, Config,
+        save_allowed_buyer_state, save_bid_list_state, save_contract_config, Bid, BidList, Config,
+        RequiredAttributesConfig,
     };
     use crate::version_info::{set_version_info, VersionInfoV1};
     use cosmwasm_std::testing::mock_env;
-    use cosmwasm_std::MessageInfo;
+    use cosmwasm_std::{to_json_binary, Binary, ContractResult, MessageInfo, SystemResult};
     use provwasm_mocks::mock_provenance_dependencies;
+    use provwasm_std::types::provenance::attribute::v1::{
+        QueryAttributeRequest, QueryAttributeResponse,
+    };
 
     #[test]
     fn add_bidders_to_public_forward_market() {
@@ -27,12 +31,17 @@ mod execute_add_bidder_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
@@ -49,6 +58,9 @@ mod execute_add_bidder_tests {
         let existing_bidder = Bid {
             buyer_address: deps.api.addr_make("existing-buyer-address"),
             agreement_terms_hash: "mock-hash-existing-buyers".to_string(),
+            price: None,
+            submitted_at: mock_env().block.time,
+            revision: 0,
         };
         save_bid_list_state(
             &mut deps.storage,
@@ -60,6 +72,8 @@ mod execute_add_bidder_tests {
 
         let add_bidder_message = AddBid {
             agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, add_bidder_message) {
             Ok(_) => {
@@ -70,6 +84,9 @@ mod execute_add_bidder_tests {
                         Bid {
                             buyer_address: bidder_address.clone(),
                             agreement_terms_hash: "buyer-mock-hash".to_string(),
+                            price: None,
+                            submitted_at: mock_env().block.time,
+                            revision: 1,
                         }
                     ]
                 );
@@ -95,15 +112,21 @@ mod execute_add_bidder_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: true,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![bidder_address.clone()],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_buyer_state(&mut deps.storage, &bidder_address).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -118,6 +141,8 @@ mod execute_add_bidder_tests {
 
         let add_bidder_message = AddBid {
             agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, add_bidder_message) {
             Ok(_) => {
@@ -126,6 +151,9 @@ mod execute_add_bidder_tests {
                     vec![Bid {
                         buyer_address: bidder_address.clone(),
                         agreement_terms_hash: "buyer-mock-hash".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 1,
                     }]
                 );
             }
@@ -135,6 +163,78 @@ mod execute_add_bidder_tests {
         }
     }
 
+    #[test]
+    fn rebid_keeps_prior_revisions() {
+        let mut deps = mock_provenance_dependencies();
+        let bidder_address = deps.api.addr_make("bidder-address");
+        let info = MessageInfo {
+            sender: bidder_address.clone(),
+            funds: vec![],
+        };
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        for agreement_terms_hash in ["buyer-mock-hash-0", "buyer-mock-hash-1"] {
+            let add_bidder_message = AddBid {
+                agreement_terms_hash: agreement_terms_hash.to_string(),
+                price: None,
+                trade_id: None,
+            };
+            execute(deps.as_mut(), mock_env(), info.clone(), add_bidder_message).unwrap();
+        }
+
+        let contract_state = query_contract_state(deps.as_ref()).unwrap();
+        assert_eq!(
+            contract_state.bids,
+            vec![Bid {
+                buyer_address: bidder_address.clone(),
+                agreement_terms_hash: "buyer-mock-hash-1".to_string(),
+                price: None,
+                submitted_at: mock_env().block.time,
+                revision: 2,
+            }]
+        );
+        assert_eq!(
+            contract_state.bid_revisions,
+            vec![Bid {
+                buyer_address: bidder_address.clone(),
+                agreement_terms_hash: "buyer-mock-hash-0".to_string(),
+                price: None,
+                submitted_at: mock_env().block.time,
+                revision: 1,
+            }]
+        );
+    }
+
     #[test]
     fn reject_disallowed_bidder_private_forward_market() {
         let mut deps = mock_provenance_dependencies();
@@ -150,15 +250,25 @@ mod execute_add_bidder_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: true,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![deps.api.addr_make("allowed-bidder-address")],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_buyer_state(
+            &mut deps.storage,
+            &deps.api.addr_make("allowed-bidder-address"),
+        )
+        .unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -173,6 +283,8 @@ mod execute_add_bidder_tests {
 
         let add_bidder_message = AddBid {
             agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, add_bidder_message) {
             Ok(_) => {
@@ -204,15 +316,21 @@ mod execute_add_bidder_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: true,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![bidder_address.clone()],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_buyer_state(&mut deps.storage, &bidder_address).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -230,10 +348,16 @@ mod execute_add_bidder_tests {
                     Bid {
                         buyer_address: deps.api.addr_make("existing-buyer-address-0"),
                         agreement_terms_hash: "mock-hash-existing-buyers-0".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
                     },
                     Bid {
                         buyer_address: deps.api.addr_make("existing-buyer-address-1"),
                         agreement_terms_hash: "mock-hash-existing-buyers-1".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 0,
                     },
                 ],
             },
@@ -242,6 +366,8 @@ mod execute_add_bidder_tests {
 
         let add_bidder_message = AddBid {
             agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, add_bidder_message) {
             Ok(_) => {
@@ -249,7 +375,7 @@ mod execute_add_bidder_tests {
             }
             Err(error) => {
                 match error {
-                    ContractError::MaxPrivateBuyersReached => {}
+                    ContractError::MaxBuyersReached => {}
                     _ => {
                         panic!("Unexpected error returned when attempting to breach max bidder threshold")
                     }
@@ -257,4 +383,169 @@ mod execute_add_bidder_tests {
             }
         }
     }
+
+    #[test]
+    fn reject_bidder_without_required_attributes() {
+        let mut deps = mock_provenance_dependencies();
+        let bidder_address = deps.api.addr_make("bidder-address");
+        let info = MessageInfo {
+            sender: bidder_address.clone(),
+            funds: vec![],
+        };
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: Some(RequiredAttributesConfig {
+                    sellers: vec![],
+                    buyers: vec!["kyc.buyer.pb".to_string()],
+                    dealers: vec![],
+                }),
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        // No account holds the buyer attribute
+        let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryAttributeRequest::try_from(bin.clone()).unwrap();
+            let response = QueryAttributeResponse {
+                account: message.account,
+                attributes: vec![],
+                pagination: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.attribute.v1.Query/Attribute".to_string(), cb);
+
+        let add_bidder_message = AddBid {
+            agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), mock_env(), info, add_bidder_message) {
+            Ok(_) => {
+                panic!("Failed to detect error when a bidder without the required attributes submitted a bid")
+            }
+            Err(error) => match error {
+                ContractError::MissingRequiredAttribute { attribute, .. } => {
+                    assert_eq!(attribute, "kyc.buyer.pb");
+                }
+                _ => {
+                    panic!("Unexpected error returned when a bidder without the required attributes submitted a bid")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn enforce_max_bidders_public_forward_market() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let bidder_0_address = deps.api.addr_make("bidder-0");
+        let bidder_1_address = deps.api.addr_make("bidder-1");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            MessageInfo {
+                sender: contract_admin,
+                funds: vec![],
+            },
+            UpdateMaxBidCount {
+                max_bid_count: Some(1),
+                trade_id: None,
+            },
+        )
+        .unwrap();
+
+        let bidder_0_info = MessageInfo {
+            sender: bidder_0_address.clone(),
+            funds: vec![],
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            bidder_0_info.clone(),
+            AddBid {
+                agreement_terms_hash: "buyer-mock-hash".to_string(),
+                price: None,
+                trade_id: None,
+            },
+        )
+        .unwrap();
+
+        // Replacing an existing bid does not count against the limit
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            bidder_0_info,
+            AddBid {
+                agreement_terms_hash: "buyer-mock-hash-updated".to_string(),
+                price: None,
+                trade_id: None,
+            },
+        )
+        .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            env,
+            MessageInfo {
+                sender: bidder_1_address,
+                funds: vec![],
+            },
+            AddBid {
+                agreement_terms_hash: "buyer-mock-hash".to_string(),
+                price: None,
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                panic!("Failed to detect error when the max bid count of a public contract was exceeded")
+            }
+            Err(error) => match error {
+                ContractError::MaxBuyersReached => {}
+                _ => {
+                    panic!("Unexpected error returned when the max bid count of a public contract was exceeded")
+                }
+            },
+        }
+    }
 }
@@ -5,12 +5,16 @@ mod execute_add_seller_tests {
     use crate::msg::ExecuteMsg::AddSeller;
     use crate::query::contract_state::query_contract_state;
     use crate::storage::state_store::{
-        save_bid_list_state, save_contract_config, save_seller_state, Bid, BidList, Config, Seller,
+        save_allowed_seller_state, save_bid_list_state, save_contract_config, save_seller_state,
+        Bid, BidList, Config, PoolAsset, RequiredAttributesConfig, Seller,
     };
     use crate::version_info::{set_version_info, VersionInfoV1};
     use cosmwasm_std::testing::mock_env;
-    use cosmwasm_std::MessageInfo;
+    use cosmwasm_std::{to_json_binary, Binary, ContractResult, MessageInfo, SystemResult};
     use provwasm_mocks::mock_provenance_dependencies;
+    use provwasm_std::types::provenance::attribute::v1::{
+        Attribute, AttributeType, QueryAttributeRequest, QueryAttributeResponse,
+    };
 
     #[test]
     fn add_seller_to_public_forward_market() {
@@ -26,6 +30,7 @@ mod execute_add_seller_tests {
 
         let add_seller_msg = AddSeller {
             offer_hash: "mock-offer-hash".to_string(),
+            trade_id: None,
         };
 
         save_contract_config(
@@ -33,12 +38,17 @@ mod execute_add_seller_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
@@ -58,6 +68,9 @@ mod execute_add_seller_tests {
                 bids: vec![Bid {
                     buyer_address: buyer_address.clone(),
                     agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -66,12 +79,16 @@ mod execute_add_seller_tests {
         match execute(deps.as_mut(), env, info, add_seller_msg) {
             Ok(_) => {
                 assert_eq!(
-                    query_contract_state(deps.as_ref()).unwrap().seller.unwrap(),
-                    Seller {
+                    query_contract_state(deps.as_ref()).unwrap().sellers,
+                    vec![Seller {
                         seller_address: seller_address.clone(),
-                        pool_denoms: vec![],
+                        pools: vec![],
                         offer_hash: "mock-offer-hash".to_string(),
-                    }
+                        scope_addresses: vec![],
+                        offer_revision: 1,
+                        offer_updated_at: mock_env().block.time,
+                        received_pools: vec![],
+                    }]
                 );
             }
             Err(error) => {
@@ -94,12 +111,17 @@ mod execute_add_seller_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
@@ -107,15 +129,20 @@ mod execute_add_seller_tests {
         save_seller_state(
             &mut deps.storage,
             &Seller {
-                seller_address: deps.api.addr_make("existing_seller"),
-                pool_denoms: vec![],
+                seller_address: contract_admin.clone(),
+                pools: vec![],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
 
         let add_seller_msg = AddSeller {
             offer_hash: "mock-offer-hash".to_string(),
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, add_seller_msg) {
             Ok(_) => {
@@ -130,6 +157,85 @@ mod execute_add_seller_tests {
         }
     }
 
+    #[test]
+    fn add_multiple_sellers() {
+        let mut deps = mock_provenance_dependencies();
+        let existing_seller_address = deps.api.addr_make("existing-seller");
+        let seller_address = deps.api.addr_make("additional-seller");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(2),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        let existing_seller = Seller {
+            seller_address: existing_seller_address.clone(),
+            pools: vec![PoolAsset::Marker {
+                denom: "test.denom.pool.0".to_string(),
+            }],
+            offer_hash: "existing-offer-hash".to_string(),
+            scope_addresses: vec![],
+            offer_revision: 1,
+            offer_updated_at: mock_env().block.time,
+            received_pools: vec![],
+        };
+        save_seller_state(&mut deps.storage, &existing_seller).unwrap();
+
+        let add_seller_msg = AddSeller {
+            offer_hash: "mock-offer-hash".to_string(),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, add_seller_msg) {
+            Ok(_) => {
+                let sellers = query_contract_state(deps.as_ref()).unwrap().sellers;
+                assert_eq!(sellers.len(), 2);
+                assert!(sellers.contains(&existing_seller));
+                assert!(sellers.contains(&Seller {
+                    seller_address: seller_address.clone(),
+                    pools: vec![],
+                    offer_hash: "mock-offer-hash".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
+                }));
+            }
+            Err(error) => {
+                panic!("failed to add an additional seller: {:?}", error)
+            }
+        }
+    }
+
     #[test]
     fn add_seller_to_private_forward_market() {
         let mut deps = mock_provenance_dependencies();
@@ -144,6 +250,7 @@ mod execute_add_seller_tests {
         let dealer_address = "dealer-address";
         let add_seller_msg = AddSeller {
             offer_hash: "mock-offer-hash".to_string(),
+            trade_id: None,
         };
 
         save_contract_config(
@@ -151,15 +258,21 @@ mod execute_add_seller_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: true,
-                allowed_sellers: vec![seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make(dealer_address)],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make(contract_admin),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -176,6 +289,9 @@ mod execute_add_seller_tests {
                 bids: vec![Bid {
                     buyer_address: buyer_address.clone(),
                     agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -184,12 +300,16 @@ mod execute_add_seller_tests {
         match execute(deps.as_mut(), env, info, add_seller_msg) {
             Ok(_) => {
                 assert_eq!(
-                    query_contract_state(deps.as_ref()).unwrap().seller.unwrap(),
-                    Seller {
+                    query_contract_state(deps.as_ref()).unwrap().sellers,
+                    vec![Seller {
                         seller_address: seller_address.clone(),
-                        pool_denoms: vec![],
+                        pools: vec![],
                         offer_hash: "mock-offer-hash".to_string(),
-                    }
+                        scope_addresses: vec![],
+                        offer_revision: 1,
+                        offer_updated_at: mock_env().block.time,
+                        received_pools: vec![],
+                    }]
                 );
             }
             Err(error) => {
@@ -209,6 +329,7 @@ mod execute_add_seller_tests {
         let env = mock_env();
         let add_seller_msg = AddSeller {
             offer_hash: "mock-offer-hash".to_string(),
+            trade_id: None,
         };
 
         save_contract_config(
@@ -216,12 +337,17 @@ mod execute_add_seller_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 5,
+                max_bid_count: Some(5),
                 contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
@@ -240,4 +366,113 @@ mod execute_add_seller_tests {
             },
         }
     }
+
+    #[test]
+    fn add_seller_with_required_attributes() {
+        let mut deps = mock_provenance_dependencies();
+        let kyc_seller_address = deps.api.addr_make("kyc-seller");
+        let other_seller_address = deps.api.addr_make("other-seller");
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(5),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: Some(RequiredAttributesConfig {
+                    sellers: vec!["kyc.seller.pb".to_string()],
+                    buyers: vec![],
+                    dealers: vec![],
+                }),
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &kyc_seller_address).unwrap();
+        save_allowed_seller_state(&mut deps.storage, &other_seller_address).unwrap();
+
+        // Only the kyc seller holds the seller attribute
+        let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryAttributeRequest::try_from(bin.clone()).unwrap();
+            let inner_deps = mock_provenance_dependencies();
+            let attributes = if message.account
+                == inner_deps.api.addr_make("kyc-seller").to_string()
+                && message.name == "kyc.seller.pb"
+            {
+                vec![Attribute {
+                    name: message.name.clone(),
+                    value: vec![],
+                    attribute_type: AttributeType::String.into(),
+                    address: message.account.clone(),
+                    expiration_date: None,
+                }]
+            } else {
+                vec![]
+            };
+            let response = QueryAttributeResponse {
+                account: message.account,
+                attributes,
+                pagination: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.attribute.v1.Query/Attribute".to_string(), cb);
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: other_seller_address.clone(),
+                funds: vec![],
+            },
+            AddSeller {
+                offer_hash: "mock-offer-hash".to_string(),
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                panic!("failed to return an error when adding a seller without the required attributes")
+            }
+            Err(error) => match error {
+                ContractError::MissingRequiredAttribute { address, attribute } => {
+                    assert_eq!(address, other_seller_address.to_string());
+                    assert_eq!(attribute, "kyc.seller.pb");
+                }
+                _ => {
+                    panic!("an unexpected error was returned when adding a seller without the required attributes")
+                }
+            },
+        }
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: kyc_seller_address.clone(),
+                funds: vec![],
+            },
+            AddSeller {
+                offer_hash: "mock-offer-hash".to_string(),
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {}
+            Err(error) => {
+                panic!(
+                    "failed to add a seller with the required attributes: {:?}",
+                    error
+                )
+            }
+        }
+    }
 }
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod execute_commit_bid_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{AddBid, CommitBid};
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_contract_config, AuctionConfig, BidCommitment, BidList, Config,
+    };
+    use crate::util::helpers::create_bid_commitment;
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::MessageInfo;
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn commit_bid_during_commit_window() {
+        let mut deps = mock_provenance_dependencies();
+        let bidder_address = deps.api.addr_make("bidder-address");
+        let info = MessageInfo {
+            sender: bidder_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: Some(AuctionConfig {
+                    commit_deadline: env.block.time.plus_seconds(60),
+                    reveal_deadline: env.block.time.plus_seconds(120),
+                }),
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        let commitment = create_bid_commitment("buyer-mock-hash", None, "buyer-salt");
+        let commit_bid_message = CommitBid {
+            commitment: commitment.clone(),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env.clone(), info.clone(), commit_bid_message) {
+            Ok(_) => {
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert_eq!(
+                    contract_state.bid_commitments,
+                    vec![BidCommitment {
+                        buyer_address: bidder_address.clone(),
+                        commitment,
+                        committed_at: mock_env().block.time,
+                    }]
+                );
+                assert!(contract_state.bids.is_empty());
+            }
+            Err(error) => {
+                panic!("failed to commit bid: {:?}", error)
+            }
+        }
+
+        // Bids cannot bypass the commitment while the auction is running
+        let add_bidder_message = AddBid {
+            agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, add_bidder_message) {
+            Ok(_) => {
+                panic!("failed to detect an unsealed bid on a sealed-bid auction")
+            }
+            Err(error) => match error {
+                ContractError::SealedBidRequired => {}
+                _ => {
+                    panic!("an unexpected error was returned when adding an unsealed bid")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn commit_bid_after_commit_deadline() {
+        let mut deps = mock_provenance_dependencies();
+        let info = MessageInfo {
+            sender: deps.api.addr_make("bidder-address"),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: Some(AuctionConfig {
+                    commit_deadline: env.block.time.minus_seconds(60),
+                    reveal_deadline: env.block.time.plus_seconds(60),
+                }),
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        let commit_bid_message = CommitBid {
+            commitment: create_bid_commitment("buyer-mock-hash", None, "buyer-salt"),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, commit_bid_message) {
+            Ok(_) => {
+                panic!("failed to detect a bid committed after the commit deadline")
+            }
+            Err(error) => match error {
+                ContractError::CommitWindowClosed => {}
+                _ => {
+                    panic!("an unexpected error was returned when committing a late bid")
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,463 @@
+#[cfg(test)]
+mod execute_create_trade_tests {
+    use crate::contract::{execute, query};
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{AddAllowedSellers, AddSeller, CreateTrade, UpdateMaxBidCount};
+    use crate::msg::QueryMsg::{GetContractState, GetHistory};
+    use crate::msg::{GetContractStateResponse, GetHistoryResponse};
+    use crate::storage::state_store::{
+        save_allowed_seller_state, save_bid_list_state, save_contract_config, AuctionConfig,
+        BidList, Config, Seller,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{from_json, MessageInfo, OwnedDeps};
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn create_trade_with_overrides() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let trade_dealer_address = deps.api.addr_make("trade-dealer-address");
+        let allowed_seller_address = deps.api.addr_make("allowed-seller");
+        let seller_address = deps.api.addr_make("seller-address");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_address).unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        let response = execute(
+            deps.as_mut(),
+            env.clone(),
+            MessageInfo {
+                sender: contract_admin.clone(),
+                funds: vec![],
+            },
+            CreateTrade {
+                use_private_sellers: Some(false),
+                use_private_buyers: None,
+                allowed_sellers: None,
+                allowed_buyers: None,
+                max_bid_count: None,
+                dealers: Some(vec![trade_dealer_address.to_string()]),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: None,
+            },
+        )
+        .unwrap();
+        assert_eq!("1", response.attributes[0].value);
+
+        // The trade is public, so a seller outside of the shared allowed list can join it
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            MessageInfo {
+                sender: seller_address.clone(),
+                funds: vec![],
+            },
+            AddSeller {
+                offer_hash: "mock-offer-hash".to_string(),
+                trade_id: Some(1),
+            },
+        )
+        .unwrap();
+
+        let trade_state: GetContractStateResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetContractState { trade_id: Some(1) },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(!trade_state.config.use_private_sellers);
+        assert_eq!(Some(3), trade_state.config.max_bid_count);
+        assert_eq!(vec![trade_dealer_address], trade_state.config.dealers);
+        assert!(trade_state.allowed_sellers.is_empty());
+        assert_eq!(
+            vec![Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            }],
+            trade_state.sellers
+        );
+        assert_eq!("mock", trade_state.version_info.definition);
+
+        // The trade created at instantiation is left untouched
+        let contract_state: GetContractStateResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetContractState { trade_id: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(contract_state.config.use_private_sellers);
+        assert_eq!(vec![dealer_address], contract_state.config.dealers);
+        assert_eq!(vec![allowed_seller_address], contract_state.allowed_sellers);
+        assert!(contract_state.sellers.is_empty());
+
+        match execute(
+            deps.as_mut(),
+            env,
+            MessageInfo {
+                sender: seller_address,
+                funds: vec![],
+            },
+            AddSeller {
+                offer_hash: "mock-offer-hash".to_string(),
+                trade_id: Some(2),
+            },
+        ) {
+            Ok(_) => panic!("expected error, but ok"),
+            Err(error) => match error {
+                ContractError::UnknownTrade { trade_id } => assert_eq!(2, trade_id),
+                error => panic!("unexpected error: {:?}", error),
+            },
+        }
+    }
+
+    #[test]
+    fn create_trade_as_non_admin() {
+        let mut deps = mock_provenance_dependencies();
+        let sender = deps.api.addr_make("not-the-admin");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            env,
+            MessageInfo {
+                sender,
+                funds: vec![],
+            },
+            CreateTrade {
+                use_private_sellers: None,
+                use_private_buyers: None,
+                allowed_sellers: None,
+                allowed_buyers: None,
+                max_bid_count: None,
+                dealers: None,
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: None,
+            },
+        ) {
+            Ok(_) => panic!("expected error, but ok"),
+            Err(error) => match error {
+                ContractError::UnauthorizedTradeCreation => {}
+                error => panic!("unexpected error: {:?}", error),
+            },
+        }
+    }
+
+    #[test]
+    fn create_trade_with_closed_auction() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let env = mock_env();
+
+        // The auction set at instantiation closed its commit window before the new trade is created
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: contract_admin.clone(),
+                auction: Some(AuctionConfig {
+                    commit_deadline: env.block.time.minus_seconds(100),
+                    reveal_deadline: env.block.time.minus_seconds(50),
+                }),
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+        let info = MessageInfo {
+            sender: contract_admin,
+            funds: vec![],
+        };
+        let create_trade = |auction: Option<AuctionConfig>| CreateTrade {
+            use_private_sellers: None,
+            use_private_buyers: None,
+            allowed_sellers: None,
+            allowed_buyers: None,
+            max_bid_count: None,
+            dealers: None,
+            auction,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: None,
+        };
+
+        match execute(deps.as_mut(), env.clone(), info.clone(), create_trade(None)) {
+            Ok(_) => panic!("failed to detect a shared auction whose commit window has closed"),
+            Err(error) => match error {
+                ContractError::InvalidAuctionConfig => {}
+                error => panic!("unexpected error: {:?}", error),
+            },
+        }
+
+        // Overriding the auction gives the new trade its own deadlines
+        let auction = AuctionConfig {
+            commit_deadline: env.block.time.plus_seconds(100),
+            reveal_deadline: env.block.time.plus_seconds(200),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            create_trade(Some(auction.clone())),
+        )
+        .unwrap();
+        let trade_state: GetContractStateResponse =
+            from_json(query(deps.as_ref(), env, GetContractState { trade_id: Some(1) }).unwrap())
+                .unwrap();
+        assert_eq!(Some(auction), trade_state.config.auction);
+    }
+
+    #[test]
+    fn create_trade_reads_shared_config() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let trade_dealer_address = deps.api.addr_make("trade-dealer-address");
+        let shared_seller_address = deps.api.addr_make("shared-seller");
+        let added_seller_address = deps.api.addr_make("added-seller");
+        let trade_seller_address = deps.api.addr_make("trade-seller");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &shared_seller_address).unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        let admin_info = MessageInfo {
+            sender: contract_admin.clone(),
+            funds: vec![],
+        };
+        let create_trade =
+            |dealers: Option<Vec<String>>, allowed_sellers: Option<Vec<String>>| CreateTrade {
+                use_private_sellers: None,
+                use_private_buyers: None,
+                allowed_sellers,
+                allowed_buyers: None,
+                max_bid_count: None,
+                dealers,
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: None,
+            };
+        let trade_state = |deps: &OwnedDeps<_, _, _>, trade_id: Option<u64>| {
+            from_json::<GetContractStateResponse>(
+                query(deps.as_ref(), mock_env(), GetContractState { trade_id }).unwrap(),
+            )
+            .unwrap()
+        };
+
+        // The first trade only overrides the dealers, while the second has its own allowed sellers
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            create_trade(Some(vec![trade_dealer_address.to_string()]), None),
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            create_trade(None, Some(vec![trade_seller_address.to_string()])),
+        )
+        .unwrap();
+
+        // Updates to the shared configuration reach the trades that have not overridden them
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            UpdateMaxBidCount {
+                max_bid_count: Some(7),
+                trade_id: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            AddAllowedSellers {
+                allowed_sellers: vec![added_seller_address.to_string()],
+                trade_id: None,
+            },
+        )
+        .unwrap();
+
+        let first_trade = trade_state(&deps, Some(1));
+        assert_eq!(Some(7), first_trade.config.max_bid_count);
+        assert_eq!(vec![trade_dealer_address], first_trade.config.dealers);
+        assert_eq!(2, first_trade.allowed_sellers.len());
+        assert!(first_trade.allowed_sellers.contains(&shared_seller_address));
+        assert!(first_trade.allowed_sellers.contains(&added_seller_address));
+        let second_trade = trade_state(&deps, Some(2));
+        assert_eq!(Some(7), second_trade.config.max_bid_count);
+        assert_eq!(vec![dealer_address.clone()], second_trade.config.dealers);
+        assert_eq!(
+            vec![trade_seller_address.clone()],
+            second_trade.allowed_sellers
+        );
+
+        // An update made within a trade becomes an override of that trade only
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            UpdateMaxBidCount {
+                max_bid_count: Some(2),
+                trade_id: Some(1),
+            },
+        )
+        .unwrap();
+        assert_eq!(Some(2), trade_state(&deps, Some(1)).config.max_bid_count);
+        assert_eq!(Some(7), trade_state(&deps, None).config.max_bid_count);
+        assert_eq!(Some(7), trade_state(&deps, Some(2)).config.max_bid_count);
+
+        // The creation of the trade is the first entry in its own history
+        let history: GetHistoryResponse = from_json(
+            query(
+                deps.as_ref(),
+                env,
+                GetHistory {
+                    trade_id: Some(2),
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(1, history.entries.len());
+        assert_eq!("CreateTrade", history.entries[0].action);
+        assert_eq!(contract_admin, history.entries[0].actor);
+        let fields: Vec<_> = history.entries[0]
+            .changes
+            .iter()
+            .map(|change| (change.field.as_str(), change.key.clone()))
+            .collect();
+        assert!(fields.contains(&("trade_overrides", None)));
+        assert!(fields.contains(&("allowed_sellers", Some(trade_seller_address.to_string()))));
+    }
+}
@@ -2,18 +2,21 @@
 mod execute_dealer_confirm_tests {
     use crate::contract::execute;
     use crate::error::ContractError;
+    use crate::msg::AcceptedOffer;
     use crate::storage::state_store::{
-        retrieve_optional_settlement_data_state, save_bid_list_state, save_buyer_state,
-        save_contract_config, save_seller_state, save_settlement_data_state, save_token_data_state,
-        Bid, BidList, Buyer, Config, Seller, SettlementData, TokenData,
+        retrieve_optional_settlement_data_state, save_allowed_seller_state, save_bid_list_state,
+        save_buyer_state, save_contract_config, save_margin_account_state, save_seller_state,
+        save_settlement_data_state, save_token_data_state, Bid, BidList, Buyer, Config,
+        MarginAccount, MarginConfig, PoolAsset, Seller, SettlementData, TokenData,
     };
     use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coin, CosmosMsg, Uint128};
     use cosmwasm_std::{to_json_binary, Binary, ContractResult, MessageInfo, SystemResult};
-    use cosmwasm_std::{CosmosMsg, Uint128};
     use provwasm_mocks::mock_provenance_dependencies;
     use provwasm_std::shim::Any;
     use provwasm_std::types::cosmos::auth::v1beta1::BaseAccount;
     use provwasm_std::types::cosmos::base::v1beta1::Coin;
+    use rust_decimal::Decimal;
 
     use provwasm_std::types::provenance::marker::v1::{
         AccessGrant, Balance, MarkerAccount, MarkerStatus, MarkerType, MsgTransferRequest,
@@ -42,23 +45,34 @@ mod execute_dealer_confirm_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract_admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
 
-        let pool_denoms = vec![pool_denom.into()];
+        let pools = vec![PoolAsset::Marker {
+            denom: pool_denom.into(),
+        }];
         save_bid_list_state(
             &mut deps.storage,
             &BidList {
                 bids: vec![Bid {
                     buyer_address: buyer_address.clone(),
                     agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -68,8 +82,12 @@ mod execute_dealer_confirm_tests {
             &mut deps.storage,
             &Seller {
                 seller_address: seller_address.clone(),
-                pool_denoms,
+                pools,
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -80,6 +98,7 @@ mod execute_dealer_confirm_tests {
                 buyer_address: buyer_address.clone(),
                 buyer_has_accepted_pools: true,
                 agreement_terms_hash: "".to_string(),
+                token_amount: Uint128::new(10),
             },
         )
         .unwrap();
@@ -105,7 +124,7 @@ mod execute_dealer_confirm_tests {
                 }),
                 manager: "".to_string(),
                 access_control: vec![AccessGrant {
-                    address: "".to_string(),
+                    address: mock_env().contract.address.to_string(),
                     permissions: vec![1, 2, 3, 4, 5, 6, 7],
                 }],
                 status: MarkerStatus::Active.into(),
@@ -129,14 +148,14 @@ mod execute_dealer_confirm_tests {
             SystemResult::Ok(ContractResult::Ok(binary))
         });
 
+        // The finalized pools are held in escrow by the contract
         let cb_holding = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
-            let inner_deps = mock_provenance_dependencies();
             let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
 
             let response = if message.id == "test.token.asset.pool.0" {
                 QueryHoldingResponse {
                     balances: vec![Balance {
-                        address: inner_deps.api.addr_make("seller_address").to_string(),
+                        address: mock_env().contract.address.to_string(),
                         coins: vec![Coin {
                             denom: "test.token.asset.pool.0".to_string(),
                             amount: "1".to_string(),
@@ -164,7 +183,7 @@ mod execute_dealer_confirm_tests {
             deps.as_mut(),
             env.clone(),
             info,
-            crate::msg::ExecuteMsg::DealerConfirm {},
+            crate::msg::ExecuteMsg::DealerConfirm { trade_id: None },
         ) {
             Ok(response) => {
                 assert_eq!(
@@ -200,6 +219,626 @@ mod execute_dealer_confirm_tests {
         }
     }
 
+    #[test]
+    fn execute_dealer_confirm_waits_for_every_buyer() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let buyer_address = deps.api.addr_make("contract_buyer");
+        let pending_buyer_address = deps.api.addr_make("contract_buyer_pending");
+        let pool_denom = "test.token.asset.pool.0";
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract_admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
+
+        let pools = vec![PoolAsset::Marker {
+            denom: pool_denom.into(),
+        }];
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: vec![Bid {
+                    buyer_address: buyer_address.clone(),
+                    agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
+                }],
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools,
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        // Only one of the two accepted buyers has accepted the finalized pools
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: true,
+                agreement_terms_hash: "".to_string(),
+                token_amount: Uint128::new(6),
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: pending_buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "".to_string(),
+                token_amount: Uint128::new(4),
+            },
+        )
+        .unwrap();
+
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+            let inner_deps = mock_provenance_dependencies();
+            let expected_marker = MarkerAccount {
+                base_account: Some(BaseAccount {
+                    address: inner_deps.api.addr_make("base_addr").to_string(),
+                    pub_key: None,
+                    account_number: 1,
+                    sequence: 0,
+                }),
+                manager: "".to_string(),
+                access_control: vec![AccessGrant {
+                    address: mock_env().contract.address.to_string(),
+                    permissions: vec![1, 2, 3, 4, 5, 6, 7],
+                }],
+                status: MarkerStatus::Active.into(),
+                denom: message.id.to_string(),
+                supply: "1".to_string(),
+                marker_type: MarkerType::Coin.into(),
+                supply_fixed: false,
+                allow_governance_control: false,
+                allow_forced_transfer: false,
+                required_attributes: vec![],
+            };
+
+            let response = QueryMarkerResponse {
+                marker: Some(Any {
+                    type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                    value: expected_marker.to_proto_bytes(),
+                }),
+            };
+
+            let binary = to_json_binary(&response).unwrap();
+            SystemResult::Ok(ContractResult::Ok(binary))
+        });
+
+        // The finalized pools are held in escrow by the contract
+        let cb_holding = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
+
+            let response = if message.id == "test.token.asset.pool.0" {
+                QueryHoldingResponse {
+                    balances: vec![Balance {
+                        address: mock_env().contract.address.to_string(),
+                        coins: vec![Coin {
+                            denom: "test.token.asset.pool.0".to_string(),
+                            amount: "1".to_string(),
+                        }],
+                    }],
+                    pagination: None,
+                }
+            } else {
+                panic!("unexpected query for denom")
+            };
+
+            let binary = to_json_binary(&response).unwrap();
+            SystemResult::Ok(ContractResult::Ok(binary))
+        });
+        deps.querier.registered_custom_queries.insert(
+            "/provenance.marker.v1.Query/Holding".to_string(),
+            cb_holding,
+        );
+
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb);
+
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            DealerConfirm { trade_id: None },
+        ) {
+            Ok(_) => {
+                panic!(
+                    "failed to detect a dealer confirmation before every buyer accepted the pools"
+                )
+            }
+            Err(error) => match error {
+                ContractError::InvalidConfirmationRequest => {}
+                _ => {
+                    panic!(
+                        "an unexpected error was returned when confirming before every buyer \
+                            accepted the pools: {:?}",
+                        error
+                    )
+                }
+            },
+        }
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            MessageInfo {
+                sender: pending_buyer_address,
+                funds: vec![],
+            },
+            AcceptFinalizedPools {
+                offers: vec![AcceptedOffer {
+                    seller_address: seller_address.to_string(),
+                    offer_hash: "mock-offer-hash".to_string(),
+                    offer_revision: 1,
+                }],
+                trade_id: None,
+            },
+        )
+        .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            DealerConfirm { trade_id: None },
+        ) {
+            Ok(_) => {
+                assert_eq!(
+                    retrieve_optional_settlement_data_state(&deps.storage)
+                        .unwrap()
+                        .unwrap()
+                        .settling_dealer,
+                    dealer_address
+                )
+            }
+            Err(error) => {
+                panic!(
+                    "failed to confirm once every buyer accepted the pools: {:?}",
+                    error
+                )
+            }
+        }
+    }
+
+    #[test]
+    fn execute_dealer_confirm_with_outstanding_margin() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract_admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: Some(MarginConfig {
+                    denom: "nhash".to_string(),
+                    maintenance_margin: Decimal::ONE,
+                    margin_call_window: 3600,
+                }),
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Native {
+                    coin: coin(5, "ibc/pool"),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: true,
+                agreement_terms_hash: "".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        // The seller has an open margin call and the buyer owes variation margin it could not pay, so
+        // neither account can be paid out at settlement until it is settled
+        let outstanding_accounts = [
+            MarginAccount {
+                owner: seller_address.clone(),
+                balance: Decimal::from(5),
+                margin_call_deadline: Some(env.block.time.plus_seconds(3600)),
+                owed: Decimal::ZERO,
+            },
+            MarginAccount {
+                owner: buyer_address.clone(),
+                balance: Decimal::from(20),
+                margin_call_deadline: None,
+                owed: Decimal::from(3),
+            },
+        ];
+        for margin_account in outstanding_accounts {
+            let owner = margin_account.owner.clone();
+            save_margin_account_state(&mut deps.storage, &margin_account).unwrap();
+            match execute(
+                deps.as_mut(),
+                env.clone(),
+                info.clone(),
+                DealerConfirm { trade_id: None },
+            ) {
+                Ok(_) => {
+                    panic!("failed to detect a dealer confirmation with outstanding margin")
+                }
+                Err(error) => match error {
+                    ContractError::OutstandingMargin { address } => {
+                        assert_eq!(address, owner.to_string())
+                    }
+                    _ => {
+                        panic!(
+                            "an unexpected error was returned when confirming with outstanding \
+                                margin: {:?}",
+                            error
+                        )
+                    }
+                },
+            }
+            save_margin_account_state(
+                &mut deps.storage,
+                &MarginAccount {
+                    owner,
+                    balance: Decimal::from(20),
+                    margin_call_deadline: None,
+                    owed: Decimal::ZERO,
+                },
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn execute_dealer_confirm_with_pool_outside_escrow() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let buyer_address = deps.api.addr_make("contract_buyer");
+        let pool_denom = "test.token.asset.pool.0";
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract_admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
+
+        let pools = vec![PoolAsset::Marker {
+            denom: pool_denom.into(),
+        }];
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: vec![Bid {
+                    buyer_address: buyer_address.clone(),
+                    agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
+                }],
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools,
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: true,
+                agreement_terms_hash: "".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+            let inner_deps = mock_provenance_dependencies();
+            let expected_marker = MarkerAccount {
+                base_account: Some(BaseAccount {
+                    address: inner_deps.api.addr_make("base_addr").to_string(),
+                    pub_key: None,
+                    account_number: 1,
+                    sequence: 0,
+                }),
+                manager: "".to_string(),
+                access_control: vec![AccessGrant {
+                    address: mock_env().contract.address.to_string(),
+                    permissions: vec![1, 2, 3, 4, 5, 6, 7],
+                }],
+                status: MarkerStatus::Active.into(),
+                denom: message.id.to_string(),
+                supply: "1".to_string(),
+                marker_type: MarkerType::Coin.into(),
+                supply_fixed: false,
+                allow_governance_control: false,
+                allow_forced_transfer: false,
+                required_attributes: vec![],
+            };
+
+            let response = QueryMarkerResponse {
+                marker: Some(Any {
+                    type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                    value: expected_marker.to_proto_bytes(),
+                }),
+            };
+
+            let binary = to_json_binary(&response).unwrap();
+            SystemResult::Ok(ContractResult::Ok(binary))
+        });
+
+        // The finalized pool has left the contract's escrow
+        let cb_holding = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let inner_deps = mock_provenance_dependencies();
+            let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
+
+            let response = if message.id == "test.token.asset.pool.0" {
+                QueryHoldingResponse {
+                    balances: vec![Balance {
+                        address: inner_deps.api.addr_make("allowed-seller-0").to_string(),
+                        coins: vec![Coin {
+                            denom: "test.token.asset.pool.0".to_string(),
+                            amount: "1".to_string(),
+                        }],
+                    }],
+                    pagination: None,
+                }
+            } else {
+                panic!("unexpected query for denom")
+            };
+
+            let binary = to_json_binary(&response).unwrap();
+            SystemResult::Ok(ContractResult::Ok(binary))
+        });
+        deps.querier.registered_custom_queries.insert(
+            "/provenance.marker.v1.Query/Holding".to_string(),
+            cb_holding,
+        );
+
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb);
+
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            crate::msg::ExecuteMsg::DealerConfirm { trade_id: None },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a finalized pool that is not held in escrow")
+            }
+            Err(error) => match error {
+                ContractError::EscrowMismatch { denom, holder } => {
+                    assert_eq!(denom, pool_denom);
+                    assert_eq!(holder, deps.api.addr_make("allowed-seller-0").to_string());
+                    assert!(retrieve_optional_settlement_data_state(&deps.storage)
+                        .unwrap()
+                        .is_none());
+                }
+                _ => {
+                    panic!("unexpected error when confirming a pool that is not held in escrow")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn execute_dealer_confirm_with_native_pool_shortfall() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let buyer_address = deps.api.addr_make("contract_buyer");
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract_admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Native {
+                    coin: coin(5, "ibc/pool"),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: true,
+                agreement_terms_hash: "".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        // Part of the deposited pool has left the contract's bank balance
+        deps.querier
+            .mock_querier
+            .bank
+            .update_balance(mock_env().contract.address, vec![coin(3, "ibc/pool")]);
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            DealerConfirm { trade_id: None },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a native pool that is not fully held in escrow")
+            }
+            Err(error) => match error {
+                ContractError::UnreconciledEscrow { denom } => {
+                    assert_eq!(denom, "ibc/pool");
+                    assert!(retrieve_optional_settlement_data_state(&deps.storage)
+                        .unwrap()
+                        .is_none());
+                }
+                _ => {
+                    panic!("unexpected error when confirming a native pool shortfall")
+                }
+            },
+        }
+    }
+
     #[test]
     fn execute_seller_confirm_invalid_seller() {
         let mut deps = mock_provenance_dependencies();
@@ -217,23 +856,35 @@ mod execute_dealer_confirm_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![deps.api.addr_make("different-seller")],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &deps.api.addr_make("different-seller"))
+            .unwrap();
 
-        let pool_denoms = vec![pool_denom.into()];
+        let pools = vec![PoolAsset::Marker {
+            denom: pool_denom.into(),
+        }];
         save_bid_list_state(
             &mut deps.storage,
             &BidList {
                 bids: vec![Bid {
                     buyer_address: buyer_address.clone(),
                     agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -243,8 +894,12 @@ mod execute_dealer_confirm_tests {
             &mut deps.storage,
             &Seller {
                 seller_address: seller_address.clone(),
-                pool_denoms,
+                pools,
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -255,6 +910,7 @@ mod execute_dealer_confirm_tests {
                 buyer_address: buyer_address.clone(),
                 buyer_has_accepted_pools: true,
                 agreement_terms_hash: "".to_string(),
+                token_amount: Uint128::new(10),
             },
         )
         .unwrap();
@@ -272,7 +928,7 @@ mod execute_dealer_confirm_tests {
             deps.as_mut(),
             env.clone(),
             info,
-            crate::msg::ExecuteMsg::DealerConfirm {},
+            crate::msg::ExecuteMsg::DealerConfirm { trade_id: None },
         ) {
             Ok(_) => {
                 panic!(
@@ -309,23 +965,35 @@ mod execute_dealer_confirm_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![deps.api.addr_make("different_seller")],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 5,
+                max_bid_count: Some(5),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &deps.api.addr_make("different_seller"))
+            .unwrap();
 
-        let pool_denoms = vec![pool_denom.into()];
+        let pools = vec![PoolAsset::Marker {
+            denom: pool_denom.into(),
+        }];
         save_bid_list_state(
             &mut deps.storage,
             &BidList {
                 bids: vec![Bid {
                     buyer_address: buyer_address.clone(),
                     agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -335,12 +1003,21 @@ mod execute_dealer_confirm_tests {
             &mut deps.storage,
             &Seller {
                 seller_address: seller_address.clone(),
-                pool_denoms,
+                pools,
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
-        match execute(deps.as_mut(), env.clone(), info, DealerConfirm {}) {
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            DealerConfirm { trade_id: None },
+        ) {
             Ok(_) => {
                 panic!(
                     "failed to return an error when an unauthorized seller attempted to confirm the contract"
@@ -371,12 +1048,17 @@ mod execute_dealer_confirm_tests {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 50,
+                max_bid_count: Some(50),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
@@ -386,6 +1068,9 @@ mod execute_dealer_confirm_tests {
                 bids: vec![Bid {
                     buyer_address: deps.api.addr_make("buyer-address"),
                     agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -400,21 +1085,25 @@ mod execute_dealer_confirm_tests {
         .unwrap();
 
         [
-            ContractDisable {},
+            ContractDisable { trade_id: None },
             AddSeller {
                 offer_hash: "mock-offer-hash".to_string(),
+                trade_id: None,
             },
             FinalizePools {
                 pool_denoms: vec![],
+                trade_id: None,
             },
-            DealerConfirm {},
+            DealerConfirm { trade_id: None },
             UpdateAllowedSellers {
                 allowed_sellers: vec![],
+                trade_id: None,
             },
             AcceptFinalizedPools {
-                offer_hash: "".to_string(),
+                offers: vec![],
+                trade_id: None,
             },
-            RescindFinalizedPools {},
+            RescindFinalizedPools { trade_id: None },
         ]
         .into_iter()
         .for_each(|command| -> () {
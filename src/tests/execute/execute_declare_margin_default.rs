@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod execute_declare_margin_default_tests {
+    use crate::contract::execute;
+    use crate::msg::ExecuteMsg::DeclareMarginDefault;
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_buyer_state, save_contract_config, save_margin_account_state,
+        save_seller_state, save_token_data_state, BidList, Buyer, Config, MarginAccount,
+        MarginConfig, PoolAsset, Seller, TokenData,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coin, coins, Attribute, BankMsg, CosmosMsg, MessageInfo, Uint128};
+    use provwasm_mocks::mock_provenance_dependencies;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn declare_default_after_missed_margin_call() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: Some(MarginConfig {
+                    denom: "nhash".to_string(),
+                    maintenance_margin: Decimal::ONE,
+                    margin_call_window: 3600,
+                }),
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Native {
+                    coin: coin(5, "ibc/pool"),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_margin_account_state(
+            &mut deps.storage,
+            &MarginAccount {
+                owner: seller_address.clone(),
+                balance: Decimal::from(5),
+                margin_call_deadline: Some(env.block.time.minus_seconds(1)),
+                owed: Decimal::ZERO,
+            },
+        )
+        .unwrap();
+        save_margin_account_state(
+            &mut deps.storage,
+            &MarginAccount {
+                owner: buyer_address.clone(),
+                balance: Decimal::from(35),
+                margin_call_deadline: None,
+                owed: Decimal::ZERO,
+            },
+        )
+        .unwrap();
+
+        let default_message = DeclareMarginDefault {
+            address: seller_address.to_string(),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, default_message) {
+            Ok(response) => {
+                // The seller's pool is returned as the contract is unwound
+                assert_eq!(
+                    response.messages[0].msg,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: seller_address.to_string(),
+                        amount: coins(5, "ibc/pool"),
+                    })
+                );
+                assert_eq!(
+                    response.messages[1].msg,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: buyer_address.to_string(),
+                        amount: coins(40, "nhash"),
+                    })
+                );
+                assert_eq!(response.messages.len(), 2);
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert!(contract_state.config.is_disabled);
+                assert!(contract_state.margin_accounts.is_empty());
+            }
+            Err(error) => {
+                panic!("failed to declare a margin default: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn declare_default_without_margin_deposit() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: Some(MarginConfig {
+                    denom: "nhash".to_string(),
+                    maintenance_margin: Decimal::ONE,
+                    margin_call_window: 3600,
+                }),
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_margin_account_state(
+            &mut deps.storage,
+            &MarginAccount {
+                owner: seller_address.clone(),
+                balance: Decimal::ZERO,
+                margin_call_deadline: Some(env.block.time.minus_seconds(1)),
+                owed: Decimal::from(15),
+            },
+        )
+        .unwrap();
+        save_margin_account_state(
+            &mut deps.storage,
+            &MarginAccount {
+                owner: buyer_address.clone(),
+                balance: Decimal::from(20),
+                margin_call_deadline: None,
+                owed: Decimal::ZERO,
+            },
+        )
+        .unwrap();
+
+        let default_message = DeclareMarginDefault {
+            address: seller_address.to_string(),
+            trade_id: None,
+        };
+        // The seller never deposited, so the buyer is only paid back their own margin and the
+        // variation margin the seller owes is reported as unpaid
+        match execute(deps.as_mut(), env, info, default_message) {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages[0].msg,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: buyer_address.to_string(),
+                        amount: coins(20, "nhash"),
+                    })
+                );
+                assert_eq!(response.messages.len(), 1);
+                assert!(response
+                    .attributes
+                    .contains(&Attribute::new("unpaid_margin", "15")));
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert!(contract_state.config.is_disabled);
+                assert!(contract_state.margin_accounts.is_empty());
+            }
+            Err(error) => {
+                panic!("failed to declare a margin default: {:?}", error)
+            }
+        }
+    }
+}
@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod execute_deposit_fees_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{DealerConfirm, DepositFees};
+    use crate::msg::SettlementFee;
+    use crate::query::settlement_fees::query_settlement_fees;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_buyer_state, save_contract_config, save_seller_state,
+        save_token_data_state, BidList, Buyer, Config, Fee, FeeConfig, PoolAsset, Seller,
+        TokenData,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+    use cosmwasm_std::{coins, Addr, MessageInfo, OwnedDeps, Uint128};
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+
+    fn setup_fee_contract(
+        deps: &mut OwnedDeps<MockStorage, MockApi, MockProvenanceQuerier>,
+        dealer_address: &Addr,
+        seller_address: &Addr,
+        buyer_address: &Addr,
+    ) {
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: Some(FeeConfig {
+                    denom: "nhash".to_string(),
+                    is_restricted_marker: false,
+                    buyer_fee: Some(Fee::BasisPoints { bps: 250 }),
+                    seller_fee: Some(Fee::Flat {
+                        amount: Uint128::new(7),
+                    }),
+                    platform_address: Some(deps.api.addr_make("platform-address")),
+                    platform_share_bps: 2_000,
+                }),
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool.0".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: true,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(1_000),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(1_000),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn deposit_fees_and_query_settlement_fees() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let info = MessageInfo {
+            sender: buyer_address.clone(),
+            funds: coins(30, "nhash"),
+        };
+        let env = mock_env();
+        setup_fee_contract(&mut deps, &dealer_address, &seller_address, &buyer_address);
+
+        let deposit_message = DepositFees {
+            amount: Uint128::new(30),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, deposit_message) {
+            Ok(_) => {
+                let settlement_fees = query_settlement_fees(deps.as_ref()).unwrap();
+                assert_eq!(settlement_fees.denom, Some("nhash".to_string()));
+                assert_eq!(
+                    settlement_fees.fees,
+                    vec![
+                        SettlementFee {
+                            payer: buyer_address,
+                            amount: Uint128::new(25),
+                            deposited: Uint128::new(30),
+                        },
+                        SettlementFee {
+                            payer: seller_address,
+                            amount: Uint128::new(7),
+                            deposited: Uint128::zero(),
+                        },
+                    ]
+                );
+            }
+            Err(error) => {
+                panic!("failed to deposit fees: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn dealer_confirm_with_insufficient_fee_deposit() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let env = mock_env();
+        setup_fee_contract(&mut deps, &dealer_address, &seller_address, &buyer_address);
+
+        let deposit_info = MessageInfo {
+            sender: buyer_address.clone(),
+            funds: coins(25, "nhash"),
+        };
+        let deposit_message = DepositFees {
+            amount: Uint128::new(25),
+            trade_id: None,
+        };
+        execute(deps.as_mut(), env.clone(), deposit_info, deposit_message).unwrap();
+
+        let info = MessageInfo {
+            sender: dealer_address,
+            funds: vec![],
+        };
+        match execute(deps.as_mut(), env, info, DealerConfirm { trade_id: None }) {
+            Ok(_) => {
+                panic!("failed to detect a seller that has not deposited their fee")
+            }
+            Err(error) => match error {
+                ContractError::InsufficientFeeDeposit { address, required } => {
+                    assert_eq!(address, seller_address.to_string());
+                    assert_eq!(required, Uint128::new(7));
+                }
+                _ => {
+                    panic!("an unexpected error was returned when a fee deposit was missing")
+                }
+            },
+        }
+    }
+}
@@ -8,18 +8,57 @@ mod execute_disable_contract_tests {
     };
     use crate::query::contract_state::query_contract_state;
     use crate::storage::state_store::{
-        retrieve_contract_config, save_bid_list_state, save_contract_config, save_seller_state,
-        Bid, BidList, Config, Seller,
+        retrieve_contract_config, retrieve_seller_state, save_allowed_seller_state,
+        save_bid_list_state, save_contract_config, save_seller_state, Bid, BidList, Config,
+        PoolAsset, Seller,
     };
     use crate::version_info::{set_version_info, VersionInfoV1};
     use cosmwasm_std::testing::mock_env;
-    use cosmwasm_std::{to_json_binary, Binary, ContractResult, MessageInfo, SystemResult};
-    use provwasm_mocks::mock_provenance_dependencies;
+    use cosmwasm_std::{
+        coins, to_json_binary, BankMsg, Binary, ContractResult, MessageInfo, SubMsg, SystemResult,
+    };
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+    use provwasm_std::shim::Any;
     use provwasm_std::types::cosmos::base::v1beta1::Coin;
     use provwasm_std::types::provenance::marker::v1::{
-        Balance, QueryHoldingRequest, QueryHoldingResponse,
+        Access, AccessGrant, Balance, MarkerAccount, MarkerStatus, MarkerType, QueryHoldingRequest,
+        QueryHoldingResponse, QueryMarkerRequest, QueryMarkerResponse,
     };
 
+    fn register_pool_marker(querier: &mut MockProvenanceQuerier, permissions: Vec<i32>) {
+        let cb_marker = Box::new(
+            move |bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+                let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+                let marker = MarkerAccount {
+                    base_account: None,
+                    manager: "".to_string(),
+                    access_control: vec![AccessGrant {
+                        address: mock_env().contract.address.to_string(),
+                        permissions: permissions.clone(),
+                    }],
+                    status: MarkerStatus::Active.into(),
+                    denom: message.id.to_string(),
+                    supply: "1".to_string(),
+                    marker_type: MarkerType::Restricted.into(),
+                    supply_fixed: true,
+                    allow_governance_control: false,
+                    allow_forced_transfer: false,
+                    required_attributes: vec![],
+                };
+                let response = QueryMarkerResponse {
+                    marker: Some(Any {
+                        type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                        value: marker.to_proto_bytes(),
+                    }),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+            },
+        );
+        querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb_marker);
+    }
+
     #[test]
     fn execute_disable_contract() {
         let mut deps = mock_provenance_dependencies();
@@ -30,12 +69,17 @@ mod execute_disable_contract_tests {
         let config = Config {
             use_private_sellers: true,
             use_private_buyers: false,
-            allowed_sellers: vec![allowed_seller_address.clone()],
-            allowed_buyers: vec![],
             dealers: vec![deps.api.addr_make("dealer-address")],
             is_disabled: false,
-            max_bid_count: 1,
+            max_bid_count: Some(1),
             contract_admin: contract_admin.clone(),
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
         };
 
         let info = MessageInfo {
@@ -44,8 +88,14 @@ mod execute_disable_contract_tests {
         };
 
         save_contract_config(&mut deps.storage, &config).unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_address).unwrap();
 
-        match execute(deps.as_mut(), env.clone(), info, ContractDisable {}) {
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ContractDisable { trade_id: None },
+        ) {
             Ok(_) => {
                 let config_from_contract = retrieve_contract_config(&deps.storage).unwrap();
                 let mut expected_config = config.clone();
@@ -70,16 +120,27 @@ mod execute_disable_contract_tests {
         let config = Config {
             use_private_sellers: true,
             use_private_buyers: false,
-            allowed_sellers: vec![allowed_seller_address.clone()],
-            allowed_buyers: vec![],
             dealers: vec![deps.api.addr_make("dealer-address")],
             is_disabled: false,
-            max_bid_count: 1,
+            max_bid_count: Some(1),
             contract_admin: deps.api.addr_make("contract-admin"),
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
         };
         save_contract_config(&mut deps.storage, &config).unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_address).unwrap();
 
-        match execute(deps.as_mut(), env.clone(), info, ContractDisable {}) {
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ContractDisable { trade_id: None },
+        ) {
             Ok(_) => {
                 panic!(
                     "Failed to detect error when disabling the contract as neither the buyer \
@@ -112,21 +173,33 @@ mod execute_disable_contract_tests {
         let config = Config {
             use_private_sellers: true,
             use_private_buyers: false,
-            allowed_sellers: vec![allowed_seller_address.clone()],
-            allowed_buyers: vec![],
             dealers: vec![deps.api.addr_make("dealer-address")],
             is_disabled: false,
-            max_bid_count: 5,
+            max_bid_count: Some(5),
             contract_admin: contract_admin_address.clone(),
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
         };
         save_contract_config(&mut deps.storage, &config).unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_address).unwrap();
 
         save_seller_state(
             &mut deps.storage,
             &Seller {
                 seller_address: allowed_seller_address.clone(),
-                pool_denoms: vec!["test.denom.pool.0".to_string()],
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool.0".to_string(),
+                }],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -146,6 +219,9 @@ mod execute_disable_contract_tests {
                 bids: vec![Bid {
                     buyer_address: buyer_address.clone(),
                     agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
                 }],
             },
         )
@@ -154,11 +230,10 @@ mod execute_disable_contract_tests {
         let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
             let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
 
-            let response = if message.id == "test.denom.pool.0".to_string() {
-                let inner_deps = mock_provenance_dependencies();
+            let response = if message.id == "test.denom.pool.0" {
                 QueryHoldingResponse {
                     balances: vec![Balance {
-                        address: inner_deps.api.addr_make("allowed-seller").to_string(),
+                        address: mock_env().contract.address.to_string(),
                         coins: vec![Coin {
                             denom: "test.token.asset.pool.0".to_string(),
                             amount: "1".to_string(),
@@ -177,16 +252,50 @@ mod execute_disable_contract_tests {
             .registered_custom_queries
             .insert("/provenance.marker.v1.Query/Holding".to_string(), cb);
 
-        match execute(deps.as_mut(), env.clone(), info, ContractDisable {}) {
+        // The seller revoked the contract's transfer access after finalizing, so the pool cannot be
+        // returned and the contract stays enabled
+        register_pool_marker(&mut deps.querier, vec![Access::Withdraw as i32]);
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info.clone(),
+            ContractDisable { trade_id: None },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a pool marker without transfer access")
+            }
+            Err(error) => match error {
+                ContractError::MissingMarkerAccess { denom, access } => {
+                    assert_eq!(denom, "test.denom.pool.0");
+                    assert_eq!(access, "ACCESS_TRANSFER");
+                    assert!(!retrieve_contract_config(&deps.storage).unwrap().is_disabled);
+                }
+                _ => {
+                    panic!("unexpected error when disabling without transfer access: {error:?}")
+                }
+            },
+        }
+
+        register_pool_marker(&mut deps.querier, vec![Access::Transfer as i32]);
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ContractDisable { trade_id: None },
+        ) {
             Ok(_) => {
                 let expected_seller_state = Seller {
                     seller_address: allowed_seller_address.clone(),
-                    pool_denoms: vec![],
+                    pools: vec![],
                     offer_hash: "mock-offer-hash".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
                 };
                 assert_eq!(
-                    query_contract_state(deps.as_ref()).unwrap().seller.unwrap(),
-                    expected_seller_state
+                    query_contract_state(deps.as_ref()).unwrap().sellers,
+                    vec![expected_seller_state]
                 );
             }
             Err(error) => {
@@ -195,6 +304,194 @@ mod execute_disable_contract_tests {
         }
     }
 
+    #[test]
+    fn execute_disable_contract_with_pool_outside_escrow() {
+        let mut deps = mock_provenance_dependencies();
+        let allowed_seller_address = deps.api.addr_make("allowed-seller");
+        let contract_admin_address = deps.api.addr_make("contract-admin");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let info = MessageInfo {
+            sender: contract_admin_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+        let config = Config {
+            use_private_sellers: true,
+            use_private_buyers: false,
+            dealers: vec![deps.api.addr_make("dealer-address")],
+            is_disabled: false,
+            max_bid_count: Some(5),
+            contract_admin: contract_admin_address.clone(),
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
+        };
+        save_contract_config(&mut deps.storage, &config).unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_address).unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: allowed_seller_address.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool.0".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(
+            &mut deps.storage,
+            &BidList {
+                bids: vec![Bid {
+                    buyer_address: buyer_address.clone(),
+                    agreement_terms_hash: "".to_string(),
+                    price: None,
+                    submitted_at: mock_env().block.time,
+                    revision: 0,
+                }],
+            },
+        )
+        .unwrap();
+
+        let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
+
+            let response = if message.id == "test.denom.pool.0" {
+                let inner_deps = mock_provenance_dependencies();
+                QueryHoldingResponse {
+                    balances: vec![Balance {
+                        address: inner_deps.api.addr_make("allowed-seller").to_string(),
+                        coins: vec![Coin {
+                            denom: "test.token.asset.pool.0".to_string(),
+                            amount: "1".to_string(),
+                        }],
+                    }],
+                    pagination: None,
+                }
+            } else {
+                panic!("unexpected query for denom")
+            };
+
+            let binary = to_json_binary(&response).unwrap();
+            SystemResult::Ok(ContractResult::Ok(binary))
+        });
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Holding".to_string(), cb);
+        register_pool_marker(&mut deps.querier, vec![Access::Transfer as i32]);
+
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            info,
+            ContractDisable { trade_id: None },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a finalized pool that is not held in escrow")
+            }
+            Err(error) => match error {
+                ContractError::EscrowMismatch { denom, holder } => {
+                    assert_eq!(denom, "test.denom.pool.0");
+                    assert_eq!(holder, allowed_seller_address.to_string());
+                    assert!(!retrieve_contract_config(&deps.storage).unwrap().is_disabled);
+                }
+                _ => {
+                    panic!("unexpected error when disabling with a pool that is not held in escrow")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn execute_disable_contract_returns_native_pool() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let allowed_seller_address = deps.api.addr_make("allowed-seller");
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(5),
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_address).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: allowed_seller_address.clone(),
+                pools: vec![PoolAsset::Native {
+                    coin: cosmwasm_std::Coin::new(5u128, "ibc/pool"),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: contract_admin.clone(),
+                funds: vec![],
+            },
+            ContractDisable { trade_id: None },
+        ) {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages,
+                    vec![SubMsg::new(BankMsg::Send {
+                        to_address: allowed_seller_address.to_string(),
+                        amount: coins(5, "ibc/pool"),
+                    })]
+                );
+                assert!(
+                    retrieve_seller_state(&deps.storage, &allowed_seller_address)
+                        .unwrap()
+                        .pools
+                        .is_empty()
+                );
+            }
+            Err(error) => {
+                panic!("failed to return a native pool when disabling: {:?}", error)
+            }
+        }
+    }
+
     #[test]
     fn disallow_all_executions_when_disabled() {
         let mut deps = mock_provenance_dependencies();
@@ -208,31 +505,40 @@ mod execute_disable_contract_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: true,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
         [
-            ContractDisable {},
+            ContractDisable { trade_id: None },
             AddSeller {
                 offer_hash: "mock-offer-hash".to_string(),
+                trade_id: None,
             },
             FinalizePools {
                 pool_denoms: vec![],
+                trade_id: None,
             },
-            DealerConfirm {},
+            DealerConfirm { trade_id: None },
             UpdateAllowedSellers {
                 allowed_sellers: vec![],
+                trade_id: None,
             },
             AcceptFinalizedPools {
-                offer_hash: "".to_string(),
+                offers: vec![],
+                trade_id: None,
             },
-            RescindFinalizedPools {},
+            RescindFinalizedPools { trade_id: None },
         ]
         .into_iter()
         .for_each(|command| -> () {
@@ -3,15 +3,25 @@ mod execute_finalize_pools_tests {
     use crate::contract::execute;
     use crate::error::ContractError;
     use crate::msg::ExecuteMsg::FinalizePools;
-    use crate::storage::state_store::{save_contract_config, save_seller_state, Config, Seller};
+    use crate::storage::state_store::{
+        retrieve_contract_config, retrieve_seller_state, save_allowed_seller_state,
+        save_contract_config, save_seller_state, Config, PoolAsset, PoolEligibilityConfig, Seller,
+    };
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_std::{
-        to_json_binary, Attribute, Binary, ContractResult, MessageInfo, SystemResult,
+        coins, to_json_binary, Addr, Attribute, Binary, ContractResult, MessageInfo, Storage,
+        SystemResult,
     };
-    use provwasm_mocks::mock_provenance_dependencies;
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+    use provwasm_std::shim::Any;
+    use provwasm_std::types::cosmos::auth::v1beta1::BaseAccount;
     use provwasm_std::types::cosmos::base::v1beta1::Coin;
     use provwasm_std::types::provenance::marker::v1::{
-        Balance, QueryHoldingRequest, QueryHoldingResponse,
+        Access, AccessGrant, Balance, MarkerAccount, MarkerStatus, MarkerType, QueryHoldingRequest,
+        QueryHoldingResponse, QueryMarkerRequest, QueryMarkerResponse,
+    };
+    use provwasm_std::types::provenance::metadata::v1::{
+        ScopeResponse, ScopeSpecIdInfo, ScopeWrapper, ValueOwnershipResponse,
     };
 
     #[test]
@@ -29,23 +39,33 @@ mod execute_finalize_pools_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: true,
-                allowed_sellers: vec![seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 1,
+                max_bid_count: Some(1),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
 
         let pool_denoms = vec![pool_denom.into()];
         save_seller_state(
             &mut deps.storage,
             &Seller {
                 seller_address: seller_address.clone(),
-                pool_denoms: vec![],
+                pools: vec![],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -75,18 +95,32 @@ mod execute_finalize_pools_tests {
         deps.querier
             .registered_custom_queries
             .insert("/provenance.marker.v1.Query/Holding".to_string(), cb);
+        register_pool_marker(
+            &mut deps.querier,
+            MarkerStatus::Active,
+            vec![Access::Transfer as i32],
+        );
 
         match execute(
             deps.as_mut(),
             env.clone(),
             info,
-            FinalizePools { pool_denoms },
+            FinalizePools {
+                pool_denoms,
+                trade_id: None,
+            },
         ) {
             Ok(response) => {
                 let expected_seller_state = Seller {
                     seller_address: seller_address.clone(),
-                    pool_denoms: vec![pool_denom.into()],
+                    pools: vec![PoolAsset::Marker {
+                        denom: pool_denom.into(),
+                    }],
                     offer_hash: "mock-offer-hash".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
                 };
                 assert_eq!(response.attributes.len(), 1);
                 assert_eq!(
@@ -115,22 +149,32 @@ mod execute_finalize_pools_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: true,
-                allowed_sellers: vec![seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
 
         save_seller_state(
             &mut deps.storage,
             &Seller {
                 seller_address: seller_address.clone(),
-                pool_denoms: vec![],
+                pools: vec![],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -141,6 +185,7 @@ mod execute_finalize_pools_tests {
             info,
             FinalizePools {
                 pool_denoms: vec![],
+                trade_id: None,
             },
         ) {
             Ok(_) => {
@@ -173,22 +218,32 @@ mod execute_finalize_pools_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: true,
-                allowed_sellers: vec![allowed_seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 2,
+                max_bid_count: Some(2),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_address).unwrap();
 
         save_seller_state(
             &mut deps.storage,
             &Seller {
                 seller_address: allowed_seller_address.clone(),
-                pool_denoms: vec![],
+                pools: vec![],
                 offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
@@ -199,6 +254,7 @@ mod execute_finalize_pools_tests {
             info,
             FinalizePools {
                 pool_denoms: vec!["test.denom.0".into()],
+                trade_id: None,
             },
         ) {
             Ok(_) => {
@@ -215,4 +271,459 @@ mod execute_finalize_pools_tests {
             },
         }
     }
+
+    #[test]
+    fn execute_finalize_pool_records_eligible_scopes() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        setup_pool_eligibility(
+            &mut deps.storage,
+            &seller_address,
+            PoolEligibilityConfig {
+                min_scope_count: 1,
+                max_scope_count: 2,
+                allowed_scope_specs: vec![
+                    "scopespec1qnwg86nsatx5pl56muw0v9ytlz3qu3jx6m".to_string()
+                ],
+            },
+        );
+        register_pool_queries(
+            &mut deps.querier,
+            vec!["91978ba2-5f35-459a-86a7-feca1b0512e0".to_string()],
+            "scopespec1qnwg86nsatx5pl56muw0v9ytlz3qu3jx6m",
+        );
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            FinalizePools {
+                pool_denoms: vec!["test.token.asset.pool.0".to_string()],
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                let seller_state = retrieve_seller_state(&deps.storage, &seller_address).unwrap();
+                assert_eq!(
+                    seller_state.scope_addresses,
+                    vec!["scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel".to_string()]
+                );
+            }
+            Err(error) => {
+                panic!("failed to finalize an eligible pool: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn execute_finalize_pool_invalid_scope_count() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        setup_pool_eligibility(
+            &mut deps.storage,
+            &seller_address,
+            PoolEligibilityConfig {
+                min_scope_count: 2,
+                max_scope_count: 5,
+                allowed_scope_specs: vec![],
+            },
+        );
+        register_pool_queries(
+            &mut deps.querier,
+            vec!["91978ba2-5f35-459a-86a7-feca1b0512e0".to_string()],
+            "scopespec1qnwg86nsatx5pl56muw0v9ytlz3qu3jx6m",
+        );
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            FinalizePools {
+                pool_denoms: vec!["test.token.asset.pool.0".to_string()],
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect error when the pool owns too few scopes")
+            }
+            Err(error) => match error {
+                ContractError::InvalidPoolScopeCount { count, .. } => {
+                    assert_eq!(count, 1);
+                }
+                _ => {
+                    panic!("an unexpected error was returned when the pool owns too few scopes")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn execute_finalize_pool_invalid_scope_specification() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: vec![],
+        };
+        setup_pool_eligibility(
+            &mut deps.storage,
+            &seller_address,
+            PoolEligibilityConfig {
+                min_scope_count: 1,
+                max_scope_count: 1,
+                allowed_scope_specs: vec![
+                    "scopespec1qnwg86nsatx5pl56muw0v9ytlz3qu3jx6m".to_string()
+                ],
+            },
+        );
+        register_pool_queries(
+            &mut deps.querier,
+            vec!["91978ba2-5f35-459a-86a7-feca1b0512e0".to_string()],
+            "scopespec1unexpected",
+        );
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            FinalizePools {
+                pool_denoms: vec!["test.token.asset.pool.0".to_string()],
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect error when a scope uses a scope spec that is not allowed")
+            }
+            Err(error) => match error {
+                ContractError::InvalidScopeSpecification { scope_address } => {
+                    assert_eq!(scope_address, "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel");
+                }
+                _ => {
+                    panic!(
+                        "an unexpected error was returned when a scope uses a scope spec that is \
+                            not allowed"
+                    )
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn execute_finalize_pool_without_transfer_access() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        setup_pool_eligibility(
+            &mut deps.storage,
+            &seller_address,
+            PoolEligibilityConfig {
+                min_scope_count: 0,
+                max_scope_count: 5,
+                allowed_scope_specs: vec![],
+            },
+        );
+        register_pool_queries(&mut deps.querier, vec![], "");
+        register_pool_marker(
+            &mut deps.querier,
+            MarkerStatus::Active,
+            vec![Access::Withdraw as i32],
+        );
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: seller_address.clone(),
+                funds: vec![],
+            },
+            FinalizePools {
+                pool_denoms: vec!["test.token.asset.pool.0".into()],
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a pool marker that has not granted transfer access")
+            }
+            Err(error) => match error {
+                ContractError::MissingMarkerAccess { denom, access } => {
+                    assert_eq!(denom, "test.token.asset.pool.0");
+                    assert_eq!(access, "ACCESS_TRANSFER");
+                }
+                _ => {
+                    panic!("unexpected error when finalizing without transfer access: {error:?}")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn execute_finalize_pool_inactive_marker() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        setup_pool_eligibility(
+            &mut deps.storage,
+            &seller_address,
+            PoolEligibilityConfig {
+                min_scope_count: 0,
+                max_scope_count: 5,
+                allowed_scope_specs: vec![],
+            },
+        );
+        register_pool_queries(&mut deps.querier, vec![], "");
+        register_pool_marker(
+            &mut deps.querier,
+            MarkerStatus::Cancelled,
+            vec![Access::Transfer as i32],
+        );
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: seller_address.clone(),
+                funds: vec![],
+            },
+            FinalizePools {
+                pool_denoms: vec!["test.token.asset.pool.0".into()],
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a pool marker that is not active")
+            }
+            Err(error) => match error {
+                ContractError::InactiveMarker { denom, status } => {
+                    assert_eq!(denom, "test.token.asset.pool.0");
+                    assert_eq!(status, "MARKER_STATUS_CANCELLED");
+                }
+                _ => {
+                    panic!("unexpected error when finalizing an inactive pool marker: {error:?}")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn execute_finalize_native_pool() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        setup_pool_eligibility(
+            &mut deps.storage,
+            &seller_address,
+            PoolEligibilityConfig {
+                min_scope_count: 1,
+                max_scope_count: 5,
+                allowed_scope_specs: vec![],
+            },
+        );
+
+        let info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: coins(5, "ibc/pool"),
+        };
+
+        // Native coins cannot own scopes, so they are rejected while pool eligibility is configured
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            FinalizePools {
+                pool_denoms: vec![],
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => panic!("failed to detect a native pool finalized with pool eligibility"),
+            Err(error) => match error {
+                ContractError::IneligiblePoolAsset => {}
+                _ => {
+                    panic!("unexpected error when finalizing an ineligible native pool: {error:?}")
+                }
+            },
+        }
+
+        let mut config = retrieve_contract_config(&deps.storage).unwrap();
+        config.pool_eligibility = None;
+        save_contract_config(&mut deps.storage, &config).unwrap();
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            FinalizePools {
+                pool_denoms: vec![],
+                trade_id: None,
+            },
+        ) {
+            Ok(response) => {
+                // The coins were deposited with the message, so nothing needs to be transferred
+                assert!(response.messages.is_empty());
+                let seller_state = retrieve_seller_state(&deps.storage, &seller_address).unwrap();
+                assert_eq!(
+                    seller_state.pools,
+                    vec![PoolAsset::Native {
+                        coin: cosmwasm_std::Coin::new(5u128, "ibc/pool"),
+                    }]
+                );
+                assert!(seller_state.scope_addresses.is_empty());
+            }
+            Err(error) => {
+                panic!("failed to finalize a native pool: {:?}", error)
+            }
+        }
+    }
+
+    fn setup_pool_eligibility(
+        storage: &mut dyn Storage,
+        seller_address: &Addr,
+        pool_eligibility: PoolEligibilityConfig,
+    ) {
+        let deps = mock_provenance_dependencies();
+        save_contract_config(
+            storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: true,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(1),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: Some(pool_eligibility),
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_allowed_seller_state(storage, seller_address).unwrap();
+        save_seller_state(
+            storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+    }
+
+    fn register_pool_marker(
+        querier: &mut MockProvenanceQuerier,
+        status: MarkerStatus,
+        permissions: Vec<i32>,
+    ) {
+        let cb_marker = Box::new(
+            move |bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+                let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+                let inner_deps = mock_provenance_dependencies();
+                let marker = MarkerAccount {
+                    base_account: Some(BaseAccount {
+                        address: inner_deps.api.addr_make("pool_addr").to_string(),
+                        pub_key: None,
+                        account_number: 1,
+                        sequence: 0,
+                    }),
+                    manager: "".to_string(),
+                    access_control: vec![AccessGrant {
+                        address: mock_env().contract.address.to_string(),
+                        permissions: permissions.clone(),
+                    }],
+                    status: status.into(),
+                    denom: message.id.to_string(),
+                    supply: "2".to_string(),
+                    marker_type: MarkerType::Restricted.into(),
+                    supply_fixed: true,
+                    allow_governance_control: false,
+                    allow_forced_transfer: false,
+                    required_attributes: vec![],
+                };
+                let response = QueryMarkerResponse {
+                    marker: Some(Any {
+                        type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                        value: marker.to_proto_bytes(),
+                    }),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+            },
+        );
+        querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb_marker);
+    }
+
+    fn register_pool_queries(
+        querier: &mut MockProvenanceQuerier,
+        scope_uuids: Vec<String>,
+        scope_spec_address: &'static str,
+    ) {
+        let cb_holding = Box::new(|_: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let inner_deps = mock_provenance_dependencies();
+            let response = QueryHoldingResponse {
+                balances: vec![Balance {
+                    address: inner_deps.api.addr_make("allowed-seller-0").to_string(),
+                    coins: vec![Coin {
+                        denom: "test.token.asset.pool.0".to_string(),
+                        amount: "2".to_string(),
+                    }],
+                }],
+                pagination: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        querier.registered_custom_queries.insert(
+            "/provenance.marker.v1.Query/Holding".to_string(),
+            cb_holding,
+        );
+
+        register_pool_marker(querier, MarkerStatus::Active, vec![Access::Transfer as i32]);
+
+        let cb_ownership = Box::new(move |_: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let response = ValueOwnershipResponse {
+                scope_uuids: scope_uuids.clone(),
+                request: None,
+                pagination: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        querier.registered_custom_queries.insert(
+            "/provenance.metadata.v1.Query/ValueOwnership".to_string(),
+            cb_ownership,
+        );
+
+        let cb_scope = Box::new(move |_: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let response = ScopeResponse {
+                scope: Some(ScopeWrapper {
+                    scope: None,
+                    scope_id_info: None,
+                    scope_spec_id_info: Some(ScopeSpecIdInfo {
+                        scope_spec_id: vec![],
+                        scope_spec_id_prefix: vec![],
+                        scope_spec_id_scope_spec_uuid: vec![],
+                        scope_spec_addr: scope_spec_address.to_string(),
+                        scope_spec_uuid: "".to_string(),
+                    }),
+                }),
+                sessions: vec![],
+                records: vec![],
+                request: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        querier
+            .registered_custom_queries
+            .insert("/provenance.metadata.v1.Query/Scope".to_string(), cb_scope);
+    }
 }
@@ -0,0 +1,206 @@
+#[cfg(test)]
+mod execute_forfeit_bid_collateral_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::ForfeitBidCollateral;
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_acceptance_deadline_state, save_bid_collateral_state, save_bid_list_state,
+        save_buyer_state, save_contract_config, save_seller_state, BidCollateral,
+        BidCollateralConfig, BidList, Buyer, Config, PoolAsset, Seller,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coin, coins, BankMsg, CosmosMsg, MessageInfo, Uint128};
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn forfeit_collateral_after_acceptance_deadline() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address_0 = deps.api.addr_make("seller-0");
+        let seller_address_1 = deps.api.addr_make("seller-1");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: Some(BidCollateralConfig {
+                    denom: "nhash".to_string(),
+                    amount: Uint128::new(500),
+                    is_restricted_marker: false,
+                    acceptance_window: 3600,
+                }),
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        for (seller_address, denom) in [
+            (seller_address_0.clone(), "test.denom.pool.0"),
+            (seller_address_1.clone(), "test.denom.pool.1"),
+        ] {
+            save_seller_state(
+                &mut deps.storage,
+                &Seller {
+                    seller_address,
+                    pools: vec![PoolAsset::Marker {
+                        denom: denom.to_string(),
+                    }],
+                    offer_hash: "mock-offer-hash".to_string(),
+                    scope_addresses: vec![],
+                    offer_revision: 1,
+                    offer_updated_at: mock_env().block.time,
+                    received_pools: vec![],
+                },
+            )
+            .unwrap();
+        }
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_bid_collateral_state(
+            &mut deps.storage,
+            &BidCollateral {
+                bidder_address: buyer_address,
+                collateral: coin(500, "nhash"),
+            },
+        )
+        .unwrap();
+        save_acceptance_deadline_state(&mut deps.storage, &env.block.time.minus_seconds(1))
+            .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            env,
+            info,
+            ForfeitBidCollateral { trade_id: None },
+        ) {
+            Ok(response) => {
+                let messages: Vec<CosmosMsg> = response
+                    .messages
+                    .into_iter()
+                    .map(|message| message.msg)
+                    .collect();
+                assert_eq!(
+                    messages,
+                    vec![
+                        CosmosMsg::Bank(BankMsg::Send {
+                            to_address: seller_address_0.to_string(),
+                            amount: coins(250, "nhash"),
+                        }),
+                        CosmosMsg::Bank(BankMsg::Send {
+                            to_address: seller_address_1.to_string(),
+                            amount: coins(250, "nhash"),
+                        }),
+                    ]
+                );
+                assert!(query_contract_state(deps.as_ref())
+                    .unwrap()
+                    .bid_collateral
+                    .is_empty());
+            }
+            Err(error) => {
+                panic!("failed to forfeit bid collateral: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn forfeit_collateral_before_acceptance_deadline() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: Some(BidCollateralConfig {
+                    denom: "nhash".to_string(),
+                    amount: Uint128::new(500),
+                    is_restricted_marker: false,
+                    acceptance_window: 3600,
+                }),
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_acceptance_deadline_state(&mut deps.storage, &env.block.time.plus_seconds(60))
+            .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            env,
+            info,
+            ForfeitBidCollateral { trade_id: None },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a forfeit before the acceptance deadline")
+            }
+            Err(error) => match error {
+                ContractError::IllegalCollateralForfeit => {}
+                _ => {
+                    panic!("an unexpected error was returned when forfeiting collateral early")
+                }
+            },
+        }
+    }
+}
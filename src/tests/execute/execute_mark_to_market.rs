@@ -0,0 +1,468 @@
+#[cfg(test)]
+mod execute_mark_to_market_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{DepositMargin, MarkToMarket};
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_buyer_state, save_contract_config, save_seller_state,
+        save_token_data_state, BidList, Buyer, Config, MarginAccount, MarginConfig, PoolAsset,
+        Seller, TokenData,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coins, MessageInfo, Uint128};
+    use provwasm_mocks::mock_provenance_dependencies;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn mark_to_market_moves_variation_margin() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: Some(MarginConfig {
+                    denom: "nhash".to_string(),
+                    maintenance_margin: Decimal::ONE,
+                    margin_call_window: 3600,
+                }),
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool.0".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        // A seller that never finalized carries no exposure, so it does not dilute the exposure of the
+        // seller that holds the pools
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: deps.api.addr_make("unfinalized-seller-address"),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        for party_address in [&seller_address, &buyer_address] {
+            let deposit_info = MessageInfo {
+                sender: party_address.clone(),
+                funds: coins(20, "nhash"),
+            };
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                deposit_info,
+                DepositMargin { trade_id: None },
+            )
+            .unwrap();
+        }
+
+        let dealer_info = MessageInfo {
+            sender: dealer_address,
+            funds: vec![],
+        };
+        let reference_mark = MarkToMarket {
+            price: Decimal::from(5),
+            trade_id: None,
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            dealer_info.clone(),
+            reference_mark,
+        )
+        .unwrap();
+
+        let mark_message = MarkToMarket {
+            price: Decimal::from_str("6.5").unwrap(),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env.clone(), dealer_info, mark_message) {
+            Ok(_) => {
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert_eq!(
+                    contract_state.mark_price,
+                    Some(Decimal::from_str("6.5").unwrap())
+                );
+                assert!(contract_state.margin_accounts.contains(&MarginAccount {
+                    owner: buyer_address,
+                    balance: Decimal::from(35),
+                    margin_call_deadline: None,
+                    owed: Decimal::ZERO,
+                }));
+                assert!(contract_state.margin_accounts.contains(&MarginAccount {
+                    owner: seller_address,
+                    balance: Decimal::from(5),
+                    margin_call_deadline: Some(env.block.time.plus_seconds(3600)),
+                    owed: Decimal::ZERO,
+                }));
+            }
+            Err(error) => {
+                panic!("failed to mark the contract to market: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn mark_to_market_without_counterparty_deposit() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: Some(MarginConfig {
+                    denom: "nhash".to_string(),
+                    maintenance_margin: Decimal::ONE,
+                    margin_call_window: 3600,
+                }),
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool.0".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        // Only the buyer deposits margin
+        let buyer_deposit_info = MessageInfo {
+            sender: buyer_address.clone(),
+            funds: coins(20, "nhash"),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            buyer_deposit_info,
+            DepositMargin { trade_id: None },
+        )
+        .unwrap();
+
+        let dealer_info = MessageInfo {
+            sender: dealer_address,
+            funds: vec![],
+        };
+        let reference_mark = MarkToMarket {
+            price: Decimal::from(5),
+            trade_id: None,
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            dealer_info.clone(),
+            reference_mark,
+        )
+        .unwrap();
+
+        let mark_message = MarkToMarket {
+            price: Decimal::from_str("6.5").unwrap(),
+            trade_id: None,
+        };
+        // The seller owes the variation margin it cannot pay and the buyer is only credited with what
+        // was actually paid
+        match execute(deps.as_mut(), env.clone(), dealer_info, mark_message) {
+            Ok(_) => {
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert!(contract_state.margin_accounts.contains(&MarginAccount {
+                    owner: buyer_address.clone(),
+                    balance: Decimal::from(20),
+                    margin_call_deadline: None,
+                    owed: Decimal::ZERO,
+                }));
+                assert!(contract_state.margin_accounts.contains(&MarginAccount {
+                    owner: seller_address.clone(),
+                    balance: Decimal::ZERO,
+                    margin_call_deadline: Some(env.block.time.plus_seconds(3600)),
+                    owed: Decimal::from(15),
+                }));
+            }
+            Err(error) => {
+                panic!(
+                    "failed to mark the contract to market without a seller deposit: {:?}",
+                    error
+                )
+            }
+        }
+
+        // A later deposit by the seller first pays the buyer what the seller owes
+        let seller_deposit_info = MessageInfo {
+            sender: seller_address.clone(),
+            funds: coins(20, "nhash"),
+        };
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            seller_deposit_info,
+            DepositMargin { trade_id: None },
+        ) {
+            Ok(_) => {
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert!(contract_state.margin_accounts.contains(&MarginAccount {
+                    owner: buyer_address,
+                    balance: Decimal::from(35),
+                    margin_call_deadline: None,
+                    owed: Decimal::ZERO,
+                }));
+                assert!(contract_state.margin_accounts.contains(&MarginAccount {
+                    owner: seller_address,
+                    balance: Decimal::from(5),
+                    margin_call_deadline: Some(env.block.time.plus_seconds(3600)),
+                    owed: Decimal::ZERO,
+                }));
+            }
+            Err(error) => {
+                panic!("failed to deposit margin owed by the seller: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn mark_to_market_before_bid_acceptance() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let info = MessageInfo {
+            sender: dealer_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: Some(MarginConfig {
+                    denom: "nhash".to_string(),
+                    maintenance_margin: Decimal::ONE,
+                    margin_call_window: 3600,
+                }),
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        let mark_message = MarkToMarket {
+            price: Decimal::from(5),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, mark_message) {
+            Ok(_) => {
+                panic!("failed to detect a mark to market before a bid was accepted")
+            }
+            Err(error) => match error {
+                ContractError::IllegalMarkToMarket => {}
+                _ => {
+                    panic!("an unexpected error was returned when marking to market early")
+                }
+            },
+        }
+    }
+
+    #[test]
+    fn mark_to_market_with_non_positive_price() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![dealer_address.clone()],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: Some(MarginConfig {
+                    denom: "nhash".to_string(),
+                    maintenance_margin: Decimal::ONE,
+                    margin_call_window: 3600,
+                }),
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: deps.api.addr_make("buyer-address"),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        for price in [Decimal::ZERO, Decimal::from(-5)] {
+            let mark_message = MarkToMarket {
+                price,
+                trade_id: None,
+            };
+            let info = MessageInfo {
+                sender: dealer_address.clone(),
+                funds: vec![],
+            };
+            match execute(deps.as_mut(), mock_env(), info, mark_message) {
+                Ok(_) => {
+                    panic!("failed to detect a mark price of {price}")
+                }
+                Err(error) => match error {
+                    ContractError::InvalidMarkPrice => {}
+                    _ => {
+                        panic!("an unexpected error was returned for a mark price of {price}")
+                    }
+                },
+            }
+        }
+    }
+}
@@ -4,7 +4,8 @@ mod execute_add_seller_tests {
     use crate::error::ContractError;
     use crate::msg::ExecuteMsg::MintTokens;
     use crate::storage::state_store::{
-        retrieve_token_data_state, save_contract_config, save_token_data_state, Config, TokenData,
+        retrieve_token_data_state, save_allowed_seller_state, save_contract_config,
+        save_token_data_state, Config, TokenData,
     };
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_std::{MessageInfo, Uint128};
@@ -26,15 +27,21 @@ mod execute_add_seller_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: admin_address.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
 
         match execute(
             deps.as_mut(),
@@ -43,10 +50,11 @@ mod execute_add_seller_tests {
             MintTokens {
                 token_denom: "test.denom.fm".to_string(),
                 token_count: Uint128::new(100),
+                trade_id: None,
             },
         ) {
             Ok(_) => {
-                let token_data = retrieve_token_data_state(&mut deps.storage).unwrap();
+                let token_data = retrieve_token_data_state(&deps.storage).unwrap();
                 assert_eq!(
                     token_data,
                     TokenData {
@@ -77,15 +85,21 @@ mod execute_add_seller_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: admin_address.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
 
         match execute(
             deps.as_mut(),
@@ -94,6 +108,7 @@ mod execute_add_seller_tests {
             MintTokens {
                 token_denom: "test.denom.fm".to_string(),
                 token_count: Uint128::new(100),
+                trade_id: None,
             },
         ) {
             Ok(_) => {
@@ -127,15 +142,21 @@ mod execute_add_seller_tests {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![seller_address.clone()],
-                allowed_buyers: vec![],
                 dealers: vec![dealer_address.clone()],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: admin_address.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
 
         save_token_data_state(
             &mut deps.storage,
@@ -153,6 +174,7 @@ mod execute_add_seller_tests {
             MintTokens {
                 token_denom: "test.denom.fm".to_string(),
                 token_count: Uint128::new(100),
+                trade_id: None,
             },
         ) {
             Ok(_) => {
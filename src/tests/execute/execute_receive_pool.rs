@@ -0,0 +1,426 @@
+#[cfg(test)]
+mod execute_receive_pool_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{FinalizePools, Receive, ReceiveNft, RescindFinalizedPools};
+    use crate::msg::{Cw20ReceiveMsg, Cw721ExecuteMsg, Cw721ReceiveMsg, PoolReceiveMsg};
+    use crate::storage::state_store::{
+        retrieve_contract_config, retrieve_seller_state, save_bid_list_state, save_contract_config,
+        save_seller_state, BidList, Config, PoolAsset, PoolEligibilityConfig, Seller,
+    };
+    use cosmwasm_std::testing::{mock_env, MockApi};
+    use cosmwasm_std::{
+        coin, coins, to_json_binary, Addr, Coin, ContractInfoResponse, ContractResult,
+        MemoryStorage, MessageInfo, OwnedDeps, SubMsg, SystemError, SystemResult, Uint128, WasmMsg,
+        WasmQuery,
+    };
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+
+    fn setup_seller(deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>) {
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![
+                    deps.api.addr_make("cw20-token"),
+                    deps.api.addr_make("cw721-loans"),
+                    deps.api.addr_make("unregistered-token"),
+                ],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        // Only the token contracts exist on chain, while the unregistered token is an account
+        let token_contracts = [
+            deps.api.addr_make("cw20-token").to_string(),
+            deps.api.addr_make("cw721-loans").to_string(),
+        ];
+        deps.querier
+            .mock_querier
+            .update_wasm(move |query| match query {
+                WasmQuery::ContractInfo { contract_addr }
+                    if token_contracts.contains(contract_addr) =>
+                {
+                    let response = ContractInfoResponse::new(
+                        1,
+                        Addr::unchecked("token-creator"),
+                        None,
+                        false,
+                        None,
+                    );
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+                }
+                _ => SystemResult::Err(SystemError::NoSuchContract {
+                    addr: format!("{query:?}"),
+                }),
+            });
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: deps.api.addr_make("seller-address"),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn execute_receive_cw20_pool() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let seller_address = deps.api.addr_make("seller-address");
+        let token_address = deps.api.addr_make("cw20-token");
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: token_address.clone(),
+                funds: vec![],
+            },
+            Receive(Cw20ReceiveMsg {
+                sender: seller_address.to_string(),
+                amount: Uint128::new(250),
+                msg: to_json_binary(&PoolReceiveMsg::FinalizePool { trade_id: None }).unwrap(),
+            }),
+        ) {
+            Ok(response) => {
+                assert!(response.messages.is_empty());
+
+                // The pool is held for the seller until they finalize it
+                let seller = retrieve_seller_state(&deps.storage, &seller_address).unwrap();
+                assert!(seller.pools.is_empty());
+                assert_eq!(
+                    seller.received_pools,
+                    vec![PoolAsset::Cw20 {
+                        contract: token_address,
+                        amount: Uint128::new(250),
+                    }]
+                );
+            }
+            Err(error) => {
+                panic!("failed to receive a cw20 pool: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn execute_rescind_received_nft_pool() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let seller_address = deps.api.addr_make("seller-address");
+        let nft_address = deps.api.addr_make("cw721-loans");
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: nft_address.clone(),
+                funds: vec![],
+            },
+            ReceiveNft(Cw721ReceiveMsg {
+                sender: seller_address.to_string(),
+                token_id: "loan-1".to_string(),
+                msg: to_json_binary(&PoolReceiveMsg::FinalizePool { trade_id: None }).unwrap(),
+            }),
+        )
+        .unwrap();
+        finalize_pools(&mut deps, vec![]).unwrap();
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: seller_address.clone(),
+                funds: vec![],
+            },
+            RescindFinalizedPools { trade_id: None },
+        ) {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages,
+                    vec![SubMsg::new(WasmMsg::Execute {
+                        contract_addr: nft_address.to_string(),
+                        msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                            recipient: seller_address.to_string(),
+                            token_id: "loan-1".to_string(),
+                        })
+                        .unwrap(),
+                        funds: vec![],
+                    })]
+                );
+                assert!(retrieve_seller_state(&deps.storage, &seller_address)
+                    .unwrap()
+                    .pools
+                    .is_empty());
+            }
+            Err(error) => {
+                panic!("failed to rescind a received nft pool: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn execute_receive_pool_not_seller() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let token_address = deps.api.addr_make("cw20-token");
+        let other_address = deps.api.addr_make("not-a-seller");
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: token_address,
+                funds: vec![],
+            },
+            Receive(Cw20ReceiveMsg {
+                sender: other_address.to_string(),
+                amount: Uint128::new(250),
+                msg: to_json_binary(&PoolReceiveMsg::FinalizePool { trade_id: None }).unwrap(),
+            }),
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a pool sent by an address that is not a seller")
+            }
+            Err(error) => match error {
+                ContractError::UnauthorizedAsSeller => {}
+                _ => {
+                    panic!("unexpected error when receiving a pool from a non seller")
+                }
+            },
+        }
+    }
+
+    fn receive_cw20(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        token_address: &Addr,
+        amount: u128,
+    ) -> Result<cosmwasm_std::Response, ContractError> {
+        let seller_address = deps.api.addr_make("seller-address");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: token_address.clone(),
+                funds: vec![],
+            },
+            Receive(Cw20ReceiveMsg {
+                sender: seller_address.to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(&PoolReceiveMsg::FinalizePool { trade_id: None }).unwrap(),
+            }),
+        )
+    }
+
+    #[test]
+    fn execute_receive_pool_from_unaccepted_sender() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+
+        // A seller calling the hook directly and an accepted address that is not a contract are both
+        // refused, as is a token contract that was not accepted
+        let unaccepted_senders = [
+            deps.api.addr_make("seller-address"),
+            deps.api.addr_make("unregistered-token"),
+            deps.api.addr_make("other-token"),
+        ];
+        for sender in unaccepted_senders {
+            match receive_cw20(&mut deps, &sender, 250) {
+                Ok(_) => {
+                    panic!("failed to detect a pool received from {sender}")
+                }
+                Err(error) => match error {
+                    ContractError::UnacceptedTokenContract { contract } => {
+                        assert_eq!(contract, sender.to_string())
+                    }
+                    _ => {
+                        panic!("unexpected error when receiving a pool from {sender}: {error:?}")
+                    }
+                },
+            }
+        }
+        assert!(
+            retrieve_seller_state(&deps.storage, &deps.api.addr_make("seller-address"))
+                .unwrap()
+                .pools
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn execute_receive_zero_cw20_pool() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let token_address = deps.api.addr_make("cw20-token");
+
+        match receive_cw20(&mut deps, &token_address, 0) {
+            Ok(_) => {
+                panic!("failed to detect a cw20 pool without any tokens")
+            }
+            Err(error) => match error {
+                ContractError::InvalidFinalizationRequest => {}
+                _ => {
+                    panic!("unexpected error when receiving an empty cw20 pool: {error:?}")
+                }
+            },
+        }
+    }
+
+    fn receive_nft(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        token_id: &str,
+    ) -> Result<cosmwasm_std::Response, ContractError> {
+        let seller_address = deps.api.addr_make("seller-address");
+        let nft_address = deps.api.addr_make("cw721-loans");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: nft_address,
+                funds: vec![],
+            },
+            ReceiveNft(Cw721ReceiveMsg {
+                sender: seller_address.to_string(),
+                token_id: token_id.to_string(),
+                msg: to_json_binary(&PoolReceiveMsg::FinalizePool { trade_id: None }).unwrap(),
+            }),
+        )
+    }
+
+    fn finalize_pools(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        funds: Vec<Coin>,
+    ) -> Result<cosmwasm_std::Response, ContractError> {
+        let seller_address = deps.api.addr_make("seller-address");
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: seller_address,
+                funds,
+            },
+            FinalizePools {
+                pool_denoms: vec![],
+                trade_id: None,
+            },
+        )
+    }
+
+    #[test]
+    fn execute_receive_multiple_pools() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let seller_address = deps.api.addr_make("seller-address");
+        let nft_address = deps.api.addr_make("cw721-loans");
+        let token_address = deps.api.addr_make("cw20-token");
+
+        // A seller can send several token contract pools before finalizing them together
+        receive_nft(&mut deps, "loan-1").unwrap();
+        receive_nft(&mut deps, "loan-2").unwrap();
+        receive_cw20(&mut deps, &token_address, 250).unwrap();
+        finalize_pools(&mut deps, coins(5, "ibc/pool")).unwrap();
+
+        let seller = retrieve_seller_state(&deps.storage, &seller_address).unwrap();
+        assert!(seller.received_pools.is_empty());
+        assert_eq!(
+            seller.pools,
+            vec![
+                PoolAsset::Native {
+                    coin: coin(5, "ibc/pool"),
+                },
+                PoolAsset::Cw721 {
+                    contract: nft_address.clone(),
+                    token_id: "loan-1".to_string(),
+                },
+                PoolAsset::Cw721 {
+                    contract: nft_address,
+                    token_id: "loan-2".to_string(),
+                },
+                PoolAsset::Cw20 {
+                    contract: token_address,
+                    amount: Uint128::new(250),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn execute_receive_pool_follows_finalize_rules() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let seller_address = deps.api.addr_make("seller-address");
+        let token_address = deps.api.addr_make("cw20-token");
+
+        // Received pools are finalized by the seller's next FinalizePools, after which neither route
+        // can add more pools until they are rescinded
+        receive_cw20(&mut deps, &token_address, 250).unwrap();
+        receive_cw20(&mut deps, &token_address, 100).unwrap();
+        finalize_pools(&mut deps, vec![]).unwrap();
+        assert_eq!(
+            retrieve_seller_state(&deps.storage, &seller_address)
+                .unwrap()
+                .pools
+                .len(),
+            2
+        );
+        match receive_cw20(&mut deps, &token_address, 100) {
+            Ok(_) => panic!("failed to detect a pool received after the pools were finalized"),
+            Err(error) => match error {
+                ContractError::PoolAlreadyFinalized => {}
+                _ => panic!("unexpected error when receiving a pool after finalizing: {error:?}"),
+            },
+        }
+        match finalize_pools(&mut deps, coins(5, "ibc/pool")) {
+            Ok(_) => panic!("failed to detect pools finalized twice"),
+            Err(error) => match error {
+                ContractError::PoolAlreadyFinalized => {}
+                _ => panic!("unexpected error when finalizing twice: {error:?}"),
+            },
+        }
+
+        // A token contract's pool cannot own scopes, so it is refused under pool eligibility
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: seller_address.clone(),
+                funds: vec![],
+            },
+            RescindFinalizedPools { trade_id: None },
+        )
+        .unwrap();
+        let mut config = retrieve_contract_config(&deps.storage).unwrap();
+        config.pool_eligibility = Some(PoolEligibilityConfig {
+            min_scope_count: 1,
+            max_scope_count: 5,
+            allowed_scope_specs: vec![],
+        });
+        save_contract_config(&mut deps.storage, &config).unwrap();
+        match receive_cw20(&mut deps, &token_address, 250) {
+            Ok(_) => panic!("failed to detect a cw20 pool received under pool eligibility"),
+            Err(error) => match error {
+                ContractError::IneligiblePoolAsset => {}
+                _ => panic!("unexpected error when receiving an ineligible pool: {error:?}"),
+            },
+        }
+    }
+}
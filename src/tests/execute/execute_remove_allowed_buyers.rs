@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod execute_remove_allowed_buyers_tests {
+    use crate::contract::execute;
+    use crate::msg::ExecuteMsg::{AddAllowedBuyers, AddBid, RemoveAllowedBuyers};
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_contract_config, BidCollateralConfig, BidList, Config,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coins, BankMsg, CosmosMsg, MessageInfo, Uint128};
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn remove_allowed_buyers_with_bids() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let admin_info = MessageInfo {
+            sender: contract_admin.clone(),
+            funds: vec![],
+        };
+        let bidder_0_address = deps.api.addr_make("bidder-0");
+        let bidder_1_address = deps.api.addr_make("bidder-1");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: true,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: Some(BidCollateralConfig {
+                    denom: "nhash".to_string(),
+                    amount: Uint128::new(500),
+                    is_restricted_marker: false,
+                    acceptance_window: 3600,
+                }),
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            AddAllowedBuyers {
+                allowed_buyers: vec![bidder_0_address.to_string(), bidder_1_address.to_string()],
+                trade_id: None,
+            },
+        )
+        .unwrap();
+
+        for bidder_address in [&bidder_0_address, &bidder_1_address] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                MessageInfo {
+                    sender: bidder_address.clone(),
+                    funds: coins(500, "nhash"),
+                },
+                AddBid {
+                    agreement_terms_hash: "buyer-mock-hash".to_string(),
+                    price: None,
+                    trade_id: None,
+                },
+            )
+            .unwrap();
+        }
+
+        // Removing a buyer without removing their bid leaves the bid in place
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info.clone(),
+            RemoveAllowedBuyers {
+                allowed_buyers: vec![bidder_1_address.to_string()],
+                remove_bids: false,
+                trade_id: None,
+            },
+        )
+        .unwrap();
+        let contract_state = query_contract_state(deps.as_ref()).unwrap();
+        assert_eq!(
+            contract_state.allowed_buyers,
+            vec![bidder_0_address.clone()]
+        );
+        assert_eq!(contract_state.bids.len(), 2);
+
+        // Only the bids of the buyers that are removed from the list are dropped, so the bid of the
+        // buyer that was already removed is left in place
+        match execute(
+            deps.as_mut(),
+            env.clone(),
+            admin_info,
+            RemoveAllowedBuyers {
+                allowed_buyers: vec![bidder_0_address.to_string(), bidder_1_address.to_string()],
+                remove_bids: true,
+                trade_id: None,
+            },
+        ) {
+            Ok(response) => {
+                assert_eq!(response.messages.len(), 1);
+                assert_eq!(
+                    response.messages[0].msg,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: bidder_0_address.to_string(),
+                        amount: coins(500, "nhash"),
+                    })
+                );
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert!(contract_state.allowed_buyers.is_empty());
+                assert_eq!(contract_state.bids.len(), 1);
+                assert_eq!(contract_state.bids[0].buyer_address, bidder_1_address);
+                assert_eq!(contract_state.bid_collateral.len(), 1);
+                assert_eq!(
+                    contract_state.bid_collateral[0].bidder_address,
+                    bidder_1_address
+                );
+            }
+            Err(error) => {
+                panic!(
+                    "failed to remove an allowed buyer and their bid: {:?}",
+                    error
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod execute_reveal_bid_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::RevealBid;
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_commitment_state, save_bid_list_state, save_contract_config, AuctionConfig, Bid,
+        BidCommitment, BidList, Config,
+    };
+    use crate::util::helpers::create_bid_commitment;
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::MessageInfo;
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn reveal_bid_during_reveal_window() {
+        let mut deps = mock_provenance_dependencies();
+        let bidder_address = deps.api.addr_make("bidder-address");
+        let info = MessageInfo {
+            sender: bidder_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: Some(AuctionConfig {
+                    commit_deadline: env.block.time.minus_seconds(60),
+                    reveal_deadline: env.block.time.plus_seconds(60),
+                }),
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_bid_commitment_state(
+            &mut deps.storage,
+            &BidCommitment {
+                buyer_address: bidder_address.clone(),
+                commitment: create_bid_commitment("buyer-mock-hash", None, "buyer-salt"),
+                committed_at: mock_env().block.time,
+            },
+        )
+        .unwrap();
+
+        let reveal_bid_message = RevealBid {
+            agreement_terms_hash: "buyer-mock-hash".to_string(),
+            salt: "buyer-salt".to_string(),
+            price: None,
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, reveal_bid_message) {
+            Ok(_) => {
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert_eq!(
+                    contract_state.bids,
+                    vec![Bid {
+                        buyer_address: bidder_address.clone(),
+                        agreement_terms_hash: "buyer-mock-hash".to_string(),
+                        price: None,
+                        submitted_at: mock_env().block.time,
+                        revision: 1,
+                    }]
+                );
+                assert!(contract_state.bid_commitments.is_empty());
+            }
+            Err(error) => {
+                panic!("failed to reveal bid: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn reveal_bid_that_does_not_match_commitment() {
+        let mut deps = mock_provenance_dependencies();
+        let bidder_address = deps.api.addr_make("bidder-address");
+        let info = MessageInfo {
+            sender: bidder_address.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: Some(AuctionConfig {
+                    commit_deadline: env.block.time.minus_seconds(60),
+                    reveal_deadline: env.block.time.plus_seconds(60),
+                }),
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_bid_commitment_state(
+            &mut deps.storage,
+            &BidCommitment {
+                buyer_address: bidder_address,
+                commitment: create_bid_commitment("buyer-mock-hash", None, "buyer-salt"),
+                committed_at: mock_env().block.time,
+            },
+        )
+        .unwrap();
+
+        let reveal_bid_message = RevealBid {
+            agreement_terms_hash: "different-mock-hash".to_string(),
+            salt: "buyer-salt".to_string(),
+            price: None,
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, reveal_bid_message) {
+            Ok(_) => {
+                panic!("failed to detect a revealed bid that does not match the commitment")
+            }
+            Err(error) => match error {
+                ContractError::InvalidBidReveal => {}
+                _ => {
+                    panic!("an unexpected error was returned when revealing a mismatched bid")
+                }
+            },
+        }
+    }
+}
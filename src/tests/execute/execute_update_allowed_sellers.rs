@@ -1,10 +1,12 @@
 #[cfg(test)]
-mod execute_update_allowed_sellers {
+mod execute_update_allowed_sellers_tests {
     use crate::contract::execute;
     use crate::error::ContractError;
     use crate::msg::ExecuteMsg::UpdateAllowedSellers;
     use crate::query::contract_state::query_contract_state;
-    use crate::storage::state_store::{save_bid_list_state, save_contract_config, BidList, Config};
+    use crate::storage::state_store::{
+        save_allowed_seller_state, save_bid_list_state, save_contract_config, BidList, Config,
+    };
     use crate::version_info::{set_version_info, VersionInfoV1};
     use cosmwasm_std::testing::mock_env;
     use cosmwasm_std::{Attribute, MessageInfo};
@@ -27,15 +29,21 @@ mod execute_update_allowed_sellers {
             &Config {
                 use_private_sellers: true,
                 use_private_buyers: false,
-                allowed_sellers: vec![allowed_seller_0_addr],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 8,
+                max_bid_count: Some(8),
                 contract_admin: info.sender.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
+        save_allowed_seller_state(&mut deps.storage, &allowed_seller_0_addr).unwrap();
 
         set_version_info(
             &mut deps.storage,
@@ -50,32 +58,23 @@ mod execute_update_allowed_sellers {
 
         let update_allowed_sellers = UpdateAllowedSellers {
             allowed_sellers: vec![allowed_seller_1_addr.to_string()],
+            trade_id: None,
         };
 
         match execute(deps.as_mut(), env, info.clone(), update_allowed_sellers) {
             Ok(response) => {
-                let expected_config_attributes = Config {
-                    use_private_sellers: true,
-                    use_private_buyers: false,
-                    allowed_sellers: vec![allowed_seller_1_addr],
-                    allowed_buyers: vec![],
-                    dealers: vec![deps.api.addr_make("dealer-address")],
-                    is_disabled: false,
-                    max_bid_count: 8,
-                    contract_admin: info.sender.clone(),
-                };
                 assert_eq!(response.attributes.len(), 1);
                 assert_eq!(
                     response.attributes[0],
                     Attribute::new(
-                        "contract_config",
-                        format!("{:?}", expected_config_attributes)
+                        "allowed_sellers",
+                        format!("{:?}", vec![allowed_seller_1_addr.clone()])
                     )
                 );
 
                 assert_eq!(
-                    query_contract_state(deps.as_ref()).unwrap().config,
-                    expected_config_attributes
+                    query_contract_state(deps.as_ref()).unwrap().allowed_sellers,
+                    vec![allowed_seller_1_addr]
                 );
             }
             Err(error) => {
@@ -98,18 +97,24 @@ mod execute_update_allowed_sellers {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 5,
+                max_bid_count: Some(5),
                 contract_admin: info.sender.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
 
         let update_allowed_sellers = UpdateAllowedSellers {
             allowed_sellers: vec![deps.api.addr_make("allowed-seller-2").to_string()],
+            trade_id: None,
         };
         match execute(deps.as_mut(), env, info, update_allowed_sellers) {
             Ok(_) => {
@@ -1,15 +1,18 @@
 #[cfg(test)]
-mod execute_update_seller_offer_hash {
-    use crate::contract::execute;
+mod execute_update_seller_offer_hash_tests {
+    use crate::contract::{execute, query};
     use crate::error::ContractError;
     use crate::msg::ExecuteMsg::UpdateSellerOfferHash;
+    use crate::msg::GetHistoryResponse;
+    use crate::msg::QueryMsg::GetHistory;
     use crate::query::contract_state::query_contract_state;
     use crate::storage::state_store::{
-        save_bid_list_state, save_contract_config, save_seller_state, BidList, Config, Seller,
+        save_bid_list_state, save_contract_config, save_seller_state, BidList, Config,
+        HistoryChange, HistoryEntry, OfferRevision, PoolAsset, Seller,
     };
     use crate::version_info::{set_version_info, VersionInfoV1};
     use cosmwasm_std::testing::mock_env;
-    use cosmwasm_std::MessageInfo;
+    use cosmwasm_std::{from_json, to_json_string, MessageInfo};
     use provwasm_mocks::mock_provenance_dependencies;
 
     #[test]
@@ -27,12 +30,17 @@ mod execute_update_seller_offer_hash {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
@@ -52,25 +60,44 @@ mod execute_update_seller_offer_hash {
             &mut deps.storage,
             &Seller {
                 seller_address: seller_addr.clone(),
-                pool_denoms: vec!["test.denom.mock".to_string()],
+                pools: vec![],
                 offer_hash: "to-be-replaced".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
 
         let update_hash_message = UpdateSellerOfferHash {
             offer_hash: "new-hash".to_string(),
+            trade_id: None,
         };
         match execute(deps.as_mut(), env.clone(), info, update_hash_message) {
             Ok(_) => {
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
                 assert_eq!(
-                    query_contract_state(deps.as_ref()).unwrap().seller.unwrap(),
-                    Seller {
+                    contract_state.sellers,
+                    vec![Seller {
                         seller_address: seller_addr.clone(),
-                        pool_denoms: vec!["test.denom.mock".to_string()],
+                        pools: vec![],
                         offer_hash: "new-hash".to_string(),
-                    }
-                )
+                        scope_addresses: vec![],
+                        offer_revision: 2,
+                        offer_updated_at: mock_env().block.time,
+                        received_pools: vec![],
+                    }]
+                );
+                assert_eq!(
+                    contract_state.offer_revisions,
+                    vec![OfferRevision {
+                        seller_address: seller_addr.clone(),
+                        offer_hash: "to-be-replaced".to_string(),
+                        revision: 1,
+                        updated_at: mock_env().block.time,
+                    }]
+                );
             }
             Err(error) => {
                 panic!("failed to update seller offer hash: {:?}", error)
@@ -78,6 +105,80 @@ mod execute_update_seller_offer_hash {
         }
     }
 
+    #[test]
+    fn update_seller_offer_hash_with_finalized_pools() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_addr = deps.api.addr_make("public-seller-0");
+        let info = MessageInfo {
+            sender: seller_addr.clone(),
+            funds: vec![],
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_addr.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.mock".to_string(),
+                }],
+                offer_hash: "to-be-replaced".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        let update_hash_message = UpdateSellerOfferHash {
+            offer_hash: "new-hash".to_string(),
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env.clone(), info, update_hash_message) {
+            Ok(_) => {
+                panic!("failed to detect error when updating the offer hash of finalized pools")
+            }
+            Err(error) => match error {
+                ContractError::PoolAlreadyFinalized => {}
+                _ => {
+                    panic!("unexpected error encountered when updating the offer hash of finalized pools")
+                }
+            },
+        }
+    }
+
     #[test]
     fn update_seller_offer_hash_not_seller() {
         let mut deps = mock_provenance_dependencies();
@@ -92,12 +193,17 @@ mod execute_update_seller_offer_hash {
             &Config {
                 use_private_sellers: false,
                 use_private_buyers: false,
-                allowed_sellers: vec![],
-                allowed_buyers: vec![],
                 dealers: vec![deps.api.addr_make("dealer-address")],
                 is_disabled: false,
-                max_bid_count: 3,
+                max_bid_count: Some(3),
                 contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
             },
         )
         .unwrap();
@@ -117,14 +223,21 @@ mod execute_update_seller_offer_hash {
             &mut deps.storage,
             &Seller {
                 seller_address: deps.api.addr_make("public-seller-0"),
-                pool_denoms: vec!["test.denom.mock".to_string()],
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.mock".to_string(),
+                }],
                 offer_hash: "to-be-replaced".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
             },
         )
         .unwrap();
 
         let update_hash_message = UpdateSellerOfferHash {
             offer_hash: "new-hash".to_string(),
+            trade_id: None,
         };
         match execute(deps.as_mut(), env.clone(), info, update_hash_message) {
             Ok(_) => {
@@ -138,4 +251,105 @@ mod execute_update_seller_offer_hash {
             },
         }
     }
+
+    #[test]
+    fn update_seller_offer_hash_is_recorded_in_history() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_addr = deps.api.addr_make("public-seller-0");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_addr.clone(),
+                pools: vec![],
+                offer_hash: "first-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        for offer_hash in ["second-hash", "third-hash"] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                MessageInfo {
+                    sender: seller_addr.clone(),
+                    funds: vec![],
+                },
+                UpdateSellerOfferHash {
+                    offer_hash: offer_hash.to_string(),
+                    trade_id: None,
+                },
+            )
+            .unwrap();
+        }
+
+        let history: GetHistoryResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetHistory {
+                    trade_id: None,
+                    start_after: Some(1),
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let seller_json = |offer_hash: &str, offer_revision: u64| {
+            to_json_string(&Seller {
+                seller_address: seller_addr.clone(),
+                pools: vec![],
+                offer_hash: offer_hash.to_string(),
+                scope_addresses: vec![],
+                offer_revision,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            })
+            .unwrap()
+        };
+        assert_eq!(
+            vec![HistoryEntry {
+                id: 2,
+                actor: seller_addr.clone(),
+                action: "UpdateSellerOfferHash".to_string(),
+                block_height: env.block.height,
+                block_time: env.block.time,
+                changes: vec![HistoryChange {
+                    field: "sellers".to_string(),
+                    key: Some(seller_addr.to_string()),
+                    before: Some(seller_json("second-hash", 2)),
+                    after: Some(seller_json("third-hash", 3)),
+                }],
+            }],
+            history.entries
+        );
+    }
 }
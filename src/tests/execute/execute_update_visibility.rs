@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod execute_update_visibility_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{AddBid, UpdateVisibility};
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_contract_config, save_seller_state, BidCollateralConfig, BidList,
+        Config, Seller,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coins, BankMsg, CosmosMsg, MessageInfo, Uint128};
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn update_visibility_to_private_buyers() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let allowed_bidder_address = deps.api.addr_make("allowed-bidder");
+        let other_bidder_address = deps.api.addr_make("other-bidder");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: Some(BidCollateralConfig {
+                    denom: "nhash".to_string(),
+                    amount: Uint128::new(500),
+                    is_restricted_marker: false,
+                    acceptance_window: 3600,
+                }),
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        for bidder_address in [&allowed_bidder_address, &other_bidder_address] {
+            execute(
+                deps.as_mut(),
+                env.clone(),
+                MessageInfo {
+                    sender: bidder_address.clone(),
+                    funds: coins(500, "nhash"),
+                },
+                AddBid {
+                    agreement_terms_hash: "buyer-mock-hash".to_string(),
+                    price: None,
+                    trade_id: None,
+                },
+            )
+            .unwrap();
+        }
+
+        match execute(
+            deps.as_mut(),
+            env,
+            MessageInfo {
+                sender: contract_admin,
+                funds: vec![],
+            },
+            UpdateVisibility {
+                private_sellers: false,
+                private_buyers: true,
+                allowed_sellers: vec![],
+                allowed_buyers: vec![allowed_bidder_address.to_string()],
+                max_bid_count: Some(1),
+                trade_id: None,
+            },
+        ) {
+            Ok(response) => {
+                // The bidder that is no longer allowed has their bid dropped and collateral returned
+                assert_eq!(
+                    response.messages[0].msg,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: other_bidder_address.to_string(),
+                        amount: coins(500, "nhash"),
+                    })
+                );
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert!(contract_state.config.use_private_buyers);
+                assert_eq!(contract_state.config.max_bid_count, Some(1));
+                assert_eq!(
+                    contract_state.allowed_buyers,
+                    vec![allowed_bidder_address.clone()]
+                );
+                assert_eq!(contract_state.bids.len(), 1);
+                assert_eq!(contract_state.bids[0].buyer_address, allowed_bidder_address);
+            }
+            Err(error) => {
+                panic!("failed to update the contract visibility: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn update_visibility_excluding_registered_seller() {
+        let mut deps = mock_provenance_dependencies();
+        let contract_admin = deps.api.addr_make("contract-admin");
+        let seller_address = deps.api.addr_make("seller-address");
+        let other_seller_address = deps.api.addr_make("other-seller");
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: contract_admin.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: contract_admin,
+                funds: vec![],
+            },
+            UpdateVisibility {
+                private_sellers: true,
+                private_buyers: false,
+                allowed_sellers: vec![other_seller_address.to_string()],
+                allowed_buyers: vec![],
+                max_bid_count: Some(3),
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                panic!("failed to detect a visibility update that excludes a registered seller")
+            }
+            Err(error) => match error {
+                ContractError::IllegalVisibilityUpdate { address } => {
+                    assert_eq!(address, seller_address.to_string());
+                }
+                _ => {
+                    panic!(
+                        "an unexpected error was returned when a visibility update excluded a \
+                            registered seller"
+                    )
+                }
+            },
+        }
+    }
+}
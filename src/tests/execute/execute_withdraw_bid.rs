@@ -0,0 +1,167 @@
+#[cfg(test)]
+mod execute_withdraw_bid_tests {
+    use crate::contract::execute;
+    use crate::error::ContractError;
+    use crate::msg::ExecuteMsg::{AddBid, WithdrawBid};
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_contract_config, BidCollateral, BidCollateralConfig, BidList,
+        Config,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coin, coins, BankMsg, CosmosMsg, MessageInfo, Uint128};
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    #[test]
+    fn withdraw_bid_with_collateral() {
+        let mut deps = mock_provenance_dependencies();
+        let bidder_address = deps.api.addr_make("bidder-address");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: Some(BidCollateralConfig {
+                    denom: "nhash".to_string(),
+                    amount: Uint128::new(500),
+                    is_restricted_marker: false,
+                    acceptance_window: 3600,
+                }),
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        let add_bidder_message = AddBid {
+            agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
+        };
+        let add_bidder_info = MessageInfo {
+            sender: bidder_address.clone(),
+            funds: coins(500, "nhash"),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            add_bidder_info,
+            add_bidder_message,
+        )
+        .unwrap();
+        assert_eq!(
+            query_contract_state(deps.as_ref()).unwrap().bid_collateral,
+            vec![BidCollateral {
+                bidder_address: bidder_address.clone(),
+                collateral: coin(500, "nhash"),
+            }]
+        );
+
+        let info = MessageInfo {
+            sender: bidder_address.clone(),
+            funds: vec![],
+        };
+        match execute(deps.as_mut(), env, info, WithdrawBid { trade_id: None }) {
+            Ok(response) => {
+                assert_eq!(
+                    response.messages[0].msg,
+                    CosmosMsg::Bank(BankMsg::Send {
+                        to_address: bidder_address.to_string(),
+                        amount: coins(500, "nhash"),
+                    })
+                );
+                let contract_state = query_contract_state(deps.as_ref()).unwrap();
+                assert!(contract_state.bids.is_empty());
+                assert!(contract_state.bid_collateral.is_empty());
+            }
+            Err(error) => {
+                panic!("failed to withdraw bid: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn add_bid_without_collateral() {
+        let mut deps = mock_provenance_dependencies();
+        let info = MessageInfo {
+            sender: deps.api.addr_make("bidder-address"),
+            funds: coins(100, "nhash"),
+        };
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: Some(BidCollateralConfig {
+                    denom: "nhash".to_string(),
+                    amount: Uint128::new(500),
+                    is_restricted_marker: false,
+                    acceptance_window: 3600,
+                }),
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        let add_bidder_message = AddBid {
+            agreement_terms_hash: "buyer-mock-hash".to_string(),
+            price: None,
+            trade_id: None,
+        };
+        match execute(deps.as_mut(), env, info, add_bidder_message) {
+            Ok(_) => {
+                panic!("failed to detect a bid placed without the required collateral")
+            }
+            Err(error) => match error {
+                ContractError::InvalidBidCollateral { .. } => {}
+                _ => {
+                    panic!("an unexpected error was returned when bidding without collateral")
+                }
+            },
+        }
+    }
+}
@@ -1,5 +1,5 @@
 #[cfg(test)]
-mod instantiate_tests {
+mod instantiate_contract_tests {
     use crate::contract::instantiate;
     use crate::error::ContractError;
     use crate::msg::InstantiateContractMsg;
@@ -31,7 +31,14 @@ mod instantiate_tests {
             ],
             allowed_buyers: vec![],
             dealers: vec![dealer_address.to_string()],
-            max_buyer_count: 1,
+            max_buyer_count: Some(1),
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
         };
         let init_response = instantiate(deps.as_mut(), env, info, instantiate_msg);
         match init_response {
@@ -39,12 +46,17 @@ mod instantiate_tests {
                 let expected_config_attributes = Config {
                     use_private_sellers: true,
                     use_private_buyers: false,
-                    allowed_sellers: vec![seller_address_0.clone(), seller_address_1.clone()],
-                    allowed_buyers: vec![],
                     dealers: vec![dealer_address.clone()],
                     is_disabled: false,
-                    max_bid_count: 1,
+                    max_bid_count: Some(1),
                     contract_admin: admin_address.clone(),
+                    auction: None,
+                    bid_collateral: None,
+                    margin: None,
+                    fees: None,
+                    pool_eligibility: None,
+                    required_attributes: None,
+                    accepted_token_contracts: vec![],
                 };
                 assert_eq!(response.attributes.len(), 1);
                 assert_eq!(
@@ -59,6 +71,13 @@ mod instantiate_tests {
                     query_contract_state(deps.as_ref()).unwrap().config,
                     expected_config_attributes
                 );
+                let mut expected_allowed_sellers =
+                    vec![seller_address_0.clone(), seller_address_1.clone()];
+                expected_allowed_sellers.sort();
+                assert_eq!(
+                    query_contract_state(deps.as_ref()).unwrap().allowed_sellers,
+                    expected_allowed_sellers
+                );
                 let expected_version_info = VersionInfoV1 {
                     definition: CRATE_NAME.to_string(),
                     version: PACKAGE_VERSION.to_string(),
@@ -93,7 +112,14 @@ mod instantiate_tests {
             ],
             allowed_buyers: vec![],
             dealers: vec![deps.api.addr_make("dealer-address").to_string()],
-            max_buyer_count: 1,
+            max_buyer_count: Some(1),
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
         };
         let init_response = instantiate(deps.as_mut(), env, info, instantiate_msg);
         match init_response {
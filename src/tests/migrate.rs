@@ -0,0 +1 @@
+mod migrate_tests;
@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod migrate_contract_tests {
+    use crate::contract::{execute, migrate};
+    use crate::msg::ExecuteMsg::AcceptFinalizedPools;
+    use crate::msg::{AcceptedOffer, MigrateMsg};
+    use crate::storage::state_store::{
+        retrieve_allowed_buyer_list_state, retrieve_allowed_seller_list_state,
+        retrieve_contract_config, retrieve_optional_buyer_state, retrieve_seller_state, Config,
+    };
+    use crate::version_info::{get_version_info, set_version_info, VersionInfoV1, PACKAGE_VERSION};
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{Addr, MessageInfo, Storage};
+    use provwasm_mocks::mock_provenance_dependencies;
+
+    fn save_legacy_config(
+        storage: &mut dyn Storage,
+        use_private_buyers: bool,
+        allowed_sellers: &[Addr],
+        allowed_buyers: &[Addr],
+        max_bid_count: i32,
+        dealer: &Addr,
+        contract_admin: &Addr,
+    ) {
+        let addresses = |addresses: &[Addr]| {
+            addresses
+                .iter()
+                .map(|address| format!("\"{address}\""))
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        storage.set(
+            b"config",
+            format!(
+                "{{\"use_private_sellers\":{},\"use_private_buyers\":{},\"allowed_sellers\":[{}],\
+                \"allowed_buyers\":[{}],\"max_bid_count\":{},\"dealers\":[\"{}\"],\
+                \"is_disabled\":false,\"contract_admin\":\"{}\"}}",
+                !allowed_sellers.is_empty(),
+                use_private_buyers,
+                addresses(allowed_sellers),
+                addresses(allowed_buyers),
+                max_bid_count,
+                dealer,
+                contract_admin
+            )
+            .as_bytes(),
+        );
+        set_version_info(
+            storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.1.1".to_string(),
+            },
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn migrate_legacy_private_config() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let buyer_address = deps.api.addr_make("allowed-buyer-0");
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let admin_address = deps.api.addr_make("contract-admin");
+        let allowed_sellers = vec![seller_address];
+        let allowed_buyers = vec![buyer_address];
+        save_legacy_config(
+            &mut deps.storage,
+            true,
+            &allowed_sellers,
+            &allowed_buyers,
+            5,
+            &dealer_address,
+            &admin_address,
+        );
+
+        match migrate(deps.as_mut(), mock_env(), MigrateMsg::ContractUpgrade {}) {
+            Ok(_) => {
+                assert_eq!(
+                    retrieve_contract_config(&deps.storage).unwrap(),
+                    Config {
+                        use_private_sellers: true,
+                        use_private_buyers: true,
+                        max_bid_count: Some(5),
+                        dealers: vec![dealer_address],
+                        is_disabled: false,
+                        contract_admin: admin_address,
+                        auction: None,
+                        bid_collateral: None,
+                        margin: None,
+                        fees: None,
+                        pool_eligibility: None,
+                        required_attributes: None,
+                        accepted_token_contracts: vec![],
+                    }
+                );
+                assert_eq!(
+                    retrieve_allowed_seller_list_state(&deps.storage).unwrap(),
+                    allowed_sellers
+                );
+                assert_eq!(
+                    retrieve_allowed_buyer_list_state(&deps.storage).unwrap(),
+                    allowed_buyers
+                );
+                assert_eq!(
+                    get_version_info(&deps.storage).unwrap().version,
+                    PACKAGE_VERSION
+                );
+            }
+            Err(error) => {
+                panic!("failed to migrate a legacy private contract: {:?}", error)
+            }
+        }
+    }
+
+    #[test]
+    fn migrate_legacy_config_without_bid_cap() {
+        // A negative count on a private contract and any count on a public contract were never a
+        // usable cap, so both are migrated to an unlimited bid count
+        for (use_private_buyers, max_bid_count) in [(true, -1), (true, 0), (false, 5)] {
+            let mut deps = mock_provenance_dependencies();
+            let buyer_address = deps.api.addr_make("allowed-buyer-0");
+            let dealer_address = deps.api.addr_make("dealer-address");
+            let admin_address = deps.api.addr_make("contract-admin");
+            let allowed_buyers = if use_private_buyers {
+                vec![buyer_address]
+            } else {
+                vec![]
+            };
+            save_legacy_config(
+                &mut deps.storage,
+                use_private_buyers,
+                &[],
+                &allowed_buyers,
+                max_bid_count,
+                &dealer_address,
+                &admin_address,
+            );
+
+            match migrate(deps.as_mut(), mock_env(), MigrateMsg::ContractUpgrade {}) {
+                Ok(_) => {
+                    let config = retrieve_contract_config(&deps.storage).unwrap();
+                    assert_eq!(config.max_bid_count, None);
+                    assert_eq!(config.use_private_buyers, use_private_buyers);
+                    assert_eq!(
+                        retrieve_allowed_buyer_list_state(&deps.storage).unwrap(),
+                        allowed_buyers
+                    );
+                }
+                Err(error) => {
+                    panic!(
+                        "failed to migrate a legacy contract with a max bid count of {}: {:?}",
+                        max_bid_count, error
+                    )
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn accept_migrated_seller_offer_at_first_revision() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("allowed-seller-0");
+        let buyer_address = deps.api.addr_make("allowed-buyer-0");
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let admin_address = deps.api.addr_make("contract-admin");
+        save_legacy_config(
+            &mut deps.storage,
+            true,
+            std::slice::from_ref(&seller_address),
+            std::slice::from_ref(&buyer_address),
+            5,
+            &dealer_address,
+            &admin_address,
+        );
+        deps.storage.set(
+            b"seller",
+            format!(
+                "{{\"seller_address\":\"{seller_address}\",\"pool_denoms\":[\"test.denom.pool.0\"],\
+                \"offer_hash\":\"legacy-offer-hash\"}}"
+            )
+            .as_bytes(),
+        );
+        deps.storage.set(
+            b"buyer",
+            format!(
+                "{{\"buyer_address\":\"{buyer_address}\",\"buyer_has_accepted_pools\":false,\
+                \"agreement_terms_hash\":\"legacy-terms-hash\"}}"
+            )
+            .as_bytes(),
+        );
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg::ContractUpgrade {}).unwrap();
+        assert_eq!(
+            retrieve_seller_state(&deps.storage, &seller_address)
+                .unwrap()
+                .offer_revision,
+            1
+        );
+
+        // A buyer reviews a migrated offer at the same first revision as a newly added seller's
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: buyer_address.clone(),
+                funds: vec![],
+            },
+            AcceptFinalizedPools {
+                offers: vec![AcceptedOffer {
+                    seller_address: seller_address.to_string(),
+                    offer_hash: "legacy-offer-hash".to_string(),
+                    offer_revision: 1,
+                }],
+                trade_id: None,
+            },
+        ) {
+            Ok(_) => {
+                assert!(
+                    retrieve_optional_buyer_state(&deps.storage, &buyer_address)
+                        .unwrap()
+                        .unwrap()
+                        .buyer_has_accepted_pools
+                );
+            }
+            Err(error) => {
+                panic!(
+                    "failed to accept the offer of a migrated seller: {:?}",
+                    error
+                )
+            }
+        }
+    }
+}
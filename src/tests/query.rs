@@ -0,0 +1,4 @@
+mod query_escrow_reconciliation;
+mod query_history;
+mod query_permissions;
+mod query_simulate;
@@ -0,0 +1,345 @@
+#[cfg(test)]
+mod query_escrow_reconciliation_tests {
+    use crate::contract::query;
+    use crate::msg::QueryMsg::GetEscrowReconciliation;
+    use crate::msg::{
+        Cw20BalanceResponse, Cw20QueryMsg, Cw721OwnerOfResponse, Cw721QueryMsg,
+        EscrowMismatchReason, GetEscrowReconciliationResponse, PoolEscrow,
+    };
+    use crate::storage::state_store::{
+        save_bid_list_state, save_contract_config, save_seller_state, BidList, Config, PoolAsset,
+        Seller,
+    };
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{
+        coin, coins, from_json, to_json_binary, Addr, Binary, ContractResult, SystemResult,
+        Uint128, WasmQuery,
+    };
+    use provwasm_mocks::mock_provenance_dependencies;
+    use provwasm_std::types::cosmos::base::v1beta1::Coin;
+    use provwasm_std::types::provenance::marker::v1::{
+        Balance, QueryHoldingRequest, QueryHoldingResponse,
+    };
+
+    #[test]
+    fn query_escrow_reconciliation() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("seller-address");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![
+                    PoolAsset::Marker {
+                        denom: "test.denom.pool.escrowed".to_string(),
+                    },
+                    PoolAsset::Marker {
+                        denom: "test.denom.pool.withdrawn".to_string(),
+                    },
+                    PoolAsset::Marker {
+                        denom: "test.denom.pool.split".to_string(),
+                    },
+                ],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
+            let inner_deps = mock_provenance_dependencies();
+            let balance = |address: String| Balance {
+                address,
+                coins: vec![Coin {
+                    denom: message.id.clone(),
+                    amount: "5".to_string(),
+                }],
+            };
+            let balances = match message.id.as_str() {
+                "test.denom.pool.escrowed" => {
+                    vec![balance(mock_env().contract.address.to_string())]
+                }
+                "test.denom.pool.withdrawn" => {
+                    vec![balance(
+                        inner_deps.api.addr_make("seller-address").to_string(),
+                    )]
+                }
+                _ => vec![
+                    balance(mock_env().contract.address.to_string()),
+                    balance(inner_deps.api.addr_make("seller-address").to_string()),
+                ],
+            };
+            let response = QueryHoldingResponse {
+                balances,
+                pagination: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Holding".to_string(), cb);
+
+        let reconciliation: GetEscrowReconciliationResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetEscrowReconciliation { trade_id: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            reconciliation.pools,
+            vec![
+                PoolEscrow {
+                    seller_address: seller_address.clone(),
+                    denom: "test.denom.pool.escrowed".to_string(),
+                    token_id: None,
+                    expected_holder: env.contract.address.clone(),
+                    holder: Some(env.contract.address.to_string()),
+                    amount: Some(Uint128::new(5)),
+                    mismatch: None,
+                },
+                PoolEscrow {
+                    seller_address: seller_address.clone(),
+                    denom: "test.denom.pool.withdrawn".to_string(),
+                    token_id: None,
+                    expected_holder: env.contract.address.clone(),
+                    holder: Some(seller_address.to_string()),
+                    amount: Some(Uint128::new(5)),
+                    mismatch: Some(EscrowMismatchReason::WrongHolder),
+                },
+                PoolEscrow {
+                    seller_address: seller_address.clone(),
+                    denom: "test.denom.pool.split".to_string(),
+                    token_id: None,
+                    expected_holder: env.contract.address.clone(),
+                    holder: None,
+                    amount: None,
+                    mismatch: Some(EscrowMismatchReason::MultipleHolders),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn query_escrow_reconciliation_of_unmarked_pools() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("seller-address");
+        let other_seller_address = deps.api.addr_make("other-seller-address");
+        let cw20_address = deps.api.addr_make("cw20-token");
+        let cw721_address = deps.api.addr_make("cw721-loans");
+        let env = mock_env();
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![cw20_address.clone(), cw721_address.clone()],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![
+                    PoolAsset::Native {
+                        coin: coin(5, "ibc/pool"),
+                    },
+                    PoolAsset::Cw20 {
+                        contract: cw20_address.clone(),
+                        amount: Uint128::new(250),
+                    },
+                    PoolAsset::Cw721 {
+                        contract: cw721_address.clone(),
+                        token_id: "loan-1".to_string(),
+                    },
+                ],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        // The other seller's pools have been received but not finalized, and are held in escrow too
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: other_seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![
+                    PoolAsset::Native {
+                        coin: coin(4, "ibc/pool"),
+                    },
+                    PoolAsset::Cw721 {
+                        contract: cw721_address.clone(),
+                        token_id: "loan-2".to_string(),
+                    },
+                    PoolAsset::Cw721 {
+                        contract: cw721_address.clone(),
+                        token_id: "loan-burned".to_string(),
+                    },
+                ],
+            },
+        )
+        .unwrap();
+
+        // The contract only holds 7 of the 9 native coins escrowed across the sellers
+        deps.querier
+            .mock_querier
+            .bank
+            .update_balance(env.contract.address.clone(), coins(7, "ibc/pool"));
+        // The contract holds all of the CW20 tokens and the first NFT, while the second NFT was
+        // transferred back to its seller and the last one no longer exists
+        let cw20_contract = cw20_address.to_string();
+        let contract_address = env.contract.address.to_string();
+        let nft_owner = other_seller_address.to_string();
+        deps.querier
+            .mock_querier
+            .update_wasm(move |query| match query {
+                WasmQuery::Smart { contract_addr, msg } if *contract_addr == cw20_contract => {
+                    let Cw20QueryMsg::Balance { .. } = from_json(msg).unwrap();
+                    let response = Cw20BalanceResponse {
+                        balance: Uint128::new(250),
+                    };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+                }
+                WasmQuery::Smart { msg, .. } => {
+                    let Cw721QueryMsg::OwnerOf { token_id, .. } = from_json(msg).unwrap();
+                    let owner = match token_id.as_str() {
+                        "loan-1" => contract_address.clone(),
+                        "loan-2" => nft_owner.clone(),
+                        _ => {
+                            return SystemResult::Ok(ContractResult::Err(
+                                "token not found".to_string(),
+                            ))
+                        }
+                    };
+                    let response = Cw721OwnerOfResponse { owner };
+                    SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+                }
+                _ => panic!("unexpected wasm query"),
+            });
+
+        let reconciliation: GetEscrowReconciliationResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                GetEscrowReconciliation { trade_id: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let escrow = |seller_address: &Addr,
+                      denom: &Addr,
+                      token_id: &str,
+                      holder: Option<String>,
+                      mismatch: Option<EscrowMismatchReason>| PoolEscrow {
+            seller_address: seller_address.clone(),
+            denom: denom.to_string(),
+            token_id: Some(token_id.to_string()),
+            expected_holder: env.contract.address.clone(),
+            amount: holder.as_ref().map(|_| Uint128::one()),
+            holder,
+            mismatch,
+        };
+        assert_eq!(
+            reconciliation.pools,
+            vec![
+                PoolEscrow {
+                    seller_address: seller_address.clone(),
+                    denom: "ibc/pool".to_string(),
+                    token_id: None,
+                    expected_holder: env.contract.address.clone(),
+                    holder: Some(env.contract.address.to_string()),
+                    amount: Some(Uint128::new(7)),
+                    mismatch: Some(EscrowMismatchReason::InsufficientBalance),
+                },
+                PoolEscrow {
+                    seller_address: seller_address.clone(),
+                    denom: cw20_address.to_string(),
+                    token_id: None,
+                    expected_holder: env.contract.address.clone(),
+                    holder: Some(env.contract.address.to_string()),
+                    amount: Some(Uint128::new(250)),
+                    mismatch: None,
+                },
+                escrow(
+                    &seller_address,
+                    &cw721_address,
+                    "loan-1",
+                    Some(env.contract.address.to_string()),
+                    None,
+                ),
+                PoolEscrow {
+                    seller_address: other_seller_address.clone(),
+                    denom: "ibc/pool".to_string(),
+                    token_id: None,
+                    expected_holder: env.contract.address.clone(),
+                    holder: Some(env.contract.address.to_string()),
+                    amount: Some(Uint128::new(7)),
+                    mismatch: Some(EscrowMismatchReason::InsufficientBalance),
+                },
+                escrow(
+                    &other_seller_address,
+                    &cw721_address,
+                    "loan-2",
+                    Some(other_seller_address.to_string()),
+                    Some(EscrowMismatchReason::WrongHolder),
+                ),
+                escrow(
+                    &other_seller_address,
+                    &cw721_address,
+                    "loan-burned",
+                    None,
+                    Some(EscrowMismatchReason::NotHeld),
+                ),
+            ]
+        );
+    }
+}
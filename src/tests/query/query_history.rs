@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod query_history_tests {
+    use crate::contract::{execute, query};
+    use crate::msg::ExecuteMsg::{AddBid, WithdrawBid};
+    use crate::msg::GetHistoryResponse;
+    use crate::msg::QueryMsg::GetHistory;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_contract_config, Bid, BidList, Config, HistoryChange,
+        HistoryEntry,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::{mock_env, MockApi};
+    use cosmwasm_std::{from_json, to_json_string, MemoryStorage, MessageInfo, OwnedDeps};
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+
+    fn setup_trade(deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>) {
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        for bidder in 0..32 {
+            let bidder_address = deps.api.addr_make(&format!("bidder-{bidder}"));
+            execute(
+                deps.as_mut(),
+                mock_env(),
+                MessageInfo {
+                    sender: bidder_address,
+                    funds: vec![],
+                },
+                AddBid {
+                    agreement_terms_hash: format!("terms-hash-{bidder}"),
+                    price: None,
+                    trade_id: None,
+                },
+            )
+            .unwrap();
+        }
+    }
+
+    fn query_history_page(
+        deps: &OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Vec<HistoryEntry> {
+        let history: GetHistoryResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                GetHistory {
+                    trade_id: None,
+                    start_after,
+                    limit,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        history.entries
+    }
+
+    fn entry_ids(entries: &[HistoryEntry]) -> Vec<u64> {
+        entries.iter().map(|entry| entry.id).collect()
+    }
+
+    #[test]
+    fn query_history_pages() {
+        let mut deps = mock_provenance_dependencies();
+        setup_trade(&mut deps);
+
+        // A page holds 10 entries by default, oldest first
+        let first_page = query_history_page(&deps, None, None);
+        assert_eq!((1..=10).collect::<Vec<u64>>(), entry_ids(&first_page));
+        let second_page = query_history_page(&deps, Some(10), None);
+        assert_eq!((11..=20).collect::<Vec<u64>>(), entry_ids(&second_page));
+
+        // Limits above the maximum are capped at 30 entries
+        let capped_page = query_history_page(&deps, None, Some(100));
+        assert_eq!((1..=30).collect::<Vec<u64>>(), entry_ids(&capped_page));
+
+        // The last page only holds the remaining entries, and there are none after the last entry
+        let last_page = query_history_page(&deps, Some(28), Some(5));
+        assert_eq!(vec![29, 30, 31, 32], entry_ids(&last_page));
+        assert!(query_history_page(&deps, Some(32), None).is_empty());
+    }
+
+    #[test]
+    fn query_history_records_changed_entries() {
+        let mut deps = mock_provenance_dependencies();
+        setup_trade(&mut deps);
+        let bidder_address = deps.api.addr_make("bidder-5");
+        let bid_json = to_json_string(&Bid {
+            buyer_address: bidder_address.clone(),
+            agreement_terms_hash: "terms-hash-5".to_string(),
+            price: None,
+            submitted_at: mock_env().block.time,
+            revision: 1,
+        })
+        .unwrap();
+
+        // Only the bid that was added is recorded, rather than every bid on the trade
+        let added_entry = query_history_page(&deps, Some(5), Some(1));
+        assert_eq!(
+            vec![HistoryEntry {
+                id: 6,
+                actor: bidder_address.clone(),
+                action: "AddBid".to_string(),
+                block_height: mock_env().block.height,
+                block_time: mock_env().block.time,
+                changes: vec![HistoryChange {
+                    field: "bids".to_string(),
+                    key: Some(bidder_address.to_string()),
+                    before: None,
+                    after: Some(bid_json.clone()),
+                }],
+            }],
+            added_entry
+        );
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: bidder_address.clone(),
+                funds: vec![],
+            },
+            WithdrawBid { trade_id: None },
+        )
+        .unwrap();
+        let withdrawn_entry = query_history_page(&deps, Some(32), None);
+        assert_eq!(
+            vec![HistoryChange {
+                field: "bids".to_string(),
+                key: Some(bidder_address.to_string()),
+                before: Some(bid_json),
+                after: None,
+            }],
+            withdrawn_entry[0].changes
+        );
+    }
+}
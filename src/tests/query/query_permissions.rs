@@ -0,0 +1,478 @@
+#[cfg(test)]
+mod query_permissions_tests {
+    use crate::contract::{execute, query};
+    use crate::msg::ExecuteMsg;
+    use crate::msg::ExecuteMsg::{AddBid, DealerConfirm, DepositMargin};
+    use crate::msg::QueryMsg::GetPermissions;
+    use crate::msg::{Action, ActionPermission, GetPermissionsResponse, Role};
+    use crate::storage::state_store::{
+        save_allowed_seller_state, save_bid_list_state, save_buyer_state, save_contract_config,
+        save_fee_deposit_state, save_seller_state, save_token_data_state, BidCollateralConfig,
+        BidList, Buyer, Config, Fee, FeeConfig, FeeDeposit, MarginConfig, PoolAsset, Seller,
+        TokenData,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::{mock_env, MockApi};
+    use cosmwasm_std::{
+        coin, from_json, to_json_binary, to_json_string, Addr, Binary, Coin, ContractResult, Deps,
+        MemoryStorage, MessageInfo, OwnedDeps, SystemResult, Uint128,
+    };
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+    use provwasm_std::types::cosmos::base::v1beta1::Coin as ProvenanceCoin;
+    use provwasm_std::types::provenance::marker::v1::{
+        Balance, QueryHoldingRequest, QueryHoldingResponse,
+    };
+    use rust_decimal::Decimal;
+
+    fn query_permissions(deps: Deps, address: String) -> GetPermissionsResponse {
+        query_permissions_with_funds(deps, address, vec![])
+    }
+
+    fn query_permissions_with_funds(
+        deps: Deps,
+        address: String,
+        funds: Vec<Coin>,
+    ) -> GetPermissionsResponse {
+        from_json(
+            query(
+                deps,
+                mock_env(),
+                GetPermissions {
+                    trade_id: None,
+                    address,
+                    funds,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    }
+
+    fn find_action(response: &GetPermissionsResponse, action: &str) -> ActionPermission {
+        response
+            .actions
+            .iter()
+            .find(|permission| permission.action == action)
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn query_permissions_of_seller_and_admin() {
+        let mut deps = mock_provenance_dependencies();
+        let seller_address = deps.api.addr_make("seller-address");
+        let admin_address = deps.api.addr_make("contract-admin");
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: true,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: Some(3),
+                contract_admin: admin_address.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        save_allowed_seller_state(&mut deps.storage, &seller_address).unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        let seller_permissions = query_permissions(deps.as_ref(), seller_address.to_string());
+        assert_eq!(
+            seller_permissions.roles,
+            vec![Role::Seller, Role::AllowedSeller]
+        );
+        assert_eq!(
+            find_action(&seller_permissions, "FinalizePools"),
+            ActionPermission {
+                action: "FinalizePools".to_string(),
+                allowed: true,
+                reason: None,
+            }
+        );
+        assert_eq!(
+            find_action(&seller_permissions, "AddSeller"),
+            ActionPermission {
+                action: "AddSeller".to_string(),
+                allowed: false,
+                reason: Some(
+                    "The address has already been registered as a seller for this contract"
+                        .to_string()
+                ),
+            }
+        );
+        assert_eq!(
+            find_action(&seller_permissions, "RescindFinalizedPools"),
+            ActionPermission {
+                action: "RescindFinalizedPools".to_string(),
+                allowed: false,
+                reason: Some("Only a finalized pool can be rescinded".to_string()),
+            }
+        );
+
+        let admin_permissions = query_permissions(deps.as_ref(), admin_address.to_string());
+        assert_eq!(admin_permissions.roles, vec![Role::Admin]);
+        assert!(find_action(&admin_permissions, "MintTokens").allowed);
+        assert!(find_action(&admin_permissions, "AddAllowedSellers").allowed);
+        assert!(!find_action(&admin_permissions, "AddAllowedBuyers").allowed);
+        assert!(!find_action(&admin_permissions, "FinalizePools").allowed);
+    }
+
+    #[test]
+    fn query_permissions_of_every_action() {
+        let mut deps = mock_provenance_dependencies();
+        let config = default_config(&deps);
+        setup_trade(&mut deps, config);
+        let permissions =
+            query_permissions(deps.as_ref(), deps.api.addr_make("anyone").to_string());
+
+        // Every route that a message can be executed by is listed once, in the order of the table
+        let listed_actions: Vec<&str> = permissions
+            .actions
+            .iter()
+            .map(|permission| permission.action.as_str())
+            .collect();
+        let actions: Vec<&str> = Action::ALL.iter().map(|action| action.name()).collect();
+        assert_eq!(actions, listed_actions);
+
+        // The name of a route is the name its message is sent with
+        for msg in [
+            AddBid {
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                price: None,
+                trade_id: None,
+            },
+            DealerConfirm { trade_id: None },
+            DepositMargin { trade_id: None },
+        ] {
+            let action = msg.action();
+            assert!(Action::ALL.contains(&action));
+            assert!(to_json_string(&msg)
+                .unwrap()
+                .starts_with(&format!("{{\"{}\":", action.name())));
+        }
+    }
+
+    #[test]
+    fn query_permissions_of_disabled_trade() {
+        let mut deps = mock_provenance_dependencies();
+        let admin_address = deps.api.addr_make("contract-admin");
+
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: true,
+                max_bid_count: None,
+                contract_admin: admin_address.clone(),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+
+        let permissions = query_permissions(deps.as_ref(), admin_address.to_string());
+        assert!(find_action(&permissions, "CreateTrade").allowed);
+        assert!(permissions
+            .actions
+            .iter()
+            .filter(|permission| permission.action != "CreateTrade")
+            .all(|permission| !permission.allowed));
+    }
+
+    fn setup_trade(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        config: Config,
+    ) {
+        save_contract_config(&mut deps.storage, &config).unwrap();
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+    }
+
+    fn default_config(deps: &OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>) -> Config {
+        Config {
+            use_private_sellers: false,
+            use_private_buyers: false,
+            dealers: vec![deps.api.addr_make("dealer-address")],
+            is_disabled: false,
+            max_bid_count: None,
+            contract_admin: deps.api.addr_make("contract-admin"),
+            auction: None,
+            bid_collateral: None,
+            margin: None,
+            fees: None,
+            pool_eligibility: None,
+            required_attributes: None,
+            accepted_token_contracts: vec![],
+        }
+    }
+
+    /// The reason given by the query must be the error the route fails with when it is executed
+    fn assert_reason_matches_execute(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        sender: &Addr,
+        funds: Vec<Coin>,
+        msg: ExecuteMsg,
+    ) {
+        let action = msg.action().name();
+        let permissions =
+            query_permissions_with_funds(deps.as_ref(), sender.to_string(), funds.clone());
+        let permission = find_action(&permissions, action);
+        match execute(
+            deps.as_mut(),
+            mock_env(),
+            MessageInfo {
+                sender: sender.clone(),
+                funds,
+            },
+            msg,
+        ) {
+            Ok(_) => panic!("expected {action} to fail"),
+            Err(error) => {
+                assert!(!permission.allowed);
+                assert_eq!(Some(error.to_string()), permission.reason);
+            }
+        }
+    }
+
+    fn register_pool_holder(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        holder: &'static str,
+    ) {
+        let cb = Box::new(
+            move |bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+                let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
+                let address = match holder {
+                    "contract" => mock_env().contract.address.to_string(),
+                    _ => mock_provenance_dependencies()
+                        .api
+                        .addr_make(holder)
+                        .to_string(),
+                };
+                let response = QueryHoldingResponse {
+                    balances: vec![Balance {
+                        address,
+                        coins: vec![ProvenanceCoin {
+                            denom: message.id,
+                            amount: "5".to_string(),
+                        }],
+                    }],
+                    pagination: None,
+                };
+                SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+            },
+        );
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Holding".to_string(), cb);
+    }
+
+    #[test]
+    fn query_permissions_of_dealer_confirm() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let seller_address = deps.api.addr_make("seller-address");
+        let mut config = default_config(&deps);
+        config.fees = Some(FeeConfig {
+            denom: "nhash".to_string(),
+            is_restricted_marker: false,
+            buyer_fee: None,
+            seller_fee: Some(Fee::Flat {
+                amount: Uint128::new(100),
+            }),
+            platform_address: None,
+            platform_share_bps: 0,
+        });
+        setup_trade(&mut deps, config);
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: seller_address.clone(),
+                pools: vec![PoolAsset::Marker {
+                    denom: "test.denom.pool".to_string(),
+                }],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: deps.api.addr_make("buyer-address"),
+                buyer_has_accepted_pools: true,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.forward.market.token".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        register_pool_holder(&mut deps, "seller-address");
+
+        // The seller has not deposited their settlement fee
+        assert_reason_matches_execute(
+            &mut deps,
+            &dealer_address,
+            vec![],
+            DealerConfirm { trade_id: None },
+        );
+
+        // Once the fee is deposited the pool must still be held in escrow by the contract
+        save_fee_deposit_state(
+            &mut deps.storage,
+            &FeeDeposit {
+                payer: seller_address,
+                amount: Uint128::new(100),
+            },
+        )
+        .unwrap();
+        assert_reason_matches_execute(
+            &mut deps,
+            &dealer_address,
+            vec![],
+            DealerConfirm { trade_id: None },
+        );
+
+        register_pool_holder(&mut deps, "contract");
+        let permissions = query_permissions(deps.as_ref(), dealer_address.to_string());
+        assert!(find_action(&permissions, "DealerConfirm").allowed);
+    }
+
+    #[test]
+    fn query_permissions_of_bidder_with_collateral() {
+        let mut deps = mock_provenance_dependencies();
+        let bidder_address = deps.api.addr_make("bidder-address");
+        let mut config = default_config(&deps);
+        config.bid_collateral = Some(BidCollateralConfig {
+            denom: "nhash".to_string(),
+            amount: Uint128::new(50),
+            is_restricted_marker: false,
+            acceptance_window: 100,
+        });
+        setup_trade(&mut deps, config);
+
+        // A bid cannot be placed without attaching the collateral
+        let add_bid = || AddBid {
+            agreement_terms_hash: "mock-terms-hash".to_string(),
+            price: None,
+            trade_id: None,
+        };
+        assert_reason_matches_execute(&mut deps, &bidder_address, vec![], add_bid());
+        assert_reason_matches_execute(
+            &mut deps,
+            &bidder_address,
+            vec![coin(49, "nhash")],
+            add_bid(),
+        );
+
+        let permissions = query_permissions_with_funds(
+            deps.as_ref(),
+            bidder_address.to_string(),
+            vec![coin(50, "nhash")],
+        );
+        assert!(find_action(&permissions, "AddBid").allowed);
+    }
+
+    #[test]
+    fn query_permissions_of_margin_routes() {
+        let mut deps = mock_provenance_dependencies();
+        let dealer_address = deps.api.addr_make("dealer-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        let mut config = default_config(&deps);
+        config.margin = Some(MarginConfig {
+            denom: "nhash".to_string(),
+            maintenance_margin: Decimal::ONE,
+            margin_call_window: 100,
+        });
+        setup_trade(&mut deps, config);
+
+        // The mark cannot be taken before a bid has been accepted
+        assert_reason_matches_execute(
+            &mut deps,
+            &dealer_address,
+            vec![],
+            ExecuteMsg::MarkToMarket {
+                price: Decimal::from(5),
+                trade_id: None,
+            },
+        );
+
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address: buyer_address.clone(),
+                buyer_has_accepted_pools: false,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        let dealer_permissions = query_permissions(deps.as_ref(), dealer_address.to_string());
+        assert!(find_action(&dealer_permissions, "MarkToMarket").allowed);
+        assert!(find_action(&dealer_permissions, "DeclareMarginDefault").allowed);
+        assert!(!find_action(&dealer_permissions, "DepositMargin").allowed);
+
+        // A deposit must attach the margin denom
+        assert_reason_matches_execute(
+            &mut deps,
+            &buyer_address,
+            vec![coin(10, "nusd")],
+            DepositMargin { trade_id: None },
+        );
+        let buyer_permissions = query_permissions_with_funds(
+            deps.as_ref(),
+            buyer_address.to_string(),
+            vec![coin(10, "nhash")],
+        );
+        assert_eq!(buyer_permissions.roles, vec![Role::Buyer]);
+        assert!(find_action(&buyer_permissions, "DepositMargin").allowed);
+        assert!(!find_action(&buyer_permissions, "AcceptFinalizedPools").allowed);
+        assert!(!find_action(&buyer_permissions, "MarkToMarket").allowed);
+    }
+}
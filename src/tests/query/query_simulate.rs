@@ -0,0 +1,443 @@
+#[cfg(test)]
+mod query_simulate_tests {
+    use crate::contract::query;
+    use crate::msg::QueryMsg::{SimulateDealerConfirm, SimulateFinalizePools};
+    use crate::msg::{Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, SimulationResponse};
+    use crate::query::contract_state::query_contract_state;
+    use crate::storage::state_store::{
+        save_bid_list_state, save_buyer_state, save_contract_config, save_seller_state,
+        save_token_data_state, BidList, Buyer, Config, PoolAsset, Seller, TokenData,
+    };
+    use crate::version_info::{set_version_info, VersionInfoV1};
+    use cosmwasm_std::testing::{mock_env, MockApi};
+    use cosmwasm_std::{
+        coins, from_json, to_json_binary, BankMsg, Binary, ContractResult, MemoryStorage,
+        OwnedDeps, SystemResult, Uint128, WasmMsg, WasmQuery,
+    };
+    use provwasm_mocks::{mock_provenance_dependencies, MockProvenanceQuerier};
+    use provwasm_std::shim::Any;
+    use provwasm_std::types::cosmos::auth::v1beta1::BaseAccount;
+    use provwasm_std::types::cosmos::base::v1beta1::Coin;
+    use provwasm_std::types::provenance::marker::v1::{
+        Access, AccessGrant, Balance, MarkerAccount, MarkerStatus, MarkerType, MsgTransferRequest,
+        QueryHoldingRequest, QueryHoldingResponse, QueryMarkerRequest, QueryMarkerResponse,
+    };
+
+    fn setup_seller(deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>) {
+        let seller_address = deps.api.addr_make("seller-address");
+        save_contract_config(
+            &mut deps.storage,
+            &Config {
+                use_private_sellers: false,
+                use_private_buyers: false,
+                dealers: vec![deps.api.addr_make("dealer-address")],
+                is_disabled: false,
+                max_bid_count: None,
+                contract_admin: deps.api.addr_make("contract-admin"),
+                auction: None,
+                bid_collateral: None,
+                margin: None,
+                fees: None,
+                pool_eligibility: None,
+                required_attributes: None,
+                accepted_token_contracts: vec![],
+            },
+        )
+        .unwrap();
+        save_bid_list_state(&mut deps.storage, &BidList { bids: vec![] }).unwrap();
+        set_version_info(
+            &mut deps.storage,
+            &VersionInfoV1 {
+                definition: "mock".to_string(),
+                version: "0.0.0".to_string(),
+            },
+        )
+        .unwrap();
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address,
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        let cb = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryHoldingRequest::try_from(bin.clone()).unwrap();
+            let inner_deps = mock_provenance_dependencies();
+            let response = QueryHoldingResponse {
+                balances: vec![Balance {
+                    address: inner_deps.api.addr_make("seller-address").to_string(),
+                    coins: vec![Coin {
+                        denom: message.id,
+                        amount: "2".to_string(),
+                    }],
+                }],
+                pagination: None,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Holding".to_string(), cb);
+
+        let cb_marker = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+            let marker = MarkerAccount {
+                base_account: None,
+                manager: "".to_string(),
+                access_control: vec![AccessGrant {
+                    address: mock_env().contract.address.to_string(),
+                    permissions: vec![Access::Transfer as i32],
+                }],
+                status: MarkerStatus::Active.into(),
+                denom: message.id,
+                supply: "2".to_string(),
+                marker_type: MarkerType::Restricted.into(),
+                supply_fixed: true,
+                allow_governance_control: false,
+                allow_forced_transfer: false,
+                required_attributes: vec![],
+            };
+            let response = QueryMarkerResponse {
+                marker: Some(Any {
+                    type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                    value: marker.to_proto_bytes(),
+                }),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb_marker);
+    }
+
+    #[test]
+    fn simulate_finalize_pools() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let seller_address = deps.api.addr_make("seller-address");
+        let env = mock_env();
+
+        let simulation: SimulationResponse = from_json(
+            query(
+                deps.as_ref(),
+                env.clone(),
+                SimulateFinalizePools {
+                    trade_id: None,
+                    seller: seller_address.to_string(),
+                    pool_denoms: vec!["test.denom.pool.0".to_string()],
+                    funds: vec![],
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            simulation,
+            SimulationResponse {
+                transfers: vec![MsgTransferRequest {
+                    amount: Some(Coin {
+                        denom: "test.denom.pool.0".to_string(),
+                        amount: "2".to_string(),
+                    }),
+                    administrator: env.contract.address.to_string(),
+                    from_address: seller_address.to_string(),
+                    to_address: env.contract.address.to_string(),
+                }],
+                bank_sends: vec![],
+                token_transfers: vec![],
+                error: None,
+            }
+        );
+
+        // The simulation must not finalize the pools of the seller
+        assert!(query_contract_state(deps.as_ref()).unwrap().sellers[0]
+            .pools
+            .is_empty());
+    }
+
+    #[test]
+    fn simulate_finalize_pools_of_another_owner() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let other_seller_address = deps.api.addr_make("other-seller-address");
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address: other_seller_address.clone(),
+                pools: vec![],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+
+        let simulation: SimulationResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                SimulateFinalizePools {
+                    trade_id: None,
+                    seller: other_seller_address.to_string(),
+                    pool_denoms: vec!["test.denom.pool.0".to_string()],
+                    funds: vec![],
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            simulation,
+            SimulationResponse {
+                transfers: vec![],
+                bank_sends: vec![],
+                token_transfers: vec![],
+                error: Some(
+                    "Only coin owned by the seller can be added to the list of proposed pools"
+                        .to_string()
+                ),
+            }
+        );
+    }
+
+    #[test]
+    fn simulate_dealer_confirm_before_pools_are_finalized() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+
+        let simulation: SimulationResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                SimulateDealerConfirm {
+                    trade_id: None,
+                    dealer: deps.api.addr_make("dealer-address").to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            simulation,
+            SimulationResponse {
+                transfers: vec![],
+                bank_sends: vec![],
+                token_transfers: vec![],
+                error: Some("The seller must finalize the pool list and the buyer must accept it before confirmation is allowed".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn simulate_finalize_native_pools() {
+        let mut deps = mock_provenance_dependencies();
+        setup_seller(&mut deps);
+        let seller_address = deps.api.addr_make("seller-address");
+
+        // Native coins are deposited with the message, so there is nothing to transfer
+        let simulation: SimulationResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                SimulateFinalizePools {
+                    trade_id: None,
+                    seller: seller_address.to_string(),
+                    pool_denoms: vec![],
+                    funds: coins(5, "ibc/pool"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            simulation,
+            SimulationResponse {
+                transfers: vec![],
+                bank_sends: vec![],
+                token_transfers: vec![],
+                error: None,
+            }
+        );
+    }
+
+    fn setup_accepted_pool(
+        deps: &mut OwnedDeps<MemoryStorage, MockApi, MockProvenanceQuerier>,
+        pool: PoolAsset,
+    ) {
+        setup_seller(deps);
+        let seller_address = deps.api.addr_make("seller-address");
+        let buyer_address = deps.api.addr_make("buyer-address");
+        save_seller_state(
+            &mut deps.storage,
+            &Seller {
+                seller_address,
+                pools: vec![pool],
+                offer_hash: "mock-offer-hash".to_string(),
+                scope_addresses: vec![],
+                offer_revision: 1,
+                offer_updated_at: mock_env().block.time,
+                received_pools: vec![],
+            },
+        )
+        .unwrap();
+        save_buyer_state(
+            &mut deps.storage,
+            &Buyer {
+                buyer_address,
+                buyer_has_accepted_pools: true,
+                agreement_terms_hash: "mock-terms-hash".to_string(),
+                token_amount: Uint128::new(10),
+            },
+        )
+        .unwrap();
+        save_token_data_state(
+            &mut deps.storage,
+            &TokenData {
+                token_denom: "test.token.fm".to_string(),
+                token_count: Uint128::new(10),
+            },
+        )
+        .unwrap();
+
+        // The forward market marker must have a base account to receive the pools
+        let cb_marker = Box::new(|bin: &Binary| -> SystemResult<ContractResult<Binary>> {
+            let message = QueryMarkerRequest::try_from(bin.clone()).unwrap();
+            let inner_deps = mock_provenance_dependencies();
+            let marker = MarkerAccount {
+                base_account: Some(BaseAccount {
+                    address: inner_deps.api.addr_make("base_addr").to_string(),
+                    pub_key: None,
+                    account_number: 1,
+                    sequence: 0,
+                }),
+                manager: "".to_string(),
+                access_control: vec![],
+                status: MarkerStatus::Active.into(),
+                denom: message.id,
+                supply: "10".to_string(),
+                marker_type: MarkerType::Restricted.into(),
+                supply_fixed: true,
+                allow_governance_control: false,
+                allow_forced_transfer: false,
+                required_attributes: vec![],
+            };
+            let response = QueryMarkerResponse {
+                marker: Some(Any {
+                    type_url: "/provenance.marker.v1.MarkerAccount".to_string(),
+                    value: marker.to_proto_bytes(),
+                }),
+            };
+            SystemResult::Ok(ContractResult::Ok(to_json_binary(&response).unwrap()))
+        });
+        deps.querier
+            .registered_custom_queries
+            .insert("/provenance.marker.v1.Query/Marker".to_string(), cb_marker);
+    }
+
+    #[test]
+    fn simulate_dealer_confirm_with_native_pool() {
+        let mut deps = mock_provenance_dependencies();
+        setup_accepted_pool(
+            &mut deps,
+            PoolAsset::Native {
+                coin: cosmwasm_std::Coin::new(5u128, "ibc/pool"),
+            },
+        );
+        deps.querier
+            .mock_querier
+            .bank
+            .update_balance(mock_env().contract.address, coins(5, "ibc/pool"));
+
+        let simulation: SimulationResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                SimulateDealerConfirm {
+                    trade_id: None,
+                    dealer: deps.api.addr_make("dealer-address").to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            simulation,
+            SimulationResponse {
+                transfers: vec![],
+                bank_sends: vec![BankMsg::Send {
+                    to_address: deps.api.addr_make("base_addr").to_string(),
+                    amount: coins(5, "ibc/pool"),
+                }],
+                token_transfers: vec![],
+                error: None,
+            }
+        );
+        assert!(query_contract_state(deps.as_ref())
+            .unwrap()
+            .settlement_data
+            .is_none());
+    }
+
+    #[test]
+    fn simulate_dealer_confirm_with_cw20_pool() {
+        let mut deps = mock_provenance_dependencies();
+        let token_address = deps.api.addr_make("cw20-token");
+        setup_accepted_pool(
+            &mut deps,
+            PoolAsset::Cw20 {
+                contract: token_address.clone(),
+                amount: Uint128::new(250),
+            },
+        );
+        deps.querier.mock_querier.update_wasm(|query| match query {
+            WasmQuery::Smart { msg, .. } => match from_json(msg).unwrap() {
+                Cw20QueryMsg::Balance { .. } => SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&Cw20BalanceResponse {
+                        balance: Uint128::new(250),
+                    })
+                    .unwrap(),
+                )),
+            },
+            _ => panic!("unexpected wasm query"),
+        });
+
+        let simulation: SimulationResponse = from_json(
+            query(
+                deps.as_ref(),
+                mock_env(),
+                SimulateDealerConfirm {
+                    trade_id: None,
+                    dealer: deps.api.addr_make("dealer-address").to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            simulation,
+            SimulationResponse {
+                transfers: vec![],
+                bank_sends: vec![],
+                token_transfers: vec![WasmMsg::Execute {
+                    contract_addr: token_address.to_string(),
+                    msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: deps.api.addr_make("base_addr").to_string(),
+                        amount: Uint128::new(250),
+                    })
+                    .unwrap(),
+                    funds: vec![],
+                }],
+                error: None,
+            }
+        );
+    }
+}
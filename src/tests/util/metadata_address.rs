@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod metadata_address_tests {
+    use crate::error::ContractError;
+    use crate::util::metadata_address::{KeyType, MetadataAddress};
+    use uuid::Uuid;
+
+    const SCOPE_UUID: &str = "91978ba2-5f35-459a-86a7-feca1b0512e0";
+    const SESSION_UUID: &str = "5803f8bc-6067-4eb5-951f-2121671c2ec0";
+    const SCOPE_SPEC_UUID: &str = "dc83ea70-eacd-40fe-9adf-1cf6148bf8a2";
+    const CONTRACT_SPEC_UUID: &str = "def6bc0a-c9dd-4874-948f-5206e6060a84";
+    const RECORD_NAME: &str = "recordname";
+
+    fn uuid(value: &str) -> Uuid {
+        Uuid::parse_str(value).unwrap()
+    }
+
+    fn assert_round_trip(address: MetadataAddress, expected_bech32: &str, key_type: KeyType) {
+        assert_eq!(address.bech32, expected_bech32);
+        assert_eq!(address.key_type, key_type);
+        assert_eq!(
+            MetadataAddress::from_bech32(expected_bech32).unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn scope_address_round_trip() {
+        assert_round_trip(
+            MetadataAddress::scope(uuid(SCOPE_UUID)).unwrap(),
+            "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwel",
+            KeyType::Scope,
+        );
+    }
+
+    #[test]
+    fn session_address_round_trip() {
+        let address = MetadataAddress::session(uuid(SCOPE_UUID), uuid(SESSION_UUID)).unwrap();
+        assert_eq!(address.primary_uuid(), uuid(SCOPE_UUID));
+        assert_round_trip(
+            address,
+            "session1qxge0zaztu65tx5x5llv5xc9zts9sqlch3sxwn44j50jzgt8rshvqyfrjcr",
+            KeyType::Session,
+        );
+    }
+
+    #[test]
+    fn record_address_round_trip() {
+        assert_round_trip(
+            MetadataAddress::record(uuid(SCOPE_UUID), RECORD_NAME).unwrap(),
+            "record1q2ge0zaztu65tx5x5llv5xc9ztsw42dq2jdvmdazuwzcaddhh8gmu3mcze3",
+            KeyType::Record,
+        );
+        // Record names are trimmed and lowercased before they are hashed
+        assert_eq!(
+            MetadataAddress::record(uuid(SCOPE_UUID), " RecordName ").unwrap(),
+            MetadataAddress::record(uuid(SCOPE_UUID), RECORD_NAME).unwrap()
+        );
+    }
+
+    #[test]
+    fn specification_addresses_round_trip() {
+        assert_round_trip(
+            MetadataAddress::scope_specification(uuid(SCOPE_SPEC_UUID)).unwrap(),
+            "scopespec1qnwg86nsatx5pl56muw0v9ytlz3qu3jx6m",
+            KeyType::ScopeSpecification,
+        );
+        assert_round_trip(
+            MetadataAddress::contract_specification(uuid(CONTRACT_SPEC_UUID)).unwrap(),
+            "contractspec1q000d0q2e8w5say53afqdesxp2zqzkr4fn",
+            KeyType::ContractSpecification,
+        );
+        assert_round_trip(
+            MetadataAddress::record_specification(uuid(CONTRACT_SPEC_UUID), RECORD_NAME).unwrap(),
+            "recspec1qh00d0q2e8w5say53afqdesxp2zw42dq2jdvmdazuwzcaddhh8gmuqhez44",
+            KeyType::RecordSpecification,
+        );
+    }
+
+    #[test]
+    fn invalid_metadata_addresses() {
+        let invalid_addresses = vec![
+            // The scope bytes with the session prefix
+            "session1qzge0zaztu65tx5x5llv5xc9ztsq0tjhln",
+            // A bad checksum
+            "scope1qzge0zaztu65tx5x5llv5xc9ztsqxlkwem",
+            // Not a bech32 address
+            "scope",
+        ];
+        for address in invalid_addresses {
+            match MetadataAddress::from_bech32(address) {
+                Ok(_) => panic!("failed to detect the invalid metadata address {address}"),
+                Err(error) => match error {
+                    ContractError::InvalidMetadataAddress { .. } => {}
+                    _ => panic!("an unexpected error was returned for {address}"),
+                },
+            }
+        }
+
+        match MetadataAddress::record(uuid(SCOPE_UUID), "  ") {
+            Ok(_) => panic!("failed to detect an empty record name"),
+            Err(error) => match error {
+                ContractError::InvalidMetadataAddress { .. } => {}
+                _ => panic!("an unexpected error was returned for an empty record name"),
+            },
+        }
+    }
+}
@@ -1,28 +1,51 @@
 use crate::error::ContractError;
 use crate::error::ContractError::{
-    IllegalConfigUpdate, InvalidDenom, InvalidDenomOwnership, UnauthorizedConfigUpdate,
+    EscrowMismatch, IllegalConfigUpdate, IllegalContractExecution, InactiveMarker,
+    InvalidBidAllocation, InvalidBidCollateral, InvalidContractExecution, InvalidDenom,
+    InvalidDenomOwnership, InvalidMetadataAddress, InvalidPoolScopeCount,
+    InvalidScopeSpecification, InvalidVisibilityConfig, MaxBuyersReached, MissingMarkerAccess,
+    MissingMarkerBaseAccount, MissingRequiredAttribute, UnauthorizedConfigUpdate,
+    UnauthorizedPrivateBuyer, UnknownTrade,
+};
+use crate::msg::{
+    Cw20BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw721ExecuteMsg, Cw721OwnerOfResponse,
+    Cw721QueryMsg, EscrowMismatchReason, PoolEscrow, SettlementFee,
 };
-use crate::msg::KeyType::Session;
-use crate::msg::{KeyType, MetadataAddress};
 use crate::storage::state_store::{
-    retrieve_contract_config, retrieve_optional_buyer_state, retrieve_optional_seller_state,
-    save_contract_config, Config,
+    clear_fee_deposit_state, clear_margin_account_state, is_allowed_buyer,
+    remove_bid_collateral_state, retrieve_bid_collateral_list_state, retrieve_buyer_list_state,
+    retrieve_contract_config, retrieve_fee_deposit_list_state, retrieve_latest_bid_revision,
+    retrieve_margin_account_list_state, retrieve_optional_bid_collateral_state,
+    retrieve_optional_buyer_state, retrieve_optional_fee_deposit_state,
+    retrieve_optional_margin_account_state, retrieve_optional_seller_state,
+    retrieve_optional_settlement_data_state, retrieve_optional_token_data_state,
+    retrieve_seller_list_state, retrieve_trade_count, save_bid_collateral_state,
+    save_contract_config, save_margin_account_state, BidCollateral, BidList, Config, Fee,
+    MarginAccount, MarginConfig, PoolAsset, PoolEligibilityConfig, BUYER_STATE, SELLERS,
 };
-use bech32::ToBase32;
+use crate::util::metadata_address::MetadataAddress;
 use cosmwasm_std::{
-    Addr, CosmosMsg, DepsMut, Empty, MessageInfo, QuerierWrapper, Response, StdError, StdResult,
-    Uint128,
+    coins, to_json_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo,
+    QuerierWrapper, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
 use provwasm_std::types::cosmos::base::query::v1beta1::PageRequest;
 use provwasm_std::types::cosmos::base::v1beta1::Coin;
+use provwasm_std::types::provenance::attribute::v1::AttributeQuerier;
 use provwasm_std::types::provenance::marker::v1::{
     Access, AccessGrant, MarkerAccount, MarkerQuerier, MarkerStatus, MarkerType,
     MsgActivateRequest, MsgAddMarkerRequest, MsgFinalizeRequest, MsgTransferRequest,
     MsgWithdrawRequest, QueryHoldingRequest, QueryHoldingResponse,
 };
 use provwasm_std::types::provenance::metadata::v1::{MetadataQuerier, ValueOwnershipResponse};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
 use uuid::Uuid;
 
+/// The number of scopes requested per page when listing the scopes owned by a pool marker
+const SCOPE_PAGE_LIMIT: u64 = 100;
+
 pub fn create_mint_tokens_messages(
     token_denom: String,
     token_count: Uint128,
@@ -58,6 +81,7 @@ pub fn create_mint_tokens_messages(
         ],
     });
 
+    #[allow(deprecated)]
     messages.push(CosmosMsg::from(MsgAddMarkerRequest {
         amount: Some(coin),
         manager: contract_address.clone(),
@@ -92,7 +116,7 @@ pub fn create_transfer_tokens_message(
     amount: Uint128,
     owner_address: String,
 ) -> Vec<CosmosMsg> {
-    return vec![CosmosMsg::from(MsgWithdrawRequest {
+    vec![CosmosMsg::from(MsgWithdrawRequest {
         denom: denom.to_string(),
         administrator: contract_address.clone(),
         to_address: owner_address.clone(),
@@ -100,7 +124,43 @@ pub fn create_transfer_tokens_message(
             denom: denom.clone(),
             amount: amount.to_string(),
         }],
-    })];
+    })]
+}
+
+/// Splits the token count across the accepted bids in proportion to each bid's allocation weight.
+/// Any tokens left over from rounding down are handed out one at a time in the order of the bids.
+pub fn allocate_tokens_pro_rata(
+    token_count: Uint128,
+    allocations: &[Uint128],
+) -> Result<Vec<Uint128>, ContractError> {
+    if allocations.is_empty() || allocations.iter().any(|allocation| allocation.is_zero()) {
+        return Err(InvalidBidAllocation);
+    }
+    let total_allocation = allocations
+        .iter()
+        .try_fold(Uint128::zero(), |total, allocation| {
+            total.checked_add(*allocation)
+        })
+        .map_err(StdError::overflow)?;
+
+    let mut token_amounts: Vec<Uint128> = allocations
+        .iter()
+        .map(|allocation| token_count.multiply_ratio(*allocation, total_allocation))
+        .collect();
+    let allocated: Uint128 = token_amounts.iter().sum();
+    let remainder = (token_count - allocated).u128() as usize;
+    for token_amount in token_amounts.iter_mut().take(remainder) {
+        *token_amount += Uint128::one();
+    }
+
+    // Every accepted buyer must receive at least one token
+    if token_amounts
+        .iter()
+        .any(|token_amount| token_amount.is_zero())
+    {
+        return Err(InvalidBidAllocation);
+    }
+    Ok(token_amounts)
 }
 
 pub fn get_owned_scopes(
@@ -126,37 +186,133 @@ pub fn get_owned_scopes(
 pub fn get_marker(id: String, querier: &MarkerQuerier<Empty>) -> StdResult<MarkerAccount> {
     let response = querier.marker(id)?;
     if let Some(marker) = response.marker {
-        return if let Ok(account) = MarkerAccount::try_from(marker) {
+        if let Ok(account) = MarkerAccount::try_from(marker) {
             Ok(account)
         } else {
             Err(StdError::generic_err("unable to type-cast marker account"))
-        };
+        }
     } else {
         Err(StdError::generic_err("no marker found for id"))
     }
 }
 
-pub fn encode_bech32(key_type: KeyType, bytes: &Vec<u8>) -> Result<String, bech32::Error> {
-    bech32::encode(
-        key_type.to_str(),
-        bytes.to_base32(),
-        bech32::Variant::Bech32,
-    )
-}
+/// Checks that the marker is active and that the contract has been granted every access it needs to
+/// move the marker's coins, so that a transfer or withdraw fails with an error naming what is missing
+/// rather than an error from the marker module
+pub fn validate_marker_access(
+    deps: &DepsMut,
+    denom: &str,
+    contract_address: &str,
+    required_access: &[Access],
+) -> Result<(), ContractError> {
+    let marker = get_marker(denom.to_string(), &MarkerQuerier::new(&deps.querier))?;
+    if marker.status != MarkerStatus::Active as i32 {
+        return Err(InactiveMarker {
+            denom: denom.to_string(),
+            status: MarkerStatus::try_from(marker.status)
+                .unwrap_or(MarkerStatus::Unspecified)
+                .as_str_name()
+                .to_string(),
+        });
+    }
 
-pub fn scope(scope_uuid: Uuid) -> Result<MetadataAddress, ContractError> {
-    let key_type_byte = KeyType::Scope as u8;
-    let bytes = [key_type_byte]
+    let granted = marker
+        .access_control
         .iter()
-        .cloned()
-        .chain(hex::decode(scope_uuid.simple().encode_lower(&mut Uuid::encode_buffer())).unwrap())
-        .collect::<Vec<u8>>();
-    let addr = encode_bech32(Session, &bytes).unwrap();
-    Ok(MetadataAddress {
-        bech32: addr,
-        bytes,
-        key_type: KeyType::Scope,
-    })
+        .filter(|grant| grant.address == contract_address)
+        .flat_map(|grant| grant.permissions.iter())
+        .collect::<Vec<_>>();
+    if let Some(access) = required_access
+        .iter()
+        .find(|access| !granted.contains(&&(**access as i32)))
+    {
+        return Err(MissingMarkerAccess {
+            denom: denom.to_string(),
+            access: access.as_str_name().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the addresses of every scope owned by the pool marker, checking that the number of scopes
+/// and their scope specifications meet the pool eligibility requirements
+pub fn get_eligible_pool_scopes(
+    deps: &DepsMut,
+    denom: &str,
+    pool_eligibility: &PoolEligibilityConfig,
+) -> Result<Vec<String>, ContractError> {
+    let pool_marker = get_marker(denom.to_string(), &MarkerQuerier::new(&deps.querier))?;
+    let pool_address = match pool_marker.base_account {
+        None => {
+            return Err(MissingMarkerBaseAccount {
+                denom: denom.to_string(),
+            })
+        }
+        Some(base_account) => base_account.address,
+    };
+
+    // Page through the scopes the marker owns, stopping early once there are more than allowed
+    let mut scope_uuids = vec![];
+    loop {
+        let page = get_owned_scopes(
+            pool_address.clone(),
+            &deps.querier,
+            scope_uuids.len() as u64,
+            SCOPE_PAGE_LIMIT,
+        )?;
+        let page_size = page.scope_uuids.len() as u64;
+        scope_uuids.extend(page.scope_uuids);
+        if page_size < SCOPE_PAGE_LIMIT
+            || scope_uuids.len() as u64 > pool_eligibility.max_scope_count as u64
+        {
+            break;
+        }
+    }
+
+    let scope_count = scope_uuids.len() as u64;
+    if scope_count < pool_eligibility.min_scope_count as u64
+        || scope_count > pool_eligibility.max_scope_count as u64
+    {
+        return Err(InvalidPoolScopeCount {
+            denom: denom.to_string(),
+            count: scope_count,
+        });
+    }
+
+    let metadata_querier = MetadataQuerier::new(&deps.querier);
+    let mut scope_addresses = vec![];
+    for scope_uuid in scope_uuids {
+        let scope_address = MetadataAddress::scope(Uuid::parse_str(&scope_uuid).map_err(|e| {
+            InvalidMetadataAddress {
+                message: format!("{e:?}"),
+            }
+        })?)?
+        .bech32;
+
+        // Every scope must use one of the allowed scope specifications when any are configured
+        if !pool_eligibility.allowed_scope_specs.is_empty() {
+            let scope_spec_address = metadata_querier
+                .scope(
+                    scope_address.clone(),
+                    "".to_string(),
+                    "".to_string(),
+                    false,
+                    false,
+                    false,
+                    false,
+                )?
+                .scope
+                .and_then(|wrapper| wrapper.scope_spec_id_info)
+                .map(|spec_info| spec_info.scope_spec_addr);
+            match scope_spec_address {
+                Some(spec_address)
+                    if pool_eligibility.allowed_scope_specs.contains(&spec_address) => {}
+                _ => return Err(InvalidScopeSpecification { scope_address }),
+            }
+        }
+        scope_addresses.push(scope_address);
+    }
+    Ok(scope_addresses)
 }
 
 pub fn get_balance(deps: &DepsMut, denom: String) -> Result<HeldCoin, ContractError> {
@@ -196,42 +352,680 @@ pub fn get_balance(deps: &DepsMut, denom: String) -> Result<HeldCoin, ContractEr
     })
 }
 
-pub fn is_seller(deps: &DepsMut, info: &MessageInfo) -> Result<bool, ContractError> {
-    match retrieve_optional_seller_state(deps.storage)? {
-        None => Ok(false),
-        Some(seller) => Ok(seller.seller_address == info.sender),
+/// Compares the holding of a marker pool with the escrow of the contract. Unlike [get_balance], a pool
+/// that is not held by exactly one address is reported rather than returned as an error
+pub fn reconcile_pool_escrow(
+    querier: &QuerierWrapper<Empty>,
+    contract_address: &Addr,
+    seller_address: &Addr,
+    denom: &str,
+) -> Result<PoolEscrow, ContractError> {
+    let holding_response: QueryHoldingResponse = querier.query(
+        &QueryHoldingRequest {
+            id: denom.to_string(),
+            pagination: None,
+        }
+        .into(),
+    )?;
+    let mut pool_escrow = PoolEscrow {
+        seller_address: seller_address.clone(),
+        denom: denom.to_string(),
+        token_id: None,
+        expected_holder: contract_address.clone(),
+        holder: None,
+        amount: None,
+        mismatch: None,
+    };
+    match holding_response.balances.as_slice() {
+        [] => pool_escrow.mismatch = Some(EscrowMismatchReason::NotHeld),
+        [balance] => {
+            let amount = balance
+                .coins
+                .iter()
+                .filter(|coin| coin.denom == denom)
+                .map(|coin| Uint128::from_str(&coin.amount))
+                .sum::<StdResult<Uint128>>()?;
+            if balance.address != contract_address.as_str() {
+                pool_escrow.mismatch = Some(EscrowMismatchReason::WrongHolder);
+            }
+            pool_escrow.holder = Some(balance.address.clone());
+            pool_escrow.amount = Some(amount);
+        }
+        _ => pool_escrow.mismatch = Some(EscrowMismatchReason::MultipleHolders),
+    }
+    Ok(pool_escrow)
+}
+
+/// Compares the holding of every pool with the escrow of the contract, including the pools received
+/// from token contracts that have not been finalized yet. Marker pools are checked against the holder
+/// of the marker's coins and CW721 pools against the owner of the NFT. Native and CW20 pools are only
+/// recorded as an amount, so every pool of the same denom or token contract is compared with the
+/// contract's balance, which must cover the total escrowed across the sellers.
+pub fn reconcile_escrow(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper<Empty>,
+    contract_address: &Addr,
+) -> Result<Vec<PoolEscrow>, ContractError> {
+    let sellers = retrieve_seller_list_state(storage)?;
+    let seller_pools = || {
+        sellers.iter().flat_map(|seller| {
+            seller
+                .pools
+                .iter()
+                .chain(seller.received_pools.iter())
+                .map(move |pool| (&seller.seller_address, pool))
+        })
+    };
+
+    // The total of each denom and token contract the contract should hold, along with its balance
+    let mut escrowed_balances: Vec<(String, Uint128, Uint128)> = vec![];
+    for (_, pool) in seller_pools() {
+        let (denom, amount) = match pool {
+            PoolAsset::Native { coin } => (coin.denom.clone(), coin.amount),
+            PoolAsset::Cw20 { contract, amount } => (contract.to_string(), *amount),
+            _ => continue,
+        };
+        match escrowed_balances
+            .iter_mut()
+            .find(|(escrowed_denom, _, _)| *escrowed_denom == denom)
+        {
+            Some((_, total, _)) => *total += amount,
+            None => {
+                let balance = match pool {
+                    PoolAsset::Cw20 { contract, .. } => {
+                        querier
+                            .query_wasm_smart::<Cw20BalanceResponse>(
+                                contract,
+                                &Cw20QueryMsg::Balance {
+                                    address: contract_address.to_string(),
+                                },
+                            )?
+                            .balance
+                    }
+                    _ => querier.query_balance(contract_address, &denom)?.amount,
+                };
+                escrowed_balances.push((denom, amount, balance));
+            }
+        }
+    }
+
+    let mut pools = vec![];
+    for (seller_address, pool) in seller_pools() {
+        let pool_escrow = match pool {
+            PoolAsset::Marker { denom } => {
+                reconcile_pool_escrow(querier, contract_address, seller_address, denom)?
+            }
+            PoolAsset::Native { coin } => reconcile_balance_escrow(
+                contract_address,
+                seller_address,
+                &coin.denom,
+                &escrowed_balances,
+            ),
+            PoolAsset::Cw20 { contract, .. } => reconcile_balance_escrow(
+                contract_address,
+                seller_address,
+                contract.as_str(),
+                &escrowed_balances,
+            ),
+            PoolAsset::Cw721 { contract, token_id } => {
+                // An NFT that no longer exists cannot be owned by anyone
+                let owner = querier
+                    .query_wasm_smart::<Cw721OwnerOfResponse>(
+                        contract,
+                        &Cw721QueryMsg::OwnerOf {
+                            token_id: token_id.clone(),
+                            include_expired: None,
+                        },
+                    )
+                    .map(|response| response.owner)
+                    .ok();
+                let mismatch = match &owner {
+                    None => Some(EscrowMismatchReason::NotHeld),
+                    Some(owner) if owner != contract_address.as_str() => {
+                        Some(EscrowMismatchReason::WrongHolder)
+                    }
+                    Some(_) => None,
+                };
+                PoolEscrow {
+                    seller_address: seller_address.clone(),
+                    denom: contract.to_string(),
+                    token_id: Some(token_id.clone()),
+                    expected_holder: contract_address.clone(),
+                    amount: owner.as_ref().map(|_| Uint128::one()),
+                    holder: owner,
+                    mismatch,
+                }
+            }
+        };
+        pools.push(pool_escrow);
+    }
+    Ok(pools)
+}
+
+/// Compares the contract's balance of a native denom or CW20 token contract with the total escrowed
+/// in its pools
+fn reconcile_balance_escrow(
+    contract_address: &Addr,
+    seller_address: &Addr,
+    denom: &str,
+    escrowed_balances: &[(String, Uint128, Uint128)],
+) -> PoolEscrow {
+    let (total, balance) = escrowed_balances
+        .iter()
+        .find(|(escrowed_denom, _, _)| escrowed_denom == denom)
+        .map(|(_, total, balance)| (*total, *balance))
+        .unwrap_or_default();
+    PoolEscrow {
+        seller_address: seller_address.clone(),
+        denom: denom.to_string(),
+        token_id: None,
+        expected_holder: contract_address.clone(),
+        holder: Some(contract_address.to_string()),
+        amount: Some(balance),
+        mismatch: (balance < total).then_some(EscrowMismatchReason::InsufficientBalance),
     }
 }
 
-pub fn is_dealer(deps: &DepsMut, info: &MessageInfo) -> Result<bool, ContractError> {
+/// Retrieves the balance of a finalized pool, which must still be held in escrow by the contract
+pub fn get_escrowed_balance(
+    deps: &DepsMut,
+    denom: String,
+    contract_address: &str,
+) -> Result<HeldCoin, ContractError> {
+    let held_coin = get_balance(deps, denom.clone())?;
+    if held_coin.address != contract_address {
+        return Err(EscrowMismatch {
+            denom,
+            holder: held_coin.address,
+        });
+    }
+    Ok(held_coin)
+}
+
+pub fn is_seller(storage: &dyn Storage, address: &Addr) -> Result<bool, ContractError> {
+    Ok(SELLERS.has(storage, address))
+}
+
+pub fn is_dealer(deps: Deps, address: &Addr) -> Result<bool, ContractError> {
     let config = retrieve_contract_config(deps.storage)?;
-    Ok(config.dealers.contains(&info.sender))
+    if !config.dealers.contains(address) {
+        return Ok(false);
+    }
+
+    // A dealer that does not hold the attributes required of dealers cannot act as one
+    match &config.required_attributes {
+        None => Ok(true),
+        Some(required_attributes) => {
+            Ok(missing_attribute(&deps.querier, address, &required_attributes.dealers)?.is_none())
+        }
+    }
 }
 
-pub fn seller_has_finalized(deps: &DepsMut) -> Result<bool, ContractError> {
-    match retrieve_optional_seller_state(deps.storage)? {
-        None => Ok(false),
-        Some(seller) => Ok(!seller.pool_denoms.is_empty()),
+/// Returns an error if the address does not hold every one of the required attributes
+pub fn validate_required_attributes(
+    deps: Deps,
+    address: &Addr,
+    required_attributes: &[String],
+) -> Result<(), ContractError> {
+    match missing_attribute(&deps.querier, address, required_attributes)? {
+        None => Ok(()),
+        Some(attribute) => Err(MissingRequiredAttribute {
+            address: address.to_string(),
+            attribute,
+        }),
+    }
+}
+
+/// Returns the first required attribute that the address does not hold
+pub fn missing_attribute(
+    querier: &QuerierWrapper<Empty>,
+    address: &Addr,
+    required_attributes: &[String],
+) -> Result<Option<String>, ContractError> {
+    let attribute_querier = AttributeQuerier::new(querier);
+    for attribute in required_attributes {
+        let response = attribute_querier.attribute(address.to_string(), attribute.clone(), None)?;
+        if response.attributes.is_empty() {
+            return Ok(Some(attribute.clone()));
+        }
     }
+    Ok(None)
 }
 
-pub fn is_buyer(deps: &DepsMut, info: &MessageInfo) -> Result<bool, ContractError> {
-    return match retrieve_optional_buyer_state(deps.storage)? {
+pub fn seller_has_finalized(
+    storage: &dyn Storage,
+    seller_address: &Addr,
+) -> Result<bool, ContractError> {
+    match retrieve_optional_seller_state(storage, seller_address)? {
         None => Ok(false),
-        Some(state) => Ok(state.buyer_address == info.sender),
+        Some(seller) => Ok(!seller.pools.is_empty()),
+    }
+}
+
+pub fn any_seller_has_finalized(storage: &dyn Storage) -> Result<bool, ContractError> {
+    Ok(retrieve_seller_list_state(storage)?
+        .iter()
+        .any(|seller| !seller.pools.is_empty()))
+}
+
+pub fn is_buyer(storage: &dyn Storage, address: &Addr) -> Result<bool, ContractError> {
+    Ok(BUYER_STATE.has(storage, address))
+}
+
+pub fn any_buyer_has_accepted(storage: &dyn Storage) -> Result<bool, ContractError> {
+    Ok(retrieve_buyer_list_state(storage)?
+        .iter()
+        .any(|buyer| buyer.buyer_has_accepted_pools))
+}
+
+pub fn all_buyers_have_accepted(storage: &dyn Storage) -> Result<bool, ContractError> {
+    let buyers = retrieve_buyer_list_state(storage)?;
+    Ok(!buyers.is_empty() && buyers.iter().all(|buyer| buyer.buyer_has_accepted_pools))
+}
+
+pub fn is_contract_admin(storage: &dyn Storage, address: &Addr) -> Result<bool, ContractError> {
+    let config = retrieve_contract_config(storage)?;
+    Ok(*address == config.contract_admin)
+}
+
+/// Makes sure the bidder is allowed to take part in the bidding when the contract uses private
+/// buyers, and that the limit of bids has not been reached. The bid count must not include the
+/// bidder's own bid so that replacing it never counts against the limit.
+pub fn validate_bidder(
+    storage: &dyn Storage,
+    config: &Config,
+    bidder_address: &Addr,
+    other_bid_count: usize,
+) -> Result<(), ContractError> {
+    if config.use_private_buyers && !is_allowed_buyer(storage, bidder_address)? {
+        return Err(UnauthorizedPrivateBuyer);
+    }
+
+    if let Some(max_bid_count) = config.max_bid_count {
+        if other_bid_count >= max_bid_count as usize {
+            return Err(MaxBuyersReached);
+        }
+    }
+    Ok(())
+}
+
+/// Creates the commitment a bidder submits during the commit window of a sealed-bid auction. The
+/// commitment is the hex encoded sha256 hash of the agreement terms hash, the optional price and a
+/// secret salt, joined with colons. A bid without a price uses an empty price segment.
+pub fn create_bid_commitment(
+    agreement_terms_hash: &str,
+    price: Option<Uint128>,
+    salt: &str,
+) -> String {
+    let price = price.map(|price| price.to_string()).unwrap_or_default();
+    let mut hasher = Sha256::new();
+    hasher.update(agreement_terms_hash.as_bytes());
+    hasher.update(b":");
+    hasher.update(price.as_bytes());
+    hasher.update(b":");
+    hasher.update(salt.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Makes sure the bidder attaches the collateral the contract requires. Collateral is only taken
+/// once, so a bidder replacing their bid must not attach it again. Collateral held in a restricted
+/// marker cannot be sent with the message, so nothing can be attached when it is transferred instead.
+pub fn validate_bid_collateral(
+    storage: &dyn Storage,
+    info: &MessageInfo,
+    config: &Config,
+) -> Result<(), ContractError> {
+    let collateral_config = match &config.bid_collateral {
+        None => return Ok(()),
+        Some(collateral_config) => collateral_config,
+    };
+    let required_collateral = cosmwasm_std::Coin {
+        denom: collateral_config.denom.clone(),
+        amount: collateral_config.amount,
+    };
+    let has_collateral = retrieve_optional_bid_collateral_state(storage, &info.sender)?.is_some();
+    let is_valid = if has_collateral || collateral_config.is_restricted_marker {
+        info.funds.is_empty()
+    } else {
+        info.funds == vec![required_collateral]
     };
+    if !is_valid {
+        return Err(InvalidBidCollateral {
+            denom: collateral_config.denom.clone(),
+            amount: collateral_config.amount,
+        });
+    }
+    Ok(())
 }
 
-pub fn buyer_has_accepted(deps: &DepsMut) -> Result<bool, ContractError> {
-    return match retrieve_optional_buyer_state(deps.storage)? {
-        None => Ok(false),
-        Some(state) => Ok(state.buyer_has_accepted_pools),
+/// Escrows the collateral the contract requires from a bidder, once the attached funds have been
+/// checked with [validate_bid_collateral]. Collateral held in a restricted marker is transferred from
+/// the bidder to the contract with the returned message.
+pub fn escrow_bid_collateral(
+    deps: &mut DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    config: &Config,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let collateral_config = match &config.bid_collateral {
+        None => return Ok(vec![]),
+        Some(collateral_config) => collateral_config,
+    };
+    if retrieve_optional_bid_collateral_state(deps.storage, &info.sender)?.is_some() {
+        return Ok(vec![]);
+    }
+
+    let required_collateral = cosmwasm_std::Coin {
+        denom: collateral_config.denom.clone(),
+        amount: collateral_config.amount,
     };
+    let mut messages = vec![];
+    if collateral_config.is_restricted_marker {
+        messages.push(create_escrow_deposit_message(
+            &required_collateral.denom,
+            required_collateral.amount,
+            env.contract.address.to_string(),
+            info.sender.to_string(),
+        ));
+    }
+
+    save_bid_collateral_state(
+        deps.storage,
+        &BidCollateral {
+            bidder_address: info.sender.clone(),
+            collateral: required_collateral,
+        },
+    )?;
+    Ok(messages)
 }
 
-pub fn is_contract_admin(deps: &DepsMut, info: &MessageInfo) -> Result<bool, ContractError> {
-    let config = retrieve_contract_config(deps.storage)?;
-    return Ok(info.sender == config.contract_admin);
+/// Creates the message that pulls funds held in a restricted marker into the contract's escrow.
+/// Restricted coins cannot be attached to a message, so the contract transfers them from the owner.
+pub fn create_escrow_deposit_message(
+    denom: &str,
+    amount: Uint128,
+    contract_address: String,
+    from_address: String,
+) -> CosmosMsg {
+    CosmosMsg::from(MsgTransferRequest {
+        amount: Some(Coin {
+            denom: denom.to_string(),
+            amount: amount.to_string(),
+        }),
+        administrator: contract_address.clone(),
+        from_address,
+        to_address: contract_address,
+    })
+}
+
+/// Creates the message that pays escrowed funds out of the contract. Restricted marker funds must be
+/// moved with a marker transfer rather than a bank send.
+pub fn create_escrow_payment_message(
+    denom: &str,
+    is_restricted_marker: bool,
+    contract_address: String,
+    recipient_address: String,
+    amount: Uint128,
+) -> CosmosMsg {
+    if is_restricted_marker {
+        CosmosMsg::from(MsgTransferRequest {
+            amount: Some(Coin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }),
+            administrator: contract_address.clone(),
+            from_address: contract_address,
+            to_address: recipient_address,
+        })
+    } else {
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient_address,
+            amount: coins(amount.u128(), denom),
+        })
+    }
+}
+
+/// The revision of the next bid placed by the bidder. Revisions keep increasing across replaced and
+/// withdrawn bids, so a revision always identifies the same terms.
+pub fn next_bid_revision(
+    storage: &dyn Storage,
+    bid_list: &BidList,
+    bidder_address: &Addr,
+) -> Result<u64, ContractError> {
+    let current_revision = bid_list
+        .bids
+        .iter()
+        .find(|bid| bid.buyer_address == *bidder_address)
+        .map(|bid| bid.revision);
+    let latest_revision = retrieve_latest_bid_revision(storage, bidder_address)?;
+    Ok(current_revision.max(latest_revision).unwrap_or_default() + 1)
+}
+
+/// Returns the escrowed collateral of every bidder matching the filter to that bidder and removes it
+/// from the escrow.
+pub fn release_bid_collateral<F>(
+    storage: &mut dyn Storage,
+    env: &Env,
+    filter: F,
+) -> Result<Vec<CosmosMsg>, ContractError>
+where
+    F: Fn(&Addr) -> bool,
+{
+    let collateral_config = match retrieve_contract_config(storage)?.bid_collateral {
+        None => return Ok(vec![]),
+        Some(collateral_config) => collateral_config,
+    };
+
+    let mut messages = vec![];
+    for bid_collateral in retrieve_bid_collateral_list_state(storage)? {
+        if !filter(&bid_collateral.bidder_address) {
+            continue;
+        }
+        messages.push(create_escrow_payment_message(
+            &collateral_config.denom,
+            collateral_config.is_restricted_marker,
+            env.contract.address.to_string(),
+            bid_collateral.bidder_address.to_string(),
+            bid_collateral.collateral.amount,
+        ));
+        remove_bid_collateral_state(storage, &bid_collateral.bidder_address);
+    }
+    Ok(messages)
+}
+
+/// Returns the number of forward market tokens a party carries margin for. Buyers are exposed to the
+/// tokens they were allocated and the sellers with finalized pools share the exposure to every minted
+/// token evenly, in the same way they share the settlement fees.
+pub fn margin_exposure(storage: &dyn Storage, address: &Addr) -> Result<Decimal, ContractError> {
+    if let Some(buyer) = retrieve_optional_buyer_state(storage, address)? {
+        return Ok(Decimal::from(buyer.token_amount.u128()));
+    }
+    if !seller_has_finalized(storage, address)? {
+        return Ok(Decimal::ZERO);
+    }
+    let token_count = match retrieve_optional_token_data_state(storage)? {
+        None => return Ok(Decimal::ZERO),
+        Some(token_data) => token_data.token_count,
+    };
+    let finalized_seller_count = retrieve_seller_list_state(storage)?
+        .iter()
+        .filter(|seller| !seller.pools.is_empty())
+        .count();
+    Ok(Decimal::from(token_count.u128()) / Decimal::from(finalized_seller_count))
+}
+
+/// Returns true if the margin account holds less than the maintenance margin for its exposure once
+/// the variation margin it owes is taken out
+pub fn is_under_margined(
+    storage: &dyn Storage,
+    margin_config: &MarginConfig,
+    margin_account: &MarginAccount,
+) -> Result<bool, ContractError> {
+    let required_margin =
+        margin_exposure(storage, &margin_account.owner)? * margin_config.maintenance_margin;
+    Ok(margin_account.balance - margin_account.owed < required_margin)
+}
+
+/// Returns the margin account of a party, or an empty account if they have not held margin yet
+pub fn retrieve_margin_account(
+    storage: &dyn Storage,
+    owner: &Addr,
+) -> Result<MarginAccount, ContractError> {
+    Ok(
+        retrieve_optional_margin_account_state(storage, owner)?.unwrap_or(MarginAccount {
+            owner: owner.clone(),
+            balance: Decimal::ZERO,
+            margin_call_deadline: None,
+            owed: Decimal::ZERO,
+        }),
+    )
+}
+
+/// Credits margin paid by a party to the other side of the trade in proportion to their exposure.
+/// Returns the amount that was credited, which is zero when the other side carries no exposure.
+pub fn credit_counterparties(
+    storage: &mut dyn Storage,
+    payer: &Addr,
+    amount: Decimal,
+) -> Result<Decimal, ContractError> {
+    let counterparties: Vec<Addr> = if retrieve_optional_buyer_state(storage, payer)?.is_some() {
+        retrieve_seller_list_state(storage)?
+            .into_iter()
+            .map(|seller| seller.seller_address)
+            .collect()
+    } else {
+        retrieve_buyer_list_state(storage)?
+            .into_iter()
+            .map(|buyer| buyer.buyer_address)
+            .collect()
+    };
+    let mut exposures = vec![];
+    for counterparty in counterparties {
+        let exposure = margin_exposure(storage, &counterparty)?;
+        exposures.push((counterparty, exposure));
+    }
+    let total_exposure: Decimal = exposures.iter().map(|(_, exposure)| *exposure).sum();
+    if amount <= Decimal::ZERO || total_exposure.is_zero() {
+        return Ok(Decimal::ZERO);
+    }
+
+    for (counterparty, exposure) in exposures {
+        let mut margin_account = retrieve_margin_account(storage, &counterparty)?;
+        margin_account.balance += amount * exposure / total_exposure;
+        save_margin_account_state(storage, &margin_account)?;
+    }
+    Ok(amount)
+}
+
+/// Pays every margin account its remaining balance and closes the accounts. Balances are rounded down
+/// to whole units of the margin denom.
+pub fn release_margin(storage: &mut dyn Storage) -> Result<Vec<CosmosMsg>, ContractError> {
+    let margin_config = match retrieve_contract_config(storage)?.margin {
+        None => return Ok(vec![]),
+        Some(margin_config) => margin_config,
+    };
+
+    let mut messages = vec![];
+    for margin_account in retrieve_margin_account_list_state(storage)? {
+        let amount = margin_account.balance.floor().to_u128().unwrap_or_default();
+        if amount == 0 {
+            continue;
+        }
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: margin_account.owner.to_string(),
+            amount: coins(amount, margin_config.denom.clone()),
+        }));
+    }
+    clear_margin_account_state(storage);
+    Ok(messages)
+}
+
+/// Calculates the fee every buyer and every seller with finalized pools owes at settlement, along with
+/// the amount they have deposited towards it
+pub fn calculate_settlement_fees(
+    storage: &dyn Storage,
+) -> Result<Vec<SettlementFee>, ContractError> {
+    let fee_config = match retrieve_contract_config(storage)?.fees {
+        None => return Ok(vec![]),
+        Some(fee_config) => fee_config,
+    };
+    let token_count = retrieve_optional_token_data_state(storage)?
+        .map(|token_data| token_data.token_count)
+        .unwrap_or_default();
+
+    // Buyers settle the tokens they were allocated and the finalized sellers share every minted token
+    let mut notionals = vec![];
+    if let Some(buyer_fee) = &fee_config.buyer_fee {
+        for buyer in retrieve_buyer_list_state(storage)? {
+            notionals.push((buyer.buyer_address, buyer_fee, buyer.token_amount));
+        }
+    }
+    if let Some(seller_fee) = &fee_config.seller_fee {
+        let finalized_sellers: Vec<_> = retrieve_seller_list_state(storage)?
+            .into_iter()
+            .filter(|seller| !seller.pools.is_empty())
+            .collect();
+        let seller_count = finalized_sellers.len() as u128;
+        for seller in finalized_sellers {
+            notionals.push((
+                seller.seller_address,
+                seller_fee,
+                token_count.multiply_ratio(1u128, seller_count),
+            ));
+        }
+    }
+
+    let mut settlement_fees = vec![];
+    for (payer, fee, notional) in notionals {
+        let amount = match fee {
+            Fee::Flat { amount } => *amount,
+            Fee::BasisPoints { bps } => notional.multiply_ratio(*bps, 10_000u128),
+        };
+        let deposited = retrieve_optional_fee_deposit_state(storage, &payer)?
+            .map(|fee_deposit| fee_deposit.amount)
+            .unwrap_or_default();
+        settlement_fees.push(SettlementFee {
+            payer,
+            amount,
+            deposited,
+        });
+    }
+    Ok(settlement_fees)
+}
+
+/// Returns every fee deposit, less the settlement fee its payer owes, to the payer and closes the
+/// deposits
+pub fn release_fee_deposits(
+    storage: &mut dyn Storage,
+    env: &Env,
+    settlement_fees: &[SettlementFee],
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let fee_config = match retrieve_contract_config(storage)?.fees {
+        None => return Ok(vec![]),
+        Some(fee_config) => fee_config,
+    };
+
+    let mut messages = vec![];
+    for fee_deposit in retrieve_fee_deposit_list_state(storage)? {
+        let owed = settlement_fees
+            .iter()
+            .find(|settlement_fee| settlement_fee.payer == fee_deposit.payer)
+            .map(|settlement_fee| settlement_fee.amount)
+            .unwrap_or_default();
+        let refund = fee_deposit.amount.saturating_sub(owed);
+        if refund.is_zero() {
+            continue;
+        }
+        messages.push(create_escrow_payment_message(
+            &fee_config.denom,
+            fee_config.is_restricted_marker,
+            env.contract.address.to_string(),
+            fee_deposit.payer.to_string(),
+            refund,
+        ));
+    }
+    clear_fee_deposit_state(storage);
+    Ok(messages)
 }
 
 pub fn update_config_as_admin(
@@ -239,43 +1033,136 @@ pub fn update_config_as_admin(
     info: MessageInfo,
     updated_config: Config,
 ) -> Result<Response, ContractError> {
-    if !is_contract_admin(&deps, &info)? {
+    validate_config_update(deps.storage, &info.sender)?;
+
+    save_contract_config(deps.storage, &updated_config)?;
+
+    Ok(Response::new().add_attribute("contract_config", format!("{:?}", updated_config)))
+}
+
+/// Makes sure the sender is the contract admin and that the configuration can still be changed, which
+/// is only until both a seller and a buyer have been established
+pub fn validate_config_update(storage: &dyn Storage, sender: &Addr) -> Result<(), ContractError> {
+    if !is_contract_admin(storage, sender)? {
         return Err(UnauthorizedConfigUpdate);
     }
 
-    let seller_state = retrieve_optional_seller_state(deps.storage)?;
-    let buyer_state = retrieve_optional_buyer_state(deps.storage)?;
-    if seller_state.is_some() && buyer_state.is_some() {
+    if !SELLERS.is_empty(storage) && !BUYER_STATE.is_empty(storage) {
         return Err(IllegalConfigUpdate);
     }
+    Ok(())
+}
 
-    save_contract_config(deps.storage, &updated_config)?;
+/// Makes sure the allowed seller list can be changed, which requires the trade to use private sellers
+pub fn validate_allowed_sellers_update(
+    storage: &dyn Storage,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    if !retrieve_contract_config(storage)?.use_private_sellers {
+        return Err(InvalidVisibilityConfig);
+    }
+    validate_config_update(storage, sender)
+}
 
-    Ok(Response::new().add_attribute("contract_config", format!("{:?}", updated_config)))
+/// Makes sure the allowed buyer list can be changed, which requires the trade to use private buyers
+pub fn validate_allowed_buyers_update(
+    storage: &dyn Storage,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    if !retrieve_contract_config(storage)?.use_private_buyers {
+        return Err(InvalidVisibilityConfig);
+    }
+    validate_config_update(storage, sender)
+}
+
+/// Makes sure the trade has not been settled or disabled, after which no further routes can be executed
+pub fn validate_trade_is_open(storage: &dyn Storage) -> Result<(), ContractError> {
+    if retrieve_optional_settlement_data_state(storage)?.is_some() {
+        return Err(IllegalContractExecution);
+    }
+    if retrieve_contract_config(storage)?.is_disabled {
+        return Err(InvalidContractExecution);
+    }
+    Ok(())
+}
+
+/// Trades created with CreateTrade are numbered from one, while routes without a trade id are
+/// executed against the trade created when the contract was instantiated
+pub fn validate_trade_id(
+    storage: &dyn Storage,
+    trade_id: Option<u64>,
+) -> Result<(), ContractError> {
+    if let Some(trade_id) = trade_id {
+        if trade_id == 0 || trade_id > retrieve_trade_count(storage)? {
+            return Err(UnknownTrade { trade_id });
+        }
+    }
+    Ok(())
 }
 
 pub fn create_send_coin_back_to_seller_messages(
     deps: &DepsMut,
     contract_address: String,
     seller_address: String,
-    pool_denoms: Vec<String>,
-) -> Result<Vec<MsgTransferRequest>, ContractError> {
+    pools: Vec<PoolAsset>,
+) -> Result<Vec<CosmosMsg>, ContractError> {
     let mut messages = vec![];
 
-    // Iterate over the list of denoms so that we can update the value owner of the scope to be the
-    // seller instead of the contract
-    for denom in pool_denoms {
-        let held_coin = get_balance(&deps, denom.clone())?;
-
-        // Transfer the coins back to the seller
-        messages.push(MsgTransferRequest {
-            amount: Some(held_coin.coin),
-            administrator: contract_address.to_string(),
-            from_address: contract_address.to_string(),
-            to_address: seller_address.to_string(),
-        });
+    // Iterate over the list of pools so that we can return each of them from the contract's escrow
+    // to the seller
+    for pool in pools {
+        messages.push(create_pool_transfer_message(
+            deps,
+            pool,
+            &contract_address,
+            seller_address.clone(),
+        )?);
+    }
+    Ok(messages)
+}
+
+/// Creates the message that moves an escrowed pool out of the contract. Marker pools must still be
+/// held by the contract and are moved with a marker transfer, native pools are sent from the
+/// contract's bank balance and CW20 and CW721 pools are transferred by their token contract
+pub fn create_pool_transfer_message(
+    deps: &DepsMut,
+    pool: PoolAsset,
+    contract_address: &str,
+    to_address: String,
+) -> Result<CosmosMsg, ContractError> {
+    match pool {
+        PoolAsset::Marker { denom } => {
+            // Access to the pool marker can be revoked or its status changed after finalization
+            validate_marker_access(deps, &denom, contract_address, &[Access::Transfer])?;
+            let held_coin = get_escrowed_balance(deps, denom, contract_address)?;
+            Ok(CosmosMsg::from(MsgTransferRequest {
+                amount: Some(held_coin.coin),
+                administrator: contract_address.to_string(),
+                from_address: contract_address.to_string(),
+                to_address,
+            }))
+        }
+        PoolAsset::Native { coin } => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address,
+            amount: vec![coin],
+        })),
+        PoolAsset::Cw20 { contract, amount } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: to_address,
+                amount,
+            })?,
+            funds: vec![],
+        })),
+        PoolAsset::Cw721 { contract, token_id } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: to_address,
+                token_id,
+            })?,
+            funds: vec![],
+        })),
     }
-    return Ok(messages);
 }
 
 pub struct HeldCoin {
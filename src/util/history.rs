@@ -0,0 +1,158 @@
+use crate::error::ContractError;
+use crate::storage::history_storage::StorageWrite;
+use crate::storage::state_store::{
+    append_history_entry, retrieve_next_history_id, Bid, BidList, HistoryChange, HistoryEntry,
+    ACCEPTANCE_DEADLINE, ALLOWED_BUYERS, ALLOWED_SELLERS, BID_COLLATERAL, BID_COMMITMENTS,
+    BID_LIST, BUYER_STATE, CONFIG, FEE_DEPOSITS, MARGIN_ACCOUNTS, MARK_PRICE, SELLERS,
+    SETTLEMENT_DATA, TOKEN_DATA, TRADE_ALLOWED_BUYERS, TRADE_ALLOWED_SELLERS, TRADE_OVERRIDES,
+};
+use cosmwasm_std::storage_keys::to_length_prefixed;
+use cosmwasm_std::{from_json, to_json_string, Addr, Env, Storage};
+
+/// Appends a history entry for a route executed by the actor, holding every entry of the trade's
+/// state that the route changed. Entries of the lists kept per address are recorded on their own,
+/// so a route only records the sellers, bids, buyers and accounts that it touched.
+pub fn record_history(
+    storage: &mut dyn Storage,
+    env: &Env,
+    actor: Addr,
+    action: &str,
+    writes: Vec<StorageWrite>,
+) -> Result<(), ContractError> {
+    let mut changes = vec![];
+    for write in writes {
+        changes.extend(history_changes(write)?);
+    }
+
+    let id = retrieve_next_history_id(storage)?;
+    append_history_entry(
+        storage,
+        &HistoryEntry {
+            id,
+            actor,
+            action: action.to_string(),
+            block_height: env.block.height,
+            block_time: env.block.time,
+            changes,
+        },
+    )
+}
+
+/// The changes recorded for a key written by a route. Keys outside of the trade's recorded state,
+/// such as the revision logs, are not recorded.
+fn history_changes(write: StorageWrite) -> Result<Vec<HistoryChange>, ContractError> {
+    let key = write.key.as_slice();
+    if key == BID_LIST.as_slice() {
+        return bid_changes(write);
+    }
+    let items = [
+        ("config", CONFIG.as_slice().to_vec()),
+        ("trade_overrides", TRADE_OVERRIDES.as_slice().to_vec()),
+        (
+            "acceptance_deadline",
+            ACCEPTANCE_DEADLINE.as_slice().to_vec(),
+        ),
+        ("mark_price", MARK_PRICE.as_slice().to_vec()),
+        ("settlement_data", SETTLEMENT_DATA.as_slice().to_vec()),
+        ("token_data", TOKEN_DATA.as_slice().to_vec()),
+    ];
+    if let Some((field, _)) = items.iter().find(|(_, item_key)| item_key == key) {
+        return Ok(vec![HistoryChange {
+            field: field.to_string(),
+            key: None,
+            before: json_value(write.before),
+            after: json_value(write.after),
+        }]);
+    }
+    let maps = [
+        (
+            "allowed_sellers",
+            to_length_prefixed(ALLOWED_SELLERS.namespace_bytes()),
+        ),
+        (
+            "allowed_buyers",
+            to_length_prefixed(ALLOWED_BUYERS.namespace_bytes()),
+        ),
+        (
+            "allowed_sellers",
+            to_length_prefixed(TRADE_ALLOWED_SELLERS.namespace_bytes()),
+        ),
+        (
+            "allowed_buyers",
+            to_length_prefixed(TRADE_ALLOWED_BUYERS.namespace_bytes()),
+        ),
+        ("sellers", to_length_prefixed(SELLERS.namespace_bytes())),
+        (
+            "bid_commitments",
+            to_length_prefixed(BID_COMMITMENTS.namespace_bytes()),
+        ),
+        (
+            "bid_collateral",
+            to_length_prefixed(BID_COLLATERAL.namespace_bytes()),
+        ),
+        ("buyers", to_length_prefixed(BUYER_STATE.namespace_bytes())),
+        (
+            "margin_accounts",
+            to_length_prefixed(MARGIN_ACCOUNTS.namespace_bytes()),
+        ),
+        (
+            "fee_deposits",
+            to_length_prefixed(FEE_DEPOSITS.namespace_bytes()),
+        ),
+    ];
+    for (field, prefix) in maps {
+        if let Some(address) = key.strip_prefix(prefix.as_slice()) {
+            return Ok(vec![HistoryChange {
+                field: field.to_string(),
+                key: Some(String::from_utf8_lossy(address).to_string()),
+                before: json_value(write.before),
+                after: json_value(write.after),
+            }]);
+        }
+    }
+    Ok(vec![])
+}
+
+/// The bids are stored together, so they are compared by bidder to record only the bids that changed
+fn bid_changes(write: StorageWrite) -> Result<Vec<HistoryChange>, ContractError> {
+    let before = stored_bids(write.before)?;
+    let after = stored_bids(write.after)?;
+    let find_bid = |bids: &[Bid], bidder: &Addr| {
+        bids.iter()
+            .find(|bid| bid.buyer_address == *bidder)
+            .cloned()
+    };
+
+    let mut changes = vec![];
+    let mut bidders: Vec<&Addr> = vec![];
+    for bid in before.iter().chain(after.iter()) {
+        if bidders.contains(&&bid.buyer_address) {
+            continue;
+        }
+        bidders.push(&bid.buyer_address);
+        let bid_before = find_bid(&before, &bid.buyer_address);
+        let bid_after = find_bid(&after, &bid.buyer_address);
+        if bid_before != bid_after {
+            changes.push(HistoryChange {
+                field: "bids".to_string(),
+                key: Some(bid.buyer_address.to_string()),
+                before: bid_before.map(|bid| to_json_string(&bid)).transpose()?,
+                after: bid_after.map(|bid| to_json_string(&bid)).transpose()?,
+            });
+        }
+    }
+    Ok(changes)
+}
+
+fn stored_bids(value: Option<Vec<u8>>) -> Result<Vec<Bid>, ContractError> {
+    Ok(value
+        .map(from_json::<BidList>)
+        .transpose()?
+        .map(|bid_list| bid_list.bids)
+        .unwrap_or_default())
+}
+
+/// Stored values are already JSON encoded, so they are recorded as they were stored
+fn json_value(value: Option<Vec<u8>>) -> Option<String> {
+    value.map(|value| String::from_utf8_lossy(&value).to_string())
+}
@@ -0,0 +1,189 @@
+use crate::error::ContractError;
+use crate::error::ContractError::InvalidMetadataAddress;
+use bech32::{FromBase32, ToBase32, Variant};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+/// The number of bytes in a uuid or in the hash of a record or record specification name
+const ID_LENGTH: usize = 16;
+
+/// A Provenance metadata address. The first byte is the key type, followed by the uuid of the
+/// scope or specification. Sessions append the session uuid to the scope uuid, while records and
+/// record specifications append the first 16 bytes of the sha256 hash of their name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetadataAddress {
+    pub bech32: String,
+    pub bytes: Vec<u8>,
+    pub key_type: KeyType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeyType {
+    Scope = 0x00,
+    Session = 0x01,
+    Record = 0x02,
+    ContractSpecification = 0x03,
+    ScopeSpecification = 0x04,
+    RecordSpecification = 0x05,
+}
+
+impl KeyType {
+    pub fn to_str(&self) -> &str {
+        match self {
+            KeyType::Scope => "scope",
+            KeyType::Session => "session",
+            KeyType::Record => "record",
+            KeyType::ContractSpecification => "contractspec",
+            KeyType::ScopeSpecification => "scopespec",
+            KeyType::RecordSpecification => "recspec",
+        }
+    }
+
+    pub fn from_byte(byte: u8) -> Result<KeyType, ContractError> {
+        match byte {
+            0x00 => Ok(KeyType::Scope),
+            0x01 => Ok(KeyType::Session),
+            0x02 => Ok(KeyType::Record),
+            0x03 => Ok(KeyType::ContractSpecification),
+            0x04 => Ok(KeyType::ScopeSpecification),
+            0x05 => Ok(KeyType::RecordSpecification),
+            _ => Err(InvalidMetadataAddress {
+                message: format!("unknown key type byte {byte:#04x}"),
+            }),
+        }
+    }
+
+    /// The length of an address of this key type, including the key type byte
+    fn address_length(&self) -> usize {
+        match self {
+            KeyType::Scope | KeyType::ContractSpecification | KeyType::ScopeSpecification => {
+                1 + ID_LENGTH
+            }
+            KeyType::Session | KeyType::Record | KeyType::RecordSpecification => 1 + 2 * ID_LENGTH,
+        }
+    }
+}
+
+impl MetadataAddress {
+    pub fn scope(scope_uuid: Uuid) -> Result<MetadataAddress, ContractError> {
+        Self::from_parts(KeyType::Scope, scope_uuid, &[])
+    }
+
+    pub fn session(scope_uuid: Uuid, session_uuid: Uuid) -> Result<MetadataAddress, ContractError> {
+        Self::from_parts(KeyType::Session, scope_uuid, session_uuid.as_bytes())
+    }
+
+    pub fn record(scope_uuid: Uuid, record_name: &str) -> Result<MetadataAddress, ContractError> {
+        Self::from_parts(KeyType::Record, scope_uuid, &name_hash(record_name)?)
+    }
+
+    pub fn contract_specification(
+        contract_spec_uuid: Uuid,
+    ) -> Result<MetadataAddress, ContractError> {
+        Self::from_parts(KeyType::ContractSpecification, contract_spec_uuid, &[])
+    }
+
+    pub fn scope_specification(scope_spec_uuid: Uuid) -> Result<MetadataAddress, ContractError> {
+        Self::from_parts(KeyType::ScopeSpecification, scope_spec_uuid, &[])
+    }
+
+    pub fn record_specification(
+        contract_spec_uuid: Uuid,
+        record_spec_name: &str,
+    ) -> Result<MetadataAddress, ContractError> {
+        Self::from_parts(
+            KeyType::RecordSpecification,
+            contract_spec_uuid,
+            &name_hash(record_spec_name)?,
+        )
+    }
+
+    /// Creates a metadata address from its raw bytes, validating the key type and the length
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<MetadataAddress, ContractError> {
+        let key_type = match bytes.first() {
+            None => {
+                return Err(InvalidMetadataAddress {
+                    message: "address bytes cannot be empty".to_string(),
+                })
+            }
+            Some(byte) => KeyType::from_byte(*byte)?,
+        };
+        if bytes.len() != key_type.address_length() {
+            return Err(InvalidMetadataAddress {
+                message: format!(
+                    "expected {} bytes for a {} address but found {}",
+                    key_type.address_length(),
+                    key_type.to_str(),
+                    bytes.len()
+                ),
+            });
+        }
+        let bech32 = bech32::encode(key_type.to_str(), bytes.to_base32(), Variant::Bech32)
+            .map_err(|e| InvalidMetadataAddress {
+                message: format!("{e:?}"),
+            })?;
+        Ok(MetadataAddress {
+            bech32,
+            bytes,
+            key_type,
+        })
+    }
+
+    /// Decodes a bech32 metadata address, checking that the prefix matches the key type
+    pub fn from_bech32(address: &str) -> Result<MetadataAddress, ContractError> {
+        let (hrp, data, variant) = bech32::decode(address).map_err(|e| InvalidMetadataAddress {
+            message: format!("{e:?}"),
+        })?;
+        if variant != Variant::Bech32 {
+            return Err(InvalidMetadataAddress {
+                message: "metadata addresses must use the bech32 variant".to_string(),
+            });
+        }
+        let bytes = Vec::<u8>::from_base32(&data).map_err(|e| InvalidMetadataAddress {
+            message: format!("{e:?}"),
+        })?;
+        let metadata_address = Self::from_bytes(bytes)?;
+        if hrp != metadata_address.key_type.to_str() {
+            return Err(InvalidMetadataAddress {
+                message: format!(
+                    "prefix {hrp:?} does not match the {} key type",
+                    metadata_address.key_type.to_str()
+                ),
+            });
+        }
+        Ok(metadata_address)
+    }
+
+    /// The scope uuid of a scope, session or record address, or the specification uuid of a
+    /// specification address
+    pub fn primary_uuid(&self) -> Uuid {
+        let mut uuid_bytes = [0u8; ID_LENGTH];
+        uuid_bytes.copy_from_slice(&self.bytes[1..1 + ID_LENGTH]);
+        Uuid::from_bytes(uuid_bytes)
+    }
+
+    fn from_parts(
+        key_type: KeyType,
+        primary_uuid: Uuid,
+        secondary_part: &[u8],
+    ) -> Result<MetadataAddress, ContractError> {
+        let bytes = [key_type as u8]
+            .iter()
+            .chain(primary_uuid.as_bytes().iter())
+            .chain(secondary_part.iter())
+            .cloned()
+            .collect::<Vec<u8>>();
+        Self::from_bytes(bytes)
+    }
+}
+
+/// Record and record specification names are trimmed and lowercased before they are hashed
+fn name_hash(name: &str) -> Result<Vec<u8>, ContractError> {
+    let normalized_name = name.trim().to_lowercase();
+    if normalized_name.is_empty() {
+        return Err(InvalidMetadataAddress {
+            message: "name cannot be empty".to_string(),
+        });
+    }
+    Ok(Sha256::digest(normalized_name.as_bytes())[..ID_LENGTH].to_vec())
+}
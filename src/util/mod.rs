@@ -1 +1,3 @@
 pub mod helpers;
+pub mod history;
+pub mod metadata_address;
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 
 pub const CRATE_NAME: &str = env!("CARGO_CRATE_NAME");
 pub const PACKAGE_VERSION: &str = env!("CARGO_PKG_VERSION");
-const VERSION_INFO_NAMESPACE: &str = "version_info";
+pub const VERSION_INFO_NAMESPACE: &str = "version_info";
 const VERSION_INFO: Item<VersionInfoV1> = Item::new(VERSION_INFO_NAMESPACE);
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]